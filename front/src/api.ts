@@ -0,0 +1,17 @@
+import { env } from "@/env";
+import { traceparent } from "@/trace";
+
+/**
+ * API サーバーへの fetch。リクエストごとに `traceparent` を付けてトレースを伝播する。
+ * Next.js はデータキャッシュのキーから `traceparent` を除くので、`revalidate` はそのまま効く。
+ */
+export function apiFetch(
+  path: string,
+  init: RequestInit = {},
+): Promise<Response> {
+  const headers = new Headers(init.headers);
+  if (!headers.has("traceparent")) {
+    headers.set("traceparent", traceparent());
+  }
+  return fetch(`${env.API_URL}${path}`, { ...init, headers });
+}
@@ -1,4 +1,4 @@
-import { env } from "@/env";
+import { apiFetch } from "@/api";
 import { Data } from "@/error";
 import { User as UserType } from "@/types/user";
 import { PageProps } from "@/types/utils";
@@ -8,7 +8,7 @@ import { Suspense } from "react";
 
 async function getUser(id: string): Promise<Data<UserType>> {
   try {
-    const response = await fetch(`${env.API_URL}/users/${id}`, {
+    const response = await apiFetch(`/v1/users/${id}`, {
       next: {
         revalidate: 60,
       },
@@ -1,4 +1,4 @@
-import { env } from "@/env";
+import { apiFetch } from "@/api";
 import { Data } from "@/error";
 import { User } from "@/types/user";
 import { Card, Heading, Spinner, Text } from "@radix-ui/themes";
@@ -7,7 +7,7 @@ import { Suspense } from "react";
 
 async function getUsers(): Promise<Data<User[]>> {
   try {
-    const response = await fetch(`${env.API_URL}/users`, {
+    const response = await apiFetch("/v1/users", {
       next: {
         revalidate: 60,
       },
@@ -6,11 +6,21 @@ export const problemDetailsType = {
   INVALID_JSON: "https://example.com/problems/invalid-json",
   METHOD_NOT_ALLOWED: "https://example.com/problems/method-not-allowed",
   BAD_REQUEST: "https://example.com/problems/bad-request",
+  NOT_ACCEPTABLE: "https://example.com/problems/not-acceptable",
   UNSUPPORTED_MEDIA_TYPE: "https://example.com/problems/unsupported-media-type",
   CONFLICT: "https://example.com/problems/conflict",
+  PRECONDITION_FAILED: "https://example.com/problems/precondition-failed",
   UNAUTHORIZED: "https://example.com/problems/unauthorized",
   FORBIDDEN: "https://example.com/problems/forbidden",
   INTERNAL_SERVER_ERROR: "https://example.com/problems/internal-server-error",
+  CSRF: "https://example.com/problems/csrf",
+  TOO_MANY_REQUESTS: "https://example.com/problems/too-many-requests",
+  INVALID_VERIFICATION_TOKEN:
+    "https://example.com/problems/invalid-verification-token",
+  EMAIL_ALREADY_VERIFIED: "https://example.com/problems/email-already-verified",
+  IDEMPOTENCY_KEY_MISMATCH:
+    "https://example.com/problems/idempotency-key-mismatch",
+  IDEMPOTENCY_KEY_IN_USE: "https://example.com/problems/idempotency-key-in-use",
 } as const;
 
 export type ProblemDetails = {
@@ -0,0 +1,12 @@
+import { describe, it, expect } from "vitest";
+import { traceparent } from "./trace";
+
+describe("traceparent", () => {
+  it("should follow the W3C Trace Context format", () => {
+    expect(traceparent()).toMatch(/^00-[0-9a-f]{32}-[0-9a-f]{16}-01$/);
+  });
+
+  it("should start a new trace on every call", () => {
+    expect(traceparent()).not.toBe(traceparent());
+  });
+});
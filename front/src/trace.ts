@@ -0,0 +1,16 @@
+/**
+ * W3C Trace Context の `traceparent` ヘッダー値を生成する。
+ * API サーバーはこのヘッダーを受け取ると、リクエストのスパンを同じトレースに繋げる。
+ */
+export function traceparent(): string {
+  const traceId = randomHex(16);
+  const spanId = randomHex(8);
+  return `00-${traceId}-${spanId}-01`;
+}
+
+function randomHex(bytes: number): string {
+  const values = crypto.getRandomValues(new Uint8Array(bytes));
+  return Array.from(values, (value) =>
+    value.toString(16).padStart(2, "0"),
+  ).join("");
+}
@@ -0,0 +1,71 @@
+// Generated by `task types` from `application::request_response` and
+// `application::problem_type`; do not edit.
+
+export type JsonValue = number | string | boolean | Array<JsonValue> | { [key in string]?: JsonValue } | null;
+
+/**
+ * An RFC 9457 problem, as every error response carries it with `application/problem+json`.
+ */
+export type ProblemResponseBody = { 
+/**
+ * Identifies the kind of problem with one of a fixed set of URIs.
+ */
+type: string, title: string, 
+/**
+ * Left out by the server, which only sends the status line; other producers may set it.
+ */
+status?: number, detail?: string, instance?: string, } & ({ [key in string]?: number | string | boolean | Array<JsonValue> | { [key in string]?: JsonValue } | null });
+
+/**
+ * Every `type` a problem from the server can carry.
+ */
+export type ProblemType = "https://example.com/problems/validate" | "https://example.com/problems/duplicate" | "https://example.com/problems/not-found" | "https://example.com/problems/invalid-json" | "https://example.com/problems/method-not-allowed" | "https://example.com/problems/bad-request" | "https://example.com/problems/not-acceptable" | "https://example.com/problems/unsupported-media-type" | "https://example.com/problems/conflict" | "https://example.com/problems/precondition-failed" | "https://example.com/problems/unauthorized" | "https://example.com/problems/forbidden" | "https://example.com/problems/internal-server-error" | "https://example.com/problems/csrf" | "https://example.com/problems/too-many-requests" | "https://example.com/problems/invalid-verification-token" | "https://example.com/problems/email-already-verified" | "https://example.com/problems/idempotency-key-mismatch" | "https://example.com/problems/idempotency-key-in-use";
+
+export type CsrfTokenResponseBody = { token: string, };
+
+export type CreateUserRequestBody = { name: string, email: string, };
+
+export type CreateUserResponseBody = { id: string, name: string, email: string, email_verified_at: string | null, };
+
+export type FindAllUserResponseBodyItem = { id: string, name: string, email: string, email_verified_at: string | null, };
+
+export type FindAllUserResponseBody = Array<FindAllUserResponseBodyItem>;
+
+export type FindUserByIdResponseBody = { id: string, name: string, email: string, email_verified_at: string | null, };
+
+export type ImportUsersMode = "transaction" | "per_row";
+
+export type ImportUserRowStatus = "created" | "invalid" | "duplicate" | "not_imported" | "failed";
+
+export type ImportUsersResponseBodyRow = { 
+/**
+ * 1-based position among the rows of the import, not counting a CSV header.
+ */
+row: number, status: ImportUserRowStatus, 
+/**
+ * The ID of the created user.
+ */
+id: string | null, 
+/**
+ * Messages per field for an `invalid` row, or under `row` if it could not be read.
+ */
+errors: { [key in string]?: Array<string> } | null, };
+
+export type ImportUsersResponseBody = { mode: ImportUsersMode, created: number, 
+/**
+ * Rows that were not created, whatever the reason.
+ */
+failed: number, rows: Array<ImportUsersResponseBodyRow>, };
+
+/**
+ * Replaces the user's name and email; changing the email resets its verification.
+ */
+export type UpdateUserRequestBody = { name: string, email: string, };
+
+export type UpdateUserResponseBody = { id: string, name: string, email: string, email_verified_at: string | null, };
+
+export type VerifyEmailResponseBody = { id: string, email_verified_at: string | null, };
+
+export type FindAuditEventsResponseBodyItem = { id: string, action: string, actor_id: string | null, subject_id: string | null, ip: string | null, user_agent: string | null, occurred_at: string, };
+
+export type FindAuditEventsResponseBody = { items: Array<FindAuditEventsResponseBodyItem>, next_cursor: number | null, };
@@ -1,5 +1,3 @@
-export type User = {
-  id: string;
-  name: string;
-  email: string;
-};
+import type { FindUserByIdResponseBody } from "./api";
+
+export type User = FindUserByIdResponseBody;
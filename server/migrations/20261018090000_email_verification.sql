@@ -0,0 +1,12 @@
+ALTER TABLE "user" ADD COLUMN email_verified_at timestamptz;
+
+CREATE TABLE email_verification_token (
+  id UUID PRIMARY KEY,
+  user_id UUID NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  token_hash VARCHAR(64) NOT NULL UNIQUE,
+  expires_at timestamptz NOT NULL,
+  consumed_at timestamptz,
+  created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX email_verification_token_user_id_idx ON email_verification_token (user_id);
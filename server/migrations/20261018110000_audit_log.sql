@@ -0,0 +1,30 @@
+CREATE TABLE audit_log (
+  seq BIGSERIAL PRIMARY KEY,
+  id UUID NOT NULL UNIQUE,
+  action VARCHAR(50) NOT NULL,
+  actor_id UUID,
+  subject_id UUID,
+  ip VARCHAR(45),
+  user_agent VARCHAR(512),
+  occurred_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX audit_log_action_idx ON audit_log (action, seq);
+CREATE INDEX audit_log_actor_id_idx ON audit_log (actor_id, seq);
+CREATE INDEX audit_log_subject_id_idx ON audit_log (subject_id, seq);
+
+-- Entries outlive the users they mention and are never rewritten, so there are no foreign keys
+-- and updates and deletes are rejected outright.
+CREATE FUNCTION audit_log_reject_change() RETURNS trigger AS $$
+BEGIN
+  RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_append_only
+  BEFORE UPDATE OR DELETE ON audit_log
+  FOR EACH STATEMENT EXECUTE FUNCTION audit_log_reject_change();
+
+CREATE TRIGGER audit_log_no_truncate
+  BEFORE TRUNCATE ON audit_log
+  FOR EACH STATEMENT EXECUTE FUNCTION audit_log_reject_change();
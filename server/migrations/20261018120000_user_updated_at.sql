@@ -0,0 +1,5 @@
+-- `updated_at` is read back into the domain model and exposed through ETags, so it must be
+-- an unambiguous instant like the other timestamps.
+ALTER TABLE "user"
+  ALTER COLUMN created_at TYPE timestamptz USING created_at AT TIME ZONE 'UTC',
+  ALTER COLUMN updated_at TYPE timestamptz USING updated_at AT TIME ZONE 'UTC';
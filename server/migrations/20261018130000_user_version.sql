@@ -0,0 +1,3 @@
+-- Incremented by every write; writes name the version they read in their `WHERE` clause so a
+-- concurrent change makes them match no row instead of being overwritten.
+ALTER TABLE "user" ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
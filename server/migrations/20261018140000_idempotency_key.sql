@@ -0,0 +1,15 @@
+-- Responses to requests sent with an `Idempotency-Key`, replayed when the request is retried.
+-- A row without a response belongs to a request that is still being processed; its short
+-- `expires_at` lets the key be claimed again if that request never completes.
+CREATE TABLE idempotency_key (
+  key VARCHAR(255) NOT NULL,
+  route VARCHAR(512) NOT NULL,
+  principal VARCHAR(80) NOT NULL,
+  request_hash VARCHAR(64) NOT NULL,
+  response_status SMALLINT,
+  response_headers JSONB,
+  response_body BYTEA,
+  created_at timestamptz NOT NULL DEFAULT now(),
+  expires_at timestamptz NOT NULL,
+  PRIMARY KEY (key, route, principal)
+);
@@ -0,0 +1,2 @@
+-- Lets the periodic purge of expired idempotency keys find them without scanning the table.
+CREATE INDEX idempotency_key_expires_at_idx ON idempotency_key (expires_at);
@@ -0,0 +1,81 @@
+//! Appends domain events to the audit log in the transaction of the write they describe, so an
+//! entry exists exactly when the change committed and can't be lost to a failure afterwards.
+
+use domain::{
+    entity::audit_event::AuditEvent, event::domain_event::DomainEvent,
+    interface::unit_of_work_interface::TransactionInterface,
+};
+
+use crate::request_context;
+
+/// The entry for `event`, attributed to the actor, IP and user agent of the current request.
+pub fn audit_event(event: &DomainEvent) -> AuditEvent {
+    let audit_event = AuditEvent::from_event(event, &request_context::current());
+    tracing::info!(
+        target: "audit",
+        action = %audit_event.action,
+        user_id = %event.user_id(),
+        occurred_at = %audit_event.occurred_at,
+        "domain event"
+    );
+    audit_event
+}
+
+/// Appends the entry for `event` within `tx`; it is written only if `tx` commits.
+pub async fn record(
+    tx: &(dyn TransactionInterface + Send + Sync),
+    event: &DomainEvent,
+) -> anyhow::Result<()> {
+    tx.audit_log_repository().append(&audit_event(event)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use domain::{
+        entity::{
+            audit_event::AuditAction, request_context::RequestContext,
+            value_object::user_id::UserId,
+        },
+        interface::{
+            audit_log_repository_interface::MockAuditLogRepositoryInterface,
+            unit_of_work_interface::MockTransactionInterface,
+        },
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_appends_event_with_request_context() {
+        let user_id = UserId::new();
+        let expected_user_id = user_id.clone();
+        let mut mocked_audit_log_repository = MockAuditLogRepositoryInterface::new();
+        mocked_audit_log_repository
+            .expect_append()
+            .withf(move |audit_event| {
+                audit_event.action == AuditAction::UserCreated
+                    && audit_event.subject_id.as_ref() == Some(&expected_user_id)
+                    && audit_event.ip.as_deref() == Some("203.0.113.7")
+                    && audit_event.user_agent.as_deref() == Some("curl/8.0")
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        let mocked_audit_log_repository = Arc::new(mocked_audit_log_repository);
+        let mut tx = MockTransactionInterface::new();
+        tx.expect_audit_log_repository()
+            .returning(move || mocked_audit_log_repository.clone());
+        let context = RequestContext {
+            actor_id: None,
+            ip: Some("203.0.113.7".to_owned()),
+            user_agent: Some("curl/8.0".to_owned()),
+        };
+
+        request_context::scope(context, async {
+            record(&tx, &DomainEvent::user_created(user_id))
+                .await
+                .unwrap();
+        })
+        .await;
+    }
+}
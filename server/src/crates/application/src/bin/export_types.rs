@@ -0,0 +1,15 @@
+//! Writes the TypeScript declarations for the API bodies, to the front end's `src/types/api.d.ts`
+//! unless another path is given.
+
+use application::typescript::{DECLARATIONS_PATH, declarations};
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DECLARATIONS_PATH.to_owned());
+
+    std::fs::write(&path, declarations())?;
+    println!("wrote {}", path);
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! Entity tags (RFC 9110 §8.8.3) of user representations, and the `If-Match` and
+//! `If-None-Match` conditions they are compared against.
+
+use domain::entity::user::User;
+
+/// A strong tag derived from the user's version, so it changes whenever the user is written.
+pub fn user_entity_tag(user: &User) -> String {
+    format!("\"{}\"", user.version)
+}
+
+/// The parsed value of an `If-Match` or `If-None-Match` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityTagCondition {
+    /// `*`, which matches any current representation.
+    Any,
+    Tags(Vec<String>),
+}
+
+impl EntityTagCondition {
+    /// Parses a comma-separated list of tags, or `*`. Tags are kept as sent, quotes included.
+    pub fn parse(value: &str) -> Self {
+        if value.trim() == "*" {
+            return EntityTagCondition::Any;
+        }
+
+        EntityTagCondition::Tags(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_owned)
+                .collect(),
+        )
+    }
+
+    /// The comparison `If-Match` uses: weak tags never match.
+    pub fn matches_strong(&self, tag: &str) -> bool {
+        match self {
+            EntityTagCondition::Any => true,
+            EntityTagCondition::Tags(tags) => tags.iter().any(|t| !is_weak(t) && t == tag),
+        }
+    }
+
+    /// The comparison `If-None-Match` uses: the `W/` prefix is ignored.
+    pub fn matches_weak(&self, tag: &str) -> bool {
+        match self {
+            EntityTagCondition::Any => true,
+            EntityTagCondition::Tags(tags) => tags.iter().any(|t| opaque(t) == opaque(tag)),
+        }
+    }
+}
+
+fn is_weak(tag: &str) -> bool {
+    tag.starts_with("W/")
+}
+
+fn opaque(tag: &str) -> &str {
+    tag.trim_start_matches("W/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_tag_changes_when_the_user_is_written() {
+        let mut user = User::new("Test User".into(), "test@example.com".into());
+        let before = user_entity_tag(&user);
+
+        user.version += 1;
+
+        assert_ne!(user_entity_tag(&user), before);
+        assert!(before.starts_with('"') && before.ends_with('"'));
+    }
+
+    #[test]
+    fn test_parse_and_compare() {
+        let condition = EntityTagCondition::parse(r#""a", W/"b""#);
+
+        assert_eq!(
+            condition,
+            EntityTagCondition::Tags(vec![r#""a""#.to_owned(), r#"W/"b""#.to_owned()])
+        );
+        assert_eq!(
+            [
+                condition.matches_strong(r#""a""#),
+                condition.matches_strong(r#""b""#),
+                condition.matches_weak(r#""b""#),
+                condition.matches_weak(r#""c""#),
+            ],
+            [true, false, true, false]
+        );
+        assert!(EntityTagCondition::parse(" * ").matches_strong(r#""c""#));
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use domain::{
+    event::domain_event::DomainEvent,
+    interface::{
+        event_publisher_interface::EventPublisherInterface,
+        event_subscriber_interface::EventSubscriberInterface,
+    },
+};
+
+/// Hands every event to each subscriber in registration order, within the publishing task.
+/// All subscribers run even if one fails; the first failure is returned.
+#[derive(Clone, Default)]
+pub struct InProcessEventPublisher {
+    subscribers: Vec<Arc<dyn EventSubscriberInterface + Send + Sync>>,
+}
+
+impl InProcessEventPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(
+        mut self,
+        subscriber: impl EventSubscriberInterface + Send + Sync + 'static,
+    ) -> Self {
+        self.subscribers.push(Arc::new(subscriber));
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisherInterface for InProcessEventPublisher {
+    #[tracing::instrument(
+        name = "InProcessEventPublisher::publish",
+        skip_all,
+        fields(topic = event.topic(), user_id = %event.user_id())
+    )]
+    async fn publish(&self, event: &DomainEvent) -> Result<(), anyhow::Error> {
+        let mut first_error = None;
+        for subscriber in &self.subscribers {
+            if let Err(e) = subscriber.handle(event).await {
+                tracing::warn!(subscriber = subscriber.name(), error = %e, "event subscriber failed");
+                first_error.get_or_insert(e);
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::{
+        entity::value_object::user_id::UserId,
+        interface::event_subscriber_interface::MockEventSubscriberInterface,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_reaches_every_subscriber() -> anyhow::Result<()> {
+        let event = DomainEvent::user_created(UserId::new());
+        let mut first = MockEventSubscriberInterface::new();
+        let mut second = MockEventSubscriberInterface::new();
+        first.expect_name().return_const("first");
+        first
+            .expect_handle()
+            .withf({
+                let event = event.clone();
+                move |handled| *handled == event
+            })
+            .times(1)
+            .returning(|_event| Ok(()));
+        second.expect_name().return_const("second");
+        second.expect_handle().times(1).returning(|_event| Ok(()));
+
+        let publisher = InProcessEventPublisher::new()
+            .subscribe(first)
+            .subscribe(second);
+        publisher.publish(&event).await?;
+
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failing_subscriber_does_not_stop_the_others() {
+        let mut failing = MockEventSubscriberInterface::new();
+        let mut other = MockEventSubscriberInterface::new();
+        failing.expect_name().return_const("failing");
+        failing
+            .expect_handle()
+            .returning(|_event| Err(anyhow::Error::msg("boom")));
+        other.expect_name().return_const("other");
+        other.expect_handle().times(1).returning(|_event| Ok(()));
+
+        let publisher = InProcessEventPublisher::new()
+            .subscribe(failing)
+            .subscribe(other);
+        let result = publisher
+            .publish(&DomainEvent::user_created(UserId::new()))
+            .await;
+
+        assert_eq!(result.unwrap_err().to_string(), "boom");
+    }
+}
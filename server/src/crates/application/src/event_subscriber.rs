@@ -0,0 +1 @@
+pub mod mail_event_subscriber;
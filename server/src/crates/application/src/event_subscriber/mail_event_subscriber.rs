@@ -0,0 +1,155 @@
+use domain::{
+    error::email_verification_error::EmailVerificationError,
+    event::domain_event::DomainEvent,
+    interface::{
+        email_verification_token_repository_interface::EmailVerificationTokenRepositoryInterface,
+        event_subscriber_interface::EventSubscriberInterface, mailer_interface::MailerInterface,
+        user_repository_interface::UserRepositoryInterface,
+    },
+};
+
+use crate::usecase::issue_email_verification::IssueEmailVerificationUsecase;
+
+/// Sends the verification email when a user is created.
+pub struct MailEventSubscriber<T, U, V>
+where
+    T: UserRepositoryInterface,
+    U: EmailVerificationTokenRepositoryInterface,
+    V: MailerInterface,
+{
+    issue_email_verification: IssueEmailVerificationUsecase<T, U, V>,
+}
+
+impl<T, U, V> MailEventSubscriber<T, U, V>
+where
+    T: UserRepositoryInterface,
+    U: EmailVerificationTokenRepositoryInterface,
+    V: MailerInterface,
+{
+    pub fn new(issue_email_verification: IssueEmailVerificationUsecase<T, U, V>) -> Self {
+        MailEventSubscriber {
+            issue_email_verification,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, U, V> EventSubscriberInterface for MailEventSubscriber<T, U, V>
+where
+    T: UserRepositoryInterface + Send + Sync,
+    U: EmailVerificationTokenRepositoryInterface + Send + Sync,
+    V: MailerInterface + Send + Sync,
+{
+    fn name(&self) -> &'static str {
+        "mail"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), anyhow::Error> {
+        let DomainEvent::UserCreated(user_created) = event else {
+            return Ok(());
+        };
+
+        match self
+            .issue_email_verification
+            .execute(user_created.user_id.clone())
+            .await
+        {
+            Ok(_) => Ok(()),
+            // A redelivered event for a user who has verified in the meantime has nothing to do.
+            Err(e)
+                if matches!(
+                    e.downcast_ref::<EmailVerificationError>(),
+                    Some(EmailVerificationError::AlreadyVerified)
+                ) =>
+            {
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use domain::{
+        entity::user::User,
+        interface::{
+            email_verification_token_repository_interface::MockEmailVerificationTokenRepositoryInterface,
+            mailer_interface::MockMailerInterface,
+            user_repository_interface::MockUserRepositoryInterface,
+        },
+    };
+
+    use super::*;
+
+    fn subscriber(
+        user: User,
+        mailer: MockMailerInterface,
+    ) -> MailEventSubscriber<
+        MockUserRepositoryInterface,
+        MockEmailVerificationTokenRepositoryInterface,
+        MockMailerInterface,
+    > {
+        let mut mocked_user_repository = MockUserRepositoryInterface::new();
+        let mut mocked_token_repository = MockEmailVerificationTokenRepositoryInterface::new();
+        mocked_user_repository
+            .expect_find_by_id()
+            .returning(move |_user_id| Ok(user.clone()));
+        mocked_token_repository
+            .expect_create()
+            .returning(|_token| Ok(()));
+
+        MailEventSubscriber::new(IssueEmailVerificationUsecase::new(
+            mocked_user_repository,
+            mocked_token_repository,
+            mailer,
+            "https://example.com/verify".into(),
+            Duration::hours(24),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_user_created_sends_verification_mail() -> anyhow::Result<()> {
+        let user = User::new("Test User".into(), "test@example.com".into());
+        let mut mocked_mailer = MockMailerInterface::new();
+        mocked_mailer
+            .expect_send()
+            .withf(|mail| mail.to == "test@example.com")
+            .times(1)
+            .returning(|_mail| Ok(()));
+
+        subscriber(user.clone(), mocked_mailer)
+            .handle(&DomainEvent::user_created(user.id))
+            .await?;
+
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_user_created_for_verified_user_is_ignored() -> anyhow::Result<()> {
+        let mut user = User::new("Test User".into(), "test@example.com".into());
+        user.email_verified_at = Some(Utc::now());
+        let mut mocked_mailer = MockMailerInterface::new();
+        mocked_mailer.expect_send().never();
+
+        subscriber(user.clone(), mocked_mailer)
+            .handle(&DomainEvent::user_created(user.id))
+            .await?;
+
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_other_events_are_ignored() -> anyhow::Result<()> {
+        let mut mocked_mailer = MockMailerInterface::new();
+        mocked_mailer.expect_send().never();
+        let user = User::new("Test User".into(), "test@example.com".into());
+
+        subscriber(user.clone(), mocked_mailer)
+            .handle(&DomainEvent::user_updated(user.id))
+            .await?;
+
+        anyhow::Ok(())
+    }
+}
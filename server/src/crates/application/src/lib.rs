@@ -1,2 +1,11 @@
+pub mod audit_log;
+pub mod entity_tag;
+pub mod event_publisher;
+pub mod event_subscriber;
+pub mod mail_template;
+pub mod outbox_handler;
+pub mod problem_type;
+pub mod request_context;
 pub mod request_response;
+pub mod typescript;
 pub mod usecase;
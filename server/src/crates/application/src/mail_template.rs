@@ -0,0 +1,141 @@
+use chrono::Duration;
+use domain::entity::mail::Mail;
+
+const EMAIL_VERIFICATION_TEXT: &str = include_str!("../templates/email_verification.txt");
+const EMAIL_VERIFICATION_HTML: &str = include_str!("../templates/email_verification.html");
+
+pub struct EmailVerificationMail<'a> {
+    pub name: &'a str,
+    pub verification_url: &'a str,
+    pub expires_in: Duration,
+}
+
+impl EmailVerificationMail<'_> {
+    pub fn render(&self, to: String) -> Mail {
+        let expires_in = describe_duration(self.expires_in);
+        let values = [
+            ("name", self.name),
+            ("verification_url", self.verification_url),
+            ("expires_in", expires_in.as_str()),
+        ];
+
+        Mail::new(
+            to,
+            "Confirm your email address".to_owned(),
+            render(EMAIL_VERIFICATION_TEXT, &values, |value| value.to_owned()),
+            render(EMAIL_VERIFICATION_HTML, &values, escape_html),
+        )
+    }
+}
+
+/// Replaces every `{{key}}` in `template` in a single pass, passing each value through `escape`
+/// first. Inserted values are never scanned again, so a name such as `{{verification_url}}` is
+/// sent as typed. Unknown keys are left as they are.
+fn render(template: &str, values: &[(&str, &str)], escape: impl Fn(&str) -> String) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start..].find("}}").map(|end| end + 2) else {
+            break;
+        };
+        let placeholder = &rest[start..start + length];
+        let key = &placeholder[2..length - 2];
+        rendered.push_str(&rest[..start]);
+        match values.iter().find(|(name, _)| *name == key) {
+            Some((_, value)) => rendered.push_str(&escape(value)),
+            None => rendered.push_str(placeholder),
+        }
+        rest = &rest[start + length..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Whole hours when the duration is a whole number of them, and minutes rounded up otherwise,
+/// so a link that lasts less than an hour never reads as expiring in "0 hours".
+fn describe_duration(duration: Duration) -> String {
+    let minutes = (duration.num_seconds() + 59) / 60;
+    let (count, unit) = if minutes > 0 && minutes % 60 == 0 {
+        (minutes / 60, "hour")
+    } else {
+        (minutes, "minute")
+    };
+
+    match count {
+        1 => format!("1 {}", unit),
+        count => format!("{} {}s", count, unit),
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_email_verification_mail() {
+        let mail = EmailVerificationMail {
+            name: "<Test User>",
+            verification_url: "https://example.com/verify?token=abc&x=1",
+            expires_in: Duration::hours(24),
+        }
+        .render("test@example.com".into());
+
+        assert_eq!(mail.to, "test@example.com");
+        assert!(mail.text_body.contains("Hi <Test User>,"));
+        assert!(
+            mail.text_body
+                .contains("https://example.com/verify?token=abc&x=1")
+        );
+        assert!(mail.text_body.contains("expires in 24 hours"));
+        assert!(mail.html_body.contains("Hi &lt;Test User&gt;,"));
+        assert!(
+            mail.html_body
+                .contains(r#"href="https://example.com/verify?token=abc&amp;x=1""#)
+        );
+        assert!(!mail.text_body.contains("{{"));
+        assert!(!mail.html_body.contains("{{"));
+    }
+
+    #[test]
+    fn test_render_does_not_expand_placeholders_inside_values() {
+        let mail = EmailVerificationMail {
+            name: "{{verification_url}}",
+            verification_url: "https://example.com/verify?token=abc",
+            expires_in: Duration::hours(24),
+        }
+        .render("test@example.com".into());
+
+        assert!(mail.text_body.contains("Hi {{verification_url}},"));
+        assert_eq!(
+            mail.text_body
+                .matches("https://example.com/verify?token=abc")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_describe_duration() {
+        assert_eq!(describe_duration(Duration::hours(24)), "24 hours");
+        assert_eq!(describe_duration(Duration::hours(1)), "1 hour");
+        assert_eq!(describe_duration(Duration::minutes(90)), "90 minutes");
+        assert_eq!(describe_duration(Duration::minutes(30)), "30 minutes");
+        assert_eq!(describe_duration(Duration::seconds(61)), "2 minutes");
+        assert_eq!(describe_duration(Duration::seconds(1)), "1 minute");
+    }
+}
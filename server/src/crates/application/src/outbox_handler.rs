@@ -0,0 +1,90 @@
+use domain::{
+    entity::outbox_message::OutboxMessage,
+    event::domain_event::DomainEvent,
+    interface::{
+        event_publisher_interface::EventPublisherInterface,
+        outbox_handler_interface::OutboxHandlerInterface,
+    },
+};
+
+/// Relays domain events recorded in the outbox to `publisher` once the write that produced them
+/// has committed.
+pub struct DomainEventOutboxHandler<P>
+where
+    P: EventPublisherInterface,
+{
+    publisher: P,
+}
+
+impl<P> DomainEventOutboxHandler<P>
+where
+    P: EventPublisherInterface,
+{
+    pub fn new(publisher: P) -> Self {
+        DomainEventOutboxHandler { publisher }
+    }
+}
+
+#[async_trait::async_trait]
+impl<P> OutboxHandlerInterface for DomainEventOutboxHandler<P>
+where
+    P: EventPublisherInterface + Send + Sync,
+{
+    fn topics(&self) -> Vec<String> {
+        DomainEvent::TOPICS
+            .iter()
+            .map(|topic| topic.to_string())
+            .collect()
+    }
+
+    async fn handle(&self, message: &OutboxMessage) -> Result<(), anyhow::Error> {
+        let event = DomainEvent::from_payload(&message.topic, message.payload.clone())?;
+        self.publisher.publish(&event).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::{
+        entity::value_object::user_id::UserId,
+        interface::event_publisher_interface::MockEventPublisherInterface,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_outbox_message_is_published_as_event() -> anyhow::Result<()> {
+        let event = DomainEvent::user_created(UserId::new());
+        let mut mocked_publisher = MockEventPublisherInterface::new();
+        mocked_publisher
+            .expect_publish()
+            .withf({
+                let event = event.clone();
+                move |published| *published == event
+            })
+            .times(1)
+            .returning(|_event| Ok(()));
+
+        let handler = DomainEventOutboxHandler::new(mocked_publisher);
+        handler.handle(&OutboxMessage::from_event(&event)).await?;
+
+        assert!(handler.topics().contains(&"user.created".to_owned()));
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_malformed_payload_fails() {
+        let mut mocked_publisher = MockEventPublisherInterface::new();
+        mocked_publisher.expect_publish().never();
+
+        let handler = DomainEventOutboxHandler::new(mocked_publisher);
+        let result = handler
+            .handle(&OutboxMessage::new(
+                "user.created",
+                serde_json::json!({ "unexpected": true }),
+            ))
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,46 @@
+//! `type` URIs of the problems the API returns, shared by the server and its clients.
+
+pub const VALIDATE: &str = "https://example.com/problems/validate";
+pub const DUPLICATE: &str = "https://example.com/problems/duplicate";
+pub const NOT_FOUND: &str = "https://example.com/problems/not-found";
+pub const NO_CONTENT: &str = "https://example.com/problems/no-content";
+pub const INVALID_JSON: &str = "https://example.com/problems/invalid-json";
+pub const METHOD_NOT_ALLOWED: &str = "https://example.com/problems/method-not-allowed";
+pub const BAD_REQUEST: &str = "https://example.com/problems/bad-request";
+pub const NOT_ACCEPTABLE: &str = "https://example.com/problems/not-acceptable";
+pub const UNSUPPORTED_MEDIA_TYPE: &str = "https://example.com/problems/unsupported-media-type";
+pub const CONFLICT: &str = "https://example.com/problems/conflict";
+pub const PRECONDITION_FAILED: &str = "https://example.com/problems/precondition-failed";
+pub const UNAUTHORIZED: &str = "https://example.com/problems/unauthorized";
+pub const FORBIDDEN: &str = "https://example.com/problems/forbidden";
+pub const INTERNAL_SERVER_ERROR: &str = "https://example.com/problems/internal-server-error";
+pub const CSRF: &str = "https://example.com/problems/csrf";
+pub const TOO_MANY_REQUESTS: &str = "https://example.com/problems/too-many-requests";
+pub const INVALID_VERIFICATION_TOKEN: &str =
+    "https://example.com/problems/invalid-verification-token";
+pub const EMAIL_ALREADY_VERIFIED: &str = "https://example.com/problems/email-already-verified";
+pub const IDEMPOTENCY_KEY_MISMATCH: &str = "https://example.com/problems/idempotency-key-mismatch";
+pub const IDEMPOTENCY_KEY_IN_USE: &str = "https://example.com/problems/idempotency-key-in-use";
+
+/// Every problem type a response can currently carry, as listed in the OpenAPI document.
+pub const ALL: &[&str] = &[
+    VALIDATE,
+    DUPLICATE,
+    NOT_FOUND,
+    INVALID_JSON,
+    METHOD_NOT_ALLOWED,
+    BAD_REQUEST,
+    NOT_ACCEPTABLE,
+    UNSUPPORTED_MEDIA_TYPE,
+    CONFLICT,
+    PRECONDITION_FAILED,
+    UNAUTHORIZED,
+    FORBIDDEN,
+    INTERNAL_SERVER_ERROR,
+    CSRF,
+    TOO_MANY_REQUESTS,
+    INVALID_VERIFICATION_TOKEN,
+    EMAIL_ALREADY_VERIFIED,
+    IDEMPOTENCY_KEY_MISMATCH,
+    IDEMPOTENCY_KEY_IN_USE,
+];
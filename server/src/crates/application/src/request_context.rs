@@ -0,0 +1,44 @@
+use std::future::Future;
+
+use domain::entity::request_context::RequestContext;
+
+tokio::task_local! {
+    static REQUEST_CONTEXT: RequestContext;
+}
+
+/// Runs `future` with `context` as the current request context, so anything it calls can record
+/// who made the request without threading it through every usecase.
+pub async fn scope<F>(context: RequestContext, future: F) -> F::Output
+where
+    F: Future,
+{
+    REQUEST_CONTEXT.scope(context, future).await
+}
+
+/// The context of the request being served, or an empty one outside a request such as in the
+/// outbox dispatcher.
+pub fn current() -> RequestContext {
+    REQUEST_CONTEXT.try_with(Clone::clone).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_current_returns_scoped_context() {
+        let context = RequestContext {
+            ip: Some("203.0.113.7".to_owned()),
+            ..RequestContext::default()
+        };
+
+        let current = scope(context.clone(), async { current() }).await;
+
+        assert_eq!(current, context);
+    }
+
+    #[tokio::test]
+    async fn test_current_outside_a_request_is_empty() {
+        assert_eq!(current(), RequestContext::default());
+    }
+}
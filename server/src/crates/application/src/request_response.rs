@@ -1,5 +1,19 @@
 pub mod create_user_request;
 pub mod create_user_response;
+pub mod csrf_token_response;
+pub mod delete_user_request;
+pub mod export_users_request;
+pub mod export_users_response;
+pub mod find_audit_events_request;
+pub mod find_audit_events_response;
 pub mod find_all_user_response;
 pub mod find_user_by_id_request;
 pub mod find_user_by_id_response;
+pub mod import_users_request;
+pub mod import_users_response;
+pub mod issue_email_verification_request;
+pub mod problem_response;
+pub mod update_user_request;
+pub mod update_user_response;
+pub mod verify_email_request;
+pub mod verify_email_response;
@@ -1,19 +1,33 @@
+use std::fmt;
+
+use domain::redact::Redacted;
 use validator::Validate;
 
 use crate::usecase::create_user::CreateUserInput;
 
-#[derive(Debug, serde::Deserialize, serde::Serialize, Validate)]
+#[derive(serde::Deserialize, serde::Serialize, Validate, utoipa::ToSchema, ts_rs::TS)]
 pub struct CreateUserRequestBody {
     #[validate(length(
         min = 2,
-        max = 50,
-        message = "Name must be between 2 and 50 characters"
+        max = 40,
+        message = "Name must be between 2 and 40 characters"
     ))]
+    #[schema(min_length = 2, max_length = 40)]
     pub name: String,
     #[validate(email)]
+    #[schema(format = Email)]
     pub email: String,
 }
 
+impl fmt::Debug for CreateUserRequestBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateUserRequestBody")
+            .field("name", &self.name)
+            .field("email", &Redacted)
+            .finish()
+    }
+}
+
 impl std::convert::From<CreateUserRequestBody> for CreateUserInput {
     fn from(CreateUserRequestBody { name, email }: CreateUserRequestBody) -> Self {
         CreateUserInput::new(name, email)
@@ -51,7 +65,7 @@ mod tests {
     #[test]
     fn test_max_name_length_is_valid() {
         let req = CreateUserRequestBody {
-            name: "A".repeat(50),
+            name: "A".repeat(40),
             email: "test@example.com".to_string(),
         };
 
@@ -61,7 +75,7 @@ mod tests {
     #[test]
     fn test_name_exceeding_max_length_fails_validation() {
         let req = CreateUserRequestBody {
-            name: "A".repeat(51),
+            name: "A".repeat(41),
             email: "test@example.com".to_string(),
         };
 
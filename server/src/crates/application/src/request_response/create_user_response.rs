@@ -1,10 +1,15 @@
+use chrono::{DateTime, Utc};
+
 use crate::usecase::create_user::CreateUserOutput;
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, utoipa::ToSchema, ts_rs::TS)]
 pub struct CreateUserResponseBody {
+    #[schema(format = Uuid)]
     pub id: String,
     pub name: String,
+    #[schema(format = Email)]
     pub email: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
 }
 
 impl std::convert::From<CreateUserOutput> for CreateUserResponseBody {
@@ -13,6 +18,7 @@ impl std::convert::From<CreateUserOutput> for CreateUserResponseBody {
             id: create_user_output.id.0.to_string(),
             name: create_user_output.name,
             email: create_user_output.email,
+            email_verified_at: create_user_output.email_verified_at,
         }
     }
 }
@@ -29,6 +35,9 @@ mod tests {
             id: id.clone(),
             name: "Test User".to_string(),
             email: "test@example.com".to_string(),
+            email_verified_at: None,
+            updated_at: Utc::now(),
+            version: 1,
         };
 
         let response: CreateUserResponseBody = output.into();
@@ -36,6 +45,7 @@ mod tests {
         assert_eq!(response.id, id.0.to_string());
         assert_eq!(response.name, "Test User");
         assert_eq!(response.email, "test@example.com");
+        assert_eq!(response.email_verified_at, None);
     }
 
     #[test]
@@ -44,10 +54,11 @@ mod tests {
             id: "79ca0feb-84f2-4e75-ae07-fc0dd877f9ce".to_string(),
             name: "Test User".to_string(),
             email: "test@example.com".to_string(),
+            email_verified_at: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
-        let expected = r#"{"id":"79ca0feb-84f2-4e75-ae07-fc0dd877f9ce","name":"Test User","email":"test@example.com"}"#;
+        let expected = r#"{"id":"79ca0feb-84f2-4e75-ae07-fc0dd877f9ce","name":"Test User","email":"test@example.com","email_verified_at":null}"#;
         assert_eq!(json, expected);
     }
 }
@@ -0,0 +1,32 @@
+use std::fmt;
+
+use domain::redact::Redacted;
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema, ts_rs::TS)]
+pub struct CsrfTokenResponseBody {
+    pub token: String,
+}
+
+impl fmt::Debug for CsrfTokenResponseBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CsrfTokenResponseBody")
+            .field("token", &Redacted)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_to_json() {
+        let response = CsrfTokenResponseBody {
+            token: "abc123".to_string(),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert_eq!(json, r#"{"token":"abc123"}"#);
+        assert!(!format!("{:?}", response).contains("abc123"));
+    }
+}
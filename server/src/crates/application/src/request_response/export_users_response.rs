@@ -0,0 +1,154 @@
+use domain::entity::user::User;
+
+use crate::request_response::{
+    export_users_request::ExportUsersFormat, find_all_user_response::FindAllUserResponseBodyItem,
+};
+
+/// The CSV columns, in the order the fields of [`FindAllUserResponseBodyItem`] are serialized.
+const CSV_COLUMNS: [&str; 4] = ["id", "name", "email", "email_verified_at"];
+
+/// Leading characters that make a spreadsheet evaluate a cell as a formula.
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Writes an export one user at a time, each as the item `GET /users` would return for it.
+#[derive(Debug, Clone, Copy)]
+pub struct ExportUsersEncoder {
+    format: ExportUsersFormat,
+}
+
+impl ExportUsersEncoder {
+    pub fn new(format: ExportUsersFormat) -> Self {
+        ExportUsersEncoder { format }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self.format {
+            ExportUsersFormat::Csv => "text/csv; charset=utf-8",
+            ExportUsersFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self.format {
+            ExportUsersFormat::Csv => "users.csv",
+            ExportUsersFormat::Ndjson => "users.ndjson",
+        }
+    }
+
+    /// What comes before the first user: the header line of a CSV export, nothing otherwise.
+    pub fn header(&self) -> anyhow::Result<Vec<u8>> {
+        match self.format {
+            ExportUsersFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                writer.write_record(CSV_COLUMNS)?;
+                Ok(writer.into_inner()?)
+            }
+            ExportUsersFormat::Ndjson => Ok(Vec::new()),
+        }
+    }
+
+    /// One line, terminated by a newline.
+    pub fn encode(&self, user: User) -> anyhow::Result<Vec<u8>> {
+        let item = FindAllUserResponseBodyItem::from(user);
+        match self.format {
+            ExportUsersFormat::Csv => {
+                let item = FindAllUserResponseBodyItem {
+                    name: neutralize_formula(item.name),
+                    email: neutralize_formula(item.email),
+                    ..item
+                };
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(false)
+                    .from_writer(Vec::new());
+                writer.serialize(item)?;
+                Ok(writer.into_inner()?)
+            }
+            ExportUsersFormat::Ndjson => {
+                let mut line = serde_json::to_vec(&item)?;
+                line.push(b'\n');
+                Ok(line)
+            }
+        }
+    }
+}
+
+/// Prefixes a cell that a spreadsheet would run as a formula with `'`, so it is shown as text.
+fn neutralize_formula(cell: String) -> String {
+    if cell.starts_with(FORMULA_PREFIXES) {
+        format!("'{}", cell)
+    } else {
+        cell
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use domain::entity::value_object::user_id::UserId;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn users() -> [User; 2] {
+        let user = |name: &str, email: &str| User {
+            id: UserId::from(Uuid::nil()),
+            ..User::new(name.to_owned(), email.to_owned())
+        };
+        [
+            User {
+                email_verified_at: Some(Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap()),
+                ..user("Ada Lovelace", "ada@example.com")
+            },
+            user("Lovelace, Ada", "ada.l@example.com"),
+        ]
+    }
+
+    fn export(format: ExportUsersFormat) -> String {
+        let encoder = ExportUsersEncoder::new(format);
+        let mut out = encoder.header().unwrap();
+        for user in users() {
+            out.extend(encoder.encode(user).unwrap());
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_encode_csv_export() {
+        let expected = "id,name,email,email_verified_at\n\
+            00000000-0000-0000-0000-000000000000,Ada Lovelace,ada@example.com,2025-01-02T03:04:05Z\n\
+            00000000-0000-0000-0000-000000000000,\"Lovelace, Ada\",ada.l@example.com,\n";
+
+        assert_eq!(export(ExportUsersFormat::Csv), expected);
+    }
+
+    #[test]
+    fn test_encode_ndjson_export() {
+        let expected = "{\"id\":\"00000000-0000-0000-0000-000000000000\",\"name\":\"Ada Lovelace\",\"email\":\"ada@example.com\",\"email_verified_at\":\"2025-01-02T03:04:05Z\"}\n\
+            {\"id\":\"00000000-0000-0000-0000-000000000000\",\"name\":\"Lovelace, Ada\",\"email\":\"ada.l@example.com\",\"email_verified_at\":null}\n";
+
+        assert_eq!(export(ExportUsersFormat::Ndjson), expected);
+    }
+
+    #[test]
+    fn test_encode_csv_export_neutralizes_formulas() {
+        let encoder = ExportUsersEncoder::new(ExportUsersFormat::Csv);
+        let user = User {
+            id: UserId::from(Uuid::nil()),
+            ..User::new(
+                "=HYPERLINK(\"https://example.com\")".to_owned(),
+                "@evil@example.com".to_owned(),
+            )
+        };
+
+        let line = String::from_utf8(encoder.encode(user).unwrap()).unwrap();
+
+        assert_eq!(
+            line,
+            "00000000-0000-0000-0000-000000000000,\"'=HYPERLINK(\"\"https://example.com\"\")\",'@evil@example.com,\n"
+        );
+        for cell in ["+1", "-1", "\tA", "\rA"] {
+            assert_eq!(neutralize_formula(cell.to_owned()), format!("'{}", cell));
+        }
+        assert_eq!(neutralize_formula("Ada".to_owned()), "Ada");
+    }
+}
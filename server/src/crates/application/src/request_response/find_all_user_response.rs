@@ -1,13 +1,19 @@
+use chrono::{DateTime, Utc};
 use domain::entity::user::User;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
 
 use crate::usecase::find_all_user::FindAllUserOutput;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, TS)]
 pub struct FindAllUserResponseBodyItem {
+    #[schema(format = Uuid)]
     pub id: String,
     pub name: String,
+    #[schema(format = Email)]
     pub email: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
 }
 
 impl From<User> for FindAllUserResponseBodyItem {
@@ -16,11 +22,12 @@ impl From<User> for FindAllUserResponseBodyItem {
             id: user.id.to_string(),
             name: user.name,
             email: user.email,
+            email_verified_at: user.email_verified_at,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, TS)]
 pub struct FindAllUserResponseBody(pub Vec<FindAllUserResponseBodyItem>);
 
 impl From<FindAllUserOutput> for FindAllUserResponseBody {
@@ -47,11 +54,17 @@ mod tests {
             id: UserId::new(),
             name: "Alice".to_string(),
             email: "alice@example.com".to_string(),
+            email_verified_at: None,
+            updated_at: Utc::now(),
+            version: 1,
         };
         let user2 = User {
             id: UserId::new(),
             name: "Bob".to_string(),
             email: "bob@example.com".to_string(),
+            email_verified_at: None,
+            updated_at: Utc::now(),
+            version: 1,
         };
 
         let output = FindAllUserOutput(vec![user1.clone(), user2.clone()]);
@@ -75,16 +88,18 @@ mod tests {
                 id: "id-1".to_string(),
                 name: "Test User 1".to_string(),
                 email: "user1@example.com".to_string(),
+                email_verified_at: None,
             },
             FindAllUserResponseBodyItem {
                 id: "id-2".to_string(),
                 name: "Test User 2".to_string(),
                 email: "user2@example.com".to_string(),
+                email_verified_at: None,
             },
         ]);
 
         let json = serde_json::to_string(&response).unwrap();
-        let expected = r#"[{"id":"id-1","name":"Test User 1","email":"user1@example.com"},{"id":"id-2","name":"Test User 2","email":"user2@example.com"}]"#;
+        let expected = r#"[{"id":"id-1","name":"Test User 1","email":"user1@example.com","email_verified_at":null},{"id":"id-2","name":"Test User 2","email":"user2@example.com","email_verified_at":null}]"#;
 
         assert_eq!(json, expected);
     }
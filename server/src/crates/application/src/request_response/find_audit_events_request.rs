@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use domain::entity::{
+    audit_event::{AuditAction, AuditEventFilter},
+    value_object::user_id::UserId,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+use validator::{Validate, ValidationError};
+
+use crate::usecase::find_audit_events::FindAuditEventsInput;
+
+pub const DEFAULT_AUDIT_EVENTS_LIMIT: i64 = 50;
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FindAuditEventsRequestQuery {
+    #[validate(custom(function = "validate_action", message = "Unknown audit action"))]
+    #[param(example = "user.created")]
+    pub action: Option<String>,
+    #[param(value_type = Option<uuid::Uuid>)]
+    pub actor_id: Option<UserId>,
+    #[param(value_type = Option<uuid::Uuid>)]
+    pub subject_id: Option<UserId>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// `next_cursor` of the previous page.
+    #[validate(range(min = 1, message = "Cursor must be positive"))]
+    #[param(minimum = 1)]
+    pub before: Option<i64>,
+    #[validate(range(min = 1, max = 200, message = "Limit must be between 1 and 200"))]
+    #[param(minimum = 1, maximum = 200, default = 50)]
+    pub limit: Option<i64>,
+}
+
+fn validate_action(action: &str) -> Result<(), ValidationError> {
+    action
+        .parse::<AuditAction>()
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("action"))
+}
+
+impl From<FindAuditEventsRequestQuery> for FindAuditEventsInput {
+    /// Expects a query that passed validation; an unknown action is dropped rather than matched.
+    fn from(query: FindAuditEventsRequestQuery) -> Self {
+        AuditEventFilter {
+            action: query.action.and_then(|action| action.parse().ok()),
+            actor_id: query.actor_id,
+            subject_id: query.subject_id,
+            from: query.from,
+            to: query.to,
+            before: query.before,
+            limit: query.limit.unwrap_or(DEFAULT_AUDIT_EVENTS_LIMIT),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query() -> FindAuditEventsRequestQuery {
+        FindAuditEventsRequestQuery {
+            action: None,
+            actor_id: None,
+            subject_id: None,
+            from: None,
+            to: None,
+            before: None,
+            limit: None,
+        }
+    }
+
+    #[test]
+    fn test_empty_query_passes_validation_and_uses_default_limit() {
+        let query = query();
+        assert!(query.validate().is_ok());
+
+        let input = FindAuditEventsInput::from(query);
+        assert_eq!(input.limit, DEFAULT_AUDIT_EVENTS_LIMIT);
+        assert_eq!(input.action, None);
+    }
+
+    #[test]
+    fn test_known_action_is_parsed() {
+        let query = FindAuditEventsRequestQuery {
+            action: Some("user.created".to_owned()),
+            ..query()
+        };
+        assert!(query.validate().is_ok());
+
+        let input = FindAuditEventsInput::from(query);
+        assert_eq!(input.action, Some(AuditAction::UserCreated));
+    }
+
+    #[test]
+    fn test_unknown_action_fails_validation() {
+        let query = FindAuditEventsRequestQuery {
+            action: Some("user.renamed".to_owned()),
+            ..query()
+        };
+
+        let errors = query.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("action"));
+    }
+
+    #[test]
+    fn test_limit_out_of_range_fails_validation() {
+        for limit in [0, 201] {
+            let query = FindAuditEventsRequestQuery {
+                limit: Some(limit),
+                ..query()
+            };
+
+            let errors = query.validate().unwrap_err();
+            assert!(errors.field_errors().contains_key("limit"));
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use domain::entity::audit_event::AuditEvent;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::usecase::find_audit_events::FindAuditEventsOutput;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, TS)]
+pub struct FindAuditEventsResponseBodyItem {
+    #[schema(format = Uuid)]
+    pub id: String,
+    #[schema(example = "user.created")]
+    pub action: String,
+    #[schema(format = Uuid)]
+    pub actor_id: Option<String>,
+    #[schema(format = Uuid)]
+    pub subject_id: Option<String>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl From<AuditEvent> for FindAuditEventsResponseBodyItem {
+    fn from(event: AuditEvent) -> Self {
+        FindAuditEventsResponseBodyItem {
+            id: event.id.to_string(),
+            action: event.action.as_str().to_owned(),
+            actor_id: event.actor_id.map(|id| id.to_string()),
+            subject_id: event.subject_id.map(|id| id.to_string()),
+            ip: event.ip,
+            user_agent: event.user_agent,
+            occurred_at: event.occurred_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, TS)]
+pub struct FindAuditEventsResponseBody {
+    pub items: Vec<FindAuditEventsResponseBodyItem>,
+    #[ts(type = "number | null")]
+    pub next_cursor: Option<i64>,
+}
+
+impl From<FindAuditEventsOutput> for FindAuditEventsResponseBody {
+    fn from(output: FindAuditEventsOutput) -> Self {
+        FindAuditEventsResponseBody {
+            items: output
+                .events
+                .into_iter()
+                .map(FindAuditEventsResponseBodyItem::from)
+                .collect(),
+            next_cursor: output.next_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use domain::entity::{
+        audit_event::{AuditAction, AuditEventPage},
+        value_object::user_id::UserId,
+    };
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize_find_audit_events_response_body_to_json() {
+        let id = Uuid::nil();
+        let subject_id = UserId::from(Uuid::nil());
+        let output = AuditEventPage {
+            events: vec![AuditEvent {
+                id,
+                action: AuditAction::UserCreated,
+                actor_id: None,
+                subject_id: Some(subject_id),
+                ip: Some("203.0.113.7".to_owned()),
+                user_agent: None,
+                occurred_at: Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap(),
+            }],
+            next_cursor: Some(42),
+        };
+
+        let json = serde_json::to_string(&FindAuditEventsResponseBody::from(output)).unwrap();
+        let expected = r#"{"items":[{"id":"00000000-0000-0000-0000-000000000000","action":"user.created","actor_id":null,"subject_id":"00000000-0000-0000-0000-000000000000","ip":"203.0.113.7","user_agent":null,"occurred_at":"2026-10-18T09:00:00Z"}],"next_cursor":42}"#;
+
+        assert_eq!(json, expected);
+    }
+}
@@ -1,7 +1,10 @@
 use domain::entity::value_object::user_id::UserId;
 use serde::Deserialize;
+use utoipa::IntoParams;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct FindUserByIdRequestParam {
+    #[param(value_type = uuid::Uuid)]
     pub id: UserId,
 }
@@ -1,10 +1,15 @@
+use chrono::{DateTime, Utc};
+
 use crate::usecase::find_user_by_id::FindUserByIdOutput;
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, utoipa::ToSchema, ts_rs::TS)]
 pub struct FindUserByIdResponseBody {
+    #[schema(format = Uuid)]
     pub id: String,
     pub name: String,
+    #[schema(format = Email)]
     pub email: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
 }
 
 impl std::convert::From<FindUserByIdOutput> for FindUserByIdResponseBody {
@@ -13,6 +18,7 @@ impl std::convert::From<FindUserByIdOutput> for FindUserByIdResponseBody {
             id: find_user_by_id_output.id.0.to_string(),
             name: find_user_by_id_output.name,
             email: find_user_by_id_output.email,
+            email_verified_at: find_user_by_id_output.email_verified_at,
         }
     }
 }
@@ -29,11 +35,15 @@ mod tests {
             id: id.clone(),
             name: "Test User".to_string(),
             email: "test@example.com".to_string(),
+            email_verified_at: None,
+            updated_at: Utc::now(),
+            version: 1,
         };
 
         let response: FindUserByIdResponseBody = output.into();
         assert_eq!(response.id, id.0.to_string());
         assert_eq!(response.name, "Test User");
         assert_eq!(response.email, "test@example.com");
+        assert_eq!(response.email_verified_at, None);
     }
 }
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationErrors};
+
+use crate::{
+    request_response::create_user_request::CreateUserRequestBody,
+    usecase::{
+        create_user::CreateUserInput,
+        import_users::{ImportMode, ImportRowErrors, ImportUsersInput},
+    },
+};
+
+/// The most rows one import may contain; larger files have to be split.
+pub const MAX_IMPORT_ROWS: usize = 1000;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportUsersMode {
+    /// Create every row, or none of them if any row fails.
+    #[default]
+    Transaction,
+    /// Create the rows that pass and report the others.
+    PerRow,
+}
+
+impl From<ImportUsersMode> for ImportMode {
+    fn from(mode: ImportUsersMode) -> Self {
+        match mode {
+            ImportUsersMode::Transaction => ImportMode::Transaction,
+            ImportUsersMode::PerRow => ImportMode::PerRow,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportUsersRequestQuery {
+    /// `transaction` if omitted.
+    pub mode: Option<ImportUsersMode>,
+}
+
+/// The rows of an import file, each either a user to create or the reason it could not be read.
+#[derive(Debug)]
+pub struct ImportUsersRequestBody {
+    pub rows: Vec<Result<CreateUserRequestBody, ImportRowErrors>>,
+}
+
+impl ImportUsersRequestBody {
+    /// A header line naming the `name` and `email` columns, then one user per line. Other
+    /// columns are ignored.
+    pub fn from_csv(body: &[u8]) -> Result<Self, String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(body);
+        let headers = reader
+            .headers()
+            .map_err(|e| format!("The CSV header could not be read: {}", e))?;
+        for column in ["name", "email"] {
+            if !headers.iter().any(|header| header == column) {
+                return Err(format!("The CSV header has no `{}` column", column));
+            }
+        }
+
+        let rows = reader
+            .deserialize::<CreateUserRequestBody>()
+            .take(MAX_IMPORT_ROWS + 1)
+            .map(|record| record.map_err(|e| row_error(e.to_string())))
+            .collect();
+        Self::new(rows)
+    }
+
+    /// An array of objects shaped like the body of `POST /users`.
+    pub fn from_json(body: &[u8]) -> Result<Self, String> {
+        let values = serde_json::from_slice::<Vec<serde_json::Value>>(body)
+            .map_err(|e| format!("The body must be a JSON array of users: {}", e))?;
+        Self::from_values(values)
+    }
+
+    /// The elements of an array already decoded from JSON or another self-describing format.
+    pub fn from_values(values: Vec<serde_json::Value>) -> Result<Self, String> {
+        let rows = values
+            .into_iter()
+            .take(MAX_IMPORT_ROWS + 1)
+            .map(|value| {
+                serde_json::from_value::<CreateUserRequestBody>(value)
+                    .map_err(|e| row_error(e.to_string()))
+            })
+            .collect();
+        Self::new(rows)
+    }
+
+    fn new(rows: Vec<Result<CreateUserRequestBody, ImportRowErrors>>) -> Result<Self, String> {
+        if rows.is_empty() {
+            return Err("The import contains no rows".to_owned());
+        }
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(format!(
+                "An import may contain at most {} rows",
+                MAX_IMPORT_ROWS
+            ));
+        }
+
+        Ok(ImportUsersRequestBody { rows })
+    }
+
+    /// Applies the `POST /users` validation rules to every row that could be read.
+    pub fn into_input(self, mode: ImportUsersMode) -> ImportUsersInput {
+        ImportUsersInput {
+            rows: self
+                .rows
+                .into_iter()
+                .map(|row| {
+                    let body = row?;
+                    match body.validate() {
+                        Ok(()) => Ok(CreateUserInput::from(body)),
+                        Err(errors) => Err(field_errors(&errors)),
+                    }
+                })
+                .collect(),
+            mode: mode.into(),
+        }
+    }
+}
+
+/// A row that could not be read at all is reported under `row` rather than under a field.
+fn row_error(message: String) -> ImportRowErrors {
+    BTreeMap::from([("row".to_owned(), vec![message])])
+}
+
+fn field_errors(errors: &ValidationErrors) -> ImportRowErrors {
+    errors
+        .field_errors()
+        .into_iter()
+        .map(|(field, errors)| {
+            let messages = errors
+                .iter()
+                .map(|e| match &e.message {
+                    Some(message) => message.to_string(),
+                    None => format!("Invalid {}", field),
+                })
+                .collect();
+            (field.to_string(), messages)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_csv_reads_rows_by_header() {
+        let body = "email, name ,team\nada@example.com,Ada Lovelace,eng\nbroken@example.com\n";
+
+        let rows = ImportUsersRequestBody::from_csv(body.as_bytes())
+            .unwrap()
+            .rows;
+
+        assert_eq!(rows.len(), 2);
+        let first = rows[0].as_ref().unwrap();
+        assert_eq!(
+            (first.name.as_str(), first.email.as_str()),
+            ("Ada Lovelace", "ada@example.com")
+        );
+        assert!(rows[1].as_ref().unwrap_err().contains_key("row"));
+    }
+
+    #[test]
+    fn test_from_csv_requires_name_and_email_columns() {
+        assert!(ImportUsersRequestBody::from_csv(b"name\nAda Lovelace\n").is_err());
+    }
+
+    #[test]
+    fn test_from_json_reports_rows_that_are_not_users() {
+        let body = r#"[{"name":"Ada Lovelace","email":"ada@example.com"},{"name":1}]"#;
+
+        let rows = ImportUsersRequestBody::from_json(body.as_bytes())
+            .unwrap()
+            .rows;
+
+        assert!(rows[0].is_ok());
+        assert!(rows[1].as_ref().unwrap_err().contains_key("row"));
+        assert!(ImportUsersRequestBody::from_json(br#"{"name":"Ada"}"#).is_err());
+        assert!(ImportUsersRequestBody::from_json(b"[]").is_err());
+    }
+
+    #[test]
+    fn test_into_input_validates_each_row() {
+        let body = r#"[
+            {"name":"Ada Lovelace","email":"ada@example.com"},
+            {"name":"A","email":"not-an-email"}
+        ]"#;
+
+        let input = ImportUsersRequestBody::from_json(body.as_bytes())
+            .unwrap()
+            .into_input(ImportUsersMode::PerRow);
+
+        assert_eq!(input.mode, ImportMode::PerRow);
+        assert!(input.rows[0].is_ok());
+        let errors = input.rows[1].as_ref().unwrap_err();
+        assert_eq!(
+            errors.keys().map(String::as_str).collect::<Vec<_>>(),
+            ["email", "name"]
+        );
+        assert_eq!(errors["email"], ["Invalid email"]);
+    }
+
+    #[test]
+    fn test_too_many_rows_are_rejected() {
+        let body = format!(
+            "name,email\n{}",
+            "Ada Lovelace,ada@example.com\n".repeat(MAX_IMPORT_ROWS + 1)
+        );
+
+        assert!(ImportUsersRequestBody::from_csv(body.as_bytes()).is_err());
+    }
+}
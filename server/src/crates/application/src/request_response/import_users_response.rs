@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    request_response::import_users_request::ImportUsersMode,
+    usecase::import_users::{ImportRowOutcome, ImportUsersOutput},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportUserRowStatus {
+    Created,
+    /// The row could not be read or failed validation; see `errors`.
+    Invalid,
+    /// The email belongs to an existing user or to an earlier row.
+    Duplicate,
+    /// The row is valid, but another row failed a `transaction` import.
+    NotImported,
+    /// The row is valid, but the user could not be stored.
+    Failed,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, TS)]
+pub struct ImportUsersResponseBodyRow {
+    /// 1-based position among the rows of the import, not counting a CSV header.
+    pub row: usize,
+    pub status: ImportUserRowStatus,
+    /// The ID of the created user.
+    #[schema(format = Uuid)]
+    pub id: Option<String>,
+    /// Messages per field for an `invalid` row, or under `row` if it could not be read.
+    pub errors: Option<BTreeMap<String, Vec<String>>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, TS)]
+pub struct ImportUsersResponseBody {
+    pub mode: ImportUsersMode,
+    pub created: usize,
+    /// Rows that were not created, whatever the reason.
+    pub failed: usize,
+    pub rows: Vec<ImportUsersResponseBodyRow>,
+}
+
+impl ImportUsersResponseBody {
+    pub fn new(mode: ImportUsersMode, output: ImportUsersOutput) -> Self {
+        let rows: Vec<ImportUsersResponseBodyRow> = output
+            .into_iter()
+            .enumerate()
+            .map(|(index, outcome)| {
+                let (status, id, errors) = match outcome {
+                    ImportRowOutcome::Created(user) => (
+                        ImportUserRowStatus::Created,
+                        Some(user.id.to_string()),
+                        None,
+                    ),
+                    ImportRowOutcome::Invalid(errors) => {
+                        (ImportUserRowStatus::Invalid, None, Some(errors))
+                    }
+                    ImportRowOutcome::Duplicate => (ImportUserRowStatus::Duplicate, None, None),
+                    ImportRowOutcome::NotImported => (ImportUserRowStatus::NotImported, None, None),
+                    ImportRowOutcome::Failed => (ImportUserRowStatus::Failed, None, None),
+                };
+                ImportUsersResponseBodyRow {
+                    row: index + 1,
+                    status,
+                    id,
+                    errors,
+                }
+            })
+            .collect();
+        let created = rows
+            .iter()
+            .filter(|row| row.status == ImportUserRowStatus::Created)
+            .count();
+
+        ImportUsersResponseBody {
+            mode,
+            created,
+            failed: rows.len() - created,
+            rows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::entity::{user::User, value_object::user_id::UserId};
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize_import_users_response_body_to_json() {
+        let user = User {
+            id: UserId::from(Uuid::nil()),
+            ..User::new("Ada Lovelace".to_owned(), "ada@example.com".to_owned())
+        };
+        let output = vec![
+            ImportRowOutcome::Created(user),
+            ImportRowOutcome::Invalid(BTreeMap::from([(
+                "name".to_owned(),
+                vec!["Name must be between 2 and 40 characters".to_owned()],
+            )])),
+            ImportRowOutcome::Duplicate,
+        ];
+
+        let json = serde_json::to_string(&ImportUsersResponseBody::new(
+            ImportUsersMode::PerRow,
+            output,
+        ))
+        .unwrap();
+        let expected = r#"{"mode":"per_row","created":1,"failed":2,"rows":[{"row":1,"status":"created","id":"00000000-0000-0000-0000-000000000000","errors":null},{"row":2,"status":"invalid","id":null,"errors":{"name":["Name must be between 2 and 40 characters"]}},{"row":3,"status":"duplicate","id":null,"errors":null}]}"#;
+
+        assert_eq!(json, expected);
+    }
+}
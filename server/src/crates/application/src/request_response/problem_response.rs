@@ -0,0 +1,27 @@
+use std::collections::BTreeMap;
+
+/// An RFC 9457 problem, as every error response carries it with `application/problem+json`.
+#[derive(
+    Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema, ts_rs::TS,
+)]
+pub struct ProblemResponseBody {
+    /// Identifies the kind of problem with one of a fixed set of URIs.
+    #[serde(rename = "type")]
+    #[ts(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    /// Left out by the server, which only sends the status line; other producers may set it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub detail: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub instance: Option<String>,
+    /// Problem-specific members, e.g. the messages for each field that failed validation.
+    #[serde(flatten)]
+    #[schema(additional_properties)]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+
+use domain::{entity::value_object::user_id::UserId, redact::Redacted};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use validator::Validate;
+
+use crate::{entity_tag::EntityTagCondition, usecase::update_user::UpdateUserInput};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct UpdateUserRequestParam {
+    #[param(value_type = uuid::Uuid)]
+    pub id: UserId,
+}
+
+/// Replaces the user's name and email; changing the email resets its verification.
+#[derive(serde::Deserialize, serde::Serialize, Validate, utoipa::ToSchema, ts_rs::TS)]
+pub struct UpdateUserRequestBody {
+    #[validate(length(
+        min = 2,
+        max = 40,
+        message = "Name must be between 2 and 40 characters"
+    ))]
+    #[schema(min_length = 2, max_length = 40)]
+    pub name: String,
+    #[validate(email)]
+    #[schema(format = Email)]
+    pub email: String,
+}
+
+impl fmt::Debug for UpdateUserRequestBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateUserRequestBody")
+            .field("name", &self.name)
+            .field("email", &Redacted)
+            .finish()
+    }
+}
+
+impl UpdateUserRequestBody {
+    pub fn into_input(self, id: UserId, if_match: Option<EntityTagCondition>) -> UpdateUserInput {
+        UpdateUserInput {
+            id,
+            name: self.name,
+            email: self.email,
+            if_match,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_request_fails_validation() {
+        let req = UpdateUserRequestBody {
+            name: "A".to_string(),
+            email: "not-an-email".to_string(),
+        };
+
+        let errors = req.validate().unwrap_err();
+        let mut fields: Vec<_> = errors.field_errors().into_keys().collect();
+        fields.sort();
+        assert_eq!(fields, vec!["email", "name"]);
+    }
+}
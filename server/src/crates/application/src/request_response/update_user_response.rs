@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+
+use crate::usecase::update_user::UpdateUserOutput;
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, utoipa::ToSchema, ts_rs::TS)]
+pub struct UpdateUserResponseBody {
+    #[schema(format = Uuid)]
+    pub id: String,
+    pub name: String,
+    #[schema(format = Email)]
+    pub email: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
+}
+
+impl std::convert::From<UpdateUserOutput> for UpdateUserResponseBody {
+    fn from(update_user_output: UpdateUserOutput) -> Self {
+        UpdateUserResponseBody {
+            id: update_user_output.id.0.to_string(),
+            name: update_user_output.name,
+            email: update_user_output.email,
+            email_verified_at: update_user_output.email_verified_at,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use std::fmt;
+
+use domain::redact::Redacted;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VerifyEmailRequestQuery {
+    pub token: String,
+}
+
+impl fmt::Debug for VerifyEmailRequestQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerifyEmailRequestQuery")
+            .field("token", &Redacted)
+            .finish()
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+
+use crate::usecase::verify_email::VerifyEmailOutput;
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, utoipa::ToSchema, ts_rs::TS)]
+pub struct VerifyEmailResponseBody {
+    #[schema(format = Uuid)]
+    pub id: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
+}
+
+impl std::convert::From<VerifyEmailOutput> for VerifyEmailResponseBody {
+    fn from(verify_email_output: VerifyEmailOutput) -> Self {
+        VerifyEmailResponseBody {
+            id: verify_email_output.id.to_string(),
+            email_verified_at: verify_email_output.email_verified_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::entity::user::User;
+
+    #[test]
+    fn test_serialize_to_json() {
+        let mut user = User::new("Test User".into(), "test@example.com".into());
+        user.email_verified_at = Some("2025-01-02T03:04:05Z".parse().unwrap());
+        let id = user.id.to_string();
+
+        let response = VerifyEmailResponseBody::from(user);
+
+        let json = serde_json::to_string(&response).unwrap();
+        let expected = format!(
+            r#"{{"id":"{}","email_verified_at":"2025-01-02T03:04:05Z"}}"#,
+            id
+        );
+        assert_eq!(json, expected);
+    }
+}
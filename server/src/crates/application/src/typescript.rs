@@ -0,0 +1,99 @@
+//! TypeScript declarations for the request and response bodies, so the front end's types are
+//! generated from the derives instead of being kept in sync by hand.
+
+use ts_rs::TS;
+
+use crate::{
+    problem_type,
+    request_response::{
+        create_user_request::CreateUserRequestBody,
+        create_user_response::CreateUserResponseBody,
+        csrf_token_response::CsrfTokenResponseBody,
+        find_all_user_response::{FindAllUserResponseBody, FindAllUserResponseBodyItem},
+        find_audit_events_response::{
+            FindAuditEventsResponseBody, FindAuditEventsResponseBodyItem,
+        },
+        find_user_by_id_response::FindUserByIdResponseBody,
+        import_users_request::ImportUsersMode,
+        import_users_response::{
+            ImportUserRowStatus, ImportUsersResponseBody, ImportUsersResponseBodyRow,
+        },
+        problem_response::ProblemResponseBody,
+        update_user_request::UpdateUserRequestBody,
+        update_user_response::UpdateUserResponseBody,
+        verify_email_response::VerifyEmailResponseBody,
+    },
+};
+
+/// Where the `export_types` binary writes [`declarations`] by default.
+pub const DECLARATIONS_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../../../front/src/types/api.d.ts"
+);
+
+/// Renders every body type, and the types they refer to, as one declaration file.
+pub fn declarations() -> String {
+    let mut out = String::from(
+        "// Generated by `task types` from `application::request_response` and\n// `application::problem_type`; do not edit.\n",
+    );
+
+    declare::<serde_json::Value>(&mut out);
+    declare::<ProblemResponseBody>(&mut out);
+    declare_problem_types(&mut out);
+    declare::<CsrfTokenResponseBody>(&mut out);
+    declare::<CreateUserRequestBody>(&mut out);
+    declare::<CreateUserResponseBody>(&mut out);
+    declare::<FindAllUserResponseBodyItem>(&mut out);
+    declare::<FindAllUserResponseBody>(&mut out);
+    declare::<FindUserByIdResponseBody>(&mut out);
+    declare::<ImportUsersMode>(&mut out);
+    declare::<ImportUserRowStatus>(&mut out);
+    declare::<ImportUsersResponseBodyRow>(&mut out);
+    declare::<ImportUsersResponseBody>(&mut out);
+    declare::<UpdateUserRequestBody>(&mut out);
+    declare::<UpdateUserResponseBody>(&mut out);
+    declare::<VerifyEmailResponseBody>(&mut out);
+    declare::<FindAuditEventsResponseBodyItem>(&mut out);
+    declare::<FindAuditEventsResponseBody>(&mut out);
+
+    out
+}
+
+/// The URIs of [`problem_type::ALL`] as a union, since they are constants rather than a type.
+fn declare_problem_types(out: &mut String) {
+    let uris: Vec<String> = problem_type::ALL
+        .iter()
+        .map(|uri| format!("\"{}\"", uri))
+        .collect();
+    out.push_str("\n/**\n * Every `type` a problem from the server can carry.\n */\n");
+    out.push_str(&format!(
+        "export type ProblemType = {};\n",
+        uris.join(" | ")
+    ));
+}
+
+fn declare<T: TS>(out: &mut String) {
+    out.push('\n');
+    if let Some(docs) = T::docs() {
+        out.push_str(&docs);
+    }
+    out.push_str("export ");
+    out.push_str(&T::decl());
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_front_end_declarations_are_up_to_date() {
+        let written = std::fs::read_to_string(DECLARATIONS_PATH)
+            .expect("front/src/types/api.d.ts should exist; run `task types`");
+
+        assert!(
+            written == declarations(),
+            "front/src/types/api.d.ts is out of date; run `task types`"
+        );
+    }
+}
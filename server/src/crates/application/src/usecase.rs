@@ -1,3 +1,11 @@
 pub mod create_user;
+pub mod delete_user;
+pub mod dispatch_outbox;
+pub mod export_users;
 pub mod find_all_user;
+pub mod find_audit_events;
 pub mod find_user_by_id;
+pub mod import_users;
+pub mod issue_email_verification;
+pub mod update_user;
+pub mod verify_email;
@@ -1,18 +1,35 @@
+use std::fmt;
+
 use domain::{
     entity::user::User,
+    event::domain_event::DomainEvent,
     interface::{
+        event_publisher_interface::EventPublisherInterface,
+        unit_of_work_interface::UnitOfWorkInterface,
         user_email_duplicate_validator_interface::UserEmailDuplicateValidatorInterface,
         user_repository_interface::UserRepositoryInterface,
     },
+    redact::Redacted,
 };
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
+use crate::audit_log;
+
+#[derive(Deserialize)]
 pub struct CreateUserInput {
     pub name: String,
     pub email: String,
 }
 
+impl fmt::Debug for CreateUserInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateUserInput")
+            .field("name", &self.name)
+            .field("email", &Redacted)
+            .finish()
+    }
+}
+
 impl CreateUserInput {
     pub fn new(name: String, email: String) -> Self {
         CreateUserInput { name, email }
@@ -23,50 +40,99 @@ pub type CreateUserOutput = User;
 
 pub struct CreateUserUsecase<T, U>
 where
-    T: UserRepositoryInterface,
-    U: UserEmailDuplicateValidatorInterface,
+    T: UnitOfWorkInterface,
+    U: EventPublisherInterface,
 {
-    user_repository: T,
-    user_email_duplicate_validator: U,
+    unit_of_work: T,
+    event_publisher: U,
 }
 
 impl<T, U> CreateUserUsecase<T, U>
 where
-    T: UserRepositoryInterface,
-    U: UserEmailDuplicateValidatorInterface,
+    T: UnitOfWorkInterface,
+    U: EventPublisherInterface,
 {
-    pub fn new(user_repository: T, user_email_duplicate_validator: U) -> Self {
+    pub fn new(unit_of_work: T, event_publisher: U) -> Self {
         CreateUserUsecase {
-            user_repository,
-            user_email_duplicate_validator,
+            unit_of_work,
+            event_publisher,
         }
     }
 
+    #[tracing::instrument(name = "CreateUserUsecase::execute", skip_all)]
     pub async fn execute(
         &mut self,
         create_user_input: CreateUserInput,
     ) -> anyhow::Result<CreateUserOutput> {
         let user = User::new(create_user_input.name, create_user_input.email);
-        self.user_email_duplicate_validator
+        // The duplicate check and the insert share a transaction, which is rolled back when
+        // dropped on an early return.
+        let tx = self.unit_of_work.begin().await?;
+        tx.user_email_duplicate_validator()
             .validate_user_email_duplicate(&user.email)
             .await?;
-        let creates_user = self.user_repository.create(&user).await?; // ← 実際に保存された結果
+        let creates_user = tx.user_repository().create(&user).await?; // ← 実際に保存された結果
+        let event = DomainEvent::user_created(creates_user.id.clone());
+        audit_log::record(tx.as_ref(), &event).await?;
+        tx.commit().await?;
+
+        // The user is already committed, so a failing subscriber must not fail the request.
+        if let Err(e) = self.event_publisher.publish(&event).await {
+            tracing::warn!(error = %e, "failed to publish user created event");
+        }
         anyhow::Ok(creates_user)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
     use domain::{
         entity::value_object::user_id::UserId,
         error::user_error::UserEmailDuplicateValidationError,
         interface::{
+            audit_log_repository_interface::MockAuditLogRepositoryInterface,
+            event_publisher_interface::MockEventPublisherInterface,
+            unit_of_work_interface::{MockTransactionInterface, MockUnitOfWorkInterface},
             user_email_duplicate_validator_interface::MockUserEmailDuplicateValidatorInterface,
             user_repository_interface::MockUserRepositoryInterface,
         },
     };
 
+    /// A unit of work whose single transaction hands out the given mocks and expects to be
+    /// committed only if `commits` is set.
+    fn mocked_unit_of_work(
+        user_repository: MockUserRepositoryInterface,
+        user_email_duplicate_validator: MockUserEmailDuplicateValidatorInterface,
+        commits: bool,
+    ) -> MockUnitOfWorkInterface {
+        let user_repository = Arc::new(user_repository);
+        let user_email_duplicate_validator = Arc::new(user_email_duplicate_validator);
+        let mut tx = MockTransactionInterface::new();
+        tx.expect_user_repository()
+            .returning(move || user_repository.clone());
+        tx.expect_user_email_duplicate_validator()
+            .returning(move || user_email_duplicate_validator.clone());
+        tx.expect_audit_log_repository().returning(|| {
+            let mut audit_log_repository = MockAuditLogRepositoryInterface::new();
+            audit_log_repository.expect_append().returning(|_| Ok(()));
+            Arc::new(audit_log_repository)
+        });
+        tx.expect_commit()
+            .times(usize::from(commits))
+            .returning(|| Ok(()));
+
+        let mut unit_of_work = MockUnitOfWorkInterface::new();
+        let mut tx = Some(tx);
+        unit_of_work
+            .expect_begin()
+            .times(1)
+            .returning(move || Ok(Box::new(tx.take().expect("begin should be called once"))));
+        unit_of_work
+    }
+
     #[tokio::test]
     async fn test_create_user_usecase_successful() -> anyhow::Result<()> {
         let mut mocked_user_repository = MockUserRepositoryInterface::new();
@@ -78,6 +144,9 @@ mod tests {
             id: UserId::new(),
             name: input.name.clone(),
             email: input.email.clone(),
+            email_verified_at: None,
+            updated_at: chrono::Utc::now(),
+            version: 1,
         };
 
         mocked_user_email_duplicate_validator
@@ -98,9 +167,20 @@ mod tests {
             })
             .returning(move |_user| Ok(expected_user.clone()));
 
+        let mut mocked_event_publisher = MockEventPublisherInterface::new();
+        mocked_event_publisher
+            .expect_publish()
+            .withf(|event| event.topic() == "user.created")
+            .times(1)
+            .returning(|_event| Ok(()));
+
         let mut usecase = CreateUserUsecase::new(
-            mocked_user_repository,
-            mocked_user_email_duplicate_validator,
+            mocked_unit_of_work(
+                mocked_user_repository,
+                mocked_user_email_duplicate_validator,
+                true,
+            ),
+            mocked_event_publisher,
         );
         let result = usecase.execute(input).await.unwrap();
 
@@ -120,7 +200,12 @@ mod tests {
             .returning(|_email| Err(UserEmailDuplicateValidationError::AlreadyExists));
 
         let input = CreateUserInput::new("Test User".into(), "test@example.com".into());
-        let mut usecase = CreateUserUsecase::new(mocked_repo, mocked_validator);
+        let mut mocked_event_publisher = MockEventPublisherInterface::new();
+        mocked_event_publisher.expect_publish().never();
+        let mut usecase = CreateUserUsecase::new(
+            mocked_unit_of_work(mocked_repo, mocked_validator, false),
+            mocked_event_publisher,
+        );
         let result = usecase.execute(input).await;
 
         assert!(result.is_err());
@@ -0,0 +1,173 @@
+use domain::{
+    entity::value_object::user_id::UserId,
+    error::user_error::UserConcurrencyError,
+    event::domain_event::DomainEvent,
+    interface::{
+        event_publisher_interface::EventPublisherInterface,
+        unit_of_work_interface::UnitOfWorkInterface,
+        user_repository_interface::UserRepositoryInterface,
+    },
+};
+
+use crate::{
+    audit_log,
+    entity_tag::{EntityTagCondition, user_entity_tag},
+};
+
+#[derive(Debug)]
+pub struct DeleteUserInput {
+    pub id: UserId,
+    /// Only delete the user if its current entity tag matches.
+    pub if_match: Option<EntityTagCondition>,
+}
+
+pub struct DeleteUserUsecase<T, U>
+where
+    T: UnitOfWorkInterface,
+    U: EventPublisherInterface,
+{
+    unit_of_work: T,
+    event_publisher: U,
+}
+
+impl<T, U> DeleteUserUsecase<T, U>
+where
+    T: UnitOfWorkInterface,
+    U: EventPublisherInterface,
+{
+    pub fn new(unit_of_work: T, event_publisher: U) -> Self {
+        DeleteUserUsecase {
+            unit_of_work,
+            event_publisher,
+        }
+    }
+
+    #[tracing::instrument(name = "DeleteUserUsecase::execute", skip_all)]
+    pub async fn execute(&mut self, delete_user_input: DeleteUserInput) -> anyhow::Result<()> {
+        let tx = self.unit_of_work.begin().await?;
+        let user_repository = tx.user_repository();
+        let user = user_repository.find_by_id(&delete_user_input.id).await?;
+        if let Some(if_match) = &delete_user_input.if_match
+            && !if_match.matches_strong(&user_entity_tag(&user))
+        {
+            return Err(UserConcurrencyError::PreconditionFailed.into());
+        }
+
+        user_repository.delete(&user).await?;
+        drop(user_repository);
+        let event = DomainEvent::user_deleted(user.id);
+        audit_log::record(tx.as_ref(), &event).await?;
+        tx.commit().await?;
+
+        if let Err(e) = self.event_publisher.publish(&event).await {
+            tracing::warn!(error = %e, "failed to publish user deleted event");
+        }
+        anyhow::Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use domain::{
+        entity::user::User,
+        interface::{
+            audit_log_repository_interface::MockAuditLogRepositoryInterface,
+            event_publisher_interface::MockEventPublisherInterface,
+            unit_of_work_interface::{MockTransactionInterface, MockUnitOfWorkInterface},
+            user_repository_interface::MockUserRepositoryInterface,
+        },
+    };
+
+    /// A unit of work whose single transaction hands out `user_repository` and expects to be
+    /// committed only if `commits` is set.
+    fn mocked_unit_of_work(
+        user_repository: MockUserRepositoryInterface,
+        commits: bool,
+    ) -> MockUnitOfWorkInterface {
+        let user_repository = Arc::new(user_repository);
+        let mut tx = MockTransactionInterface::new();
+        tx.expect_user_repository()
+            .returning(move || user_repository.clone());
+        tx.expect_audit_log_repository().returning(|| {
+            let mut audit_log_repository = MockAuditLogRepositoryInterface::new();
+            audit_log_repository.expect_append().returning(|_| Ok(()));
+            Arc::new(audit_log_repository)
+        });
+        tx.expect_commit()
+            .times(usize::from(commits))
+            .returning(|| Ok(()));
+
+        let mut unit_of_work = MockUnitOfWorkInterface::new();
+        let mut tx = Some(tx);
+        unit_of_work
+            .expect_begin()
+            .times(1)
+            .returning(move || Ok(Box::new(tx.take().expect("begin should be called once"))));
+        unit_of_work
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_usecase_successful() -> anyhow::Result<()> {
+        let user = User::new("Test User".into(), "test@example.com".into());
+        let mut user_repository = MockUserRepositoryInterface::new();
+        user_repository.expect_find_by_id().returning({
+            let user = user.clone();
+            move |_user_id| Ok(user.clone())
+        });
+        user_repository
+            .expect_delete()
+            .withf({
+                let id = user.id.clone();
+                move |user| user.id == id
+            })
+            .times(1)
+            .returning(|_user| Ok(()));
+        let mut event_publisher = MockEventPublisherInterface::new();
+        event_publisher
+            .expect_publish()
+            .withf(|event| event.topic() == "user.deleted")
+            .times(1)
+            .returning(|_event| Ok(()));
+
+        let mut usecase =
+            DeleteUserUsecase::new(mocked_unit_of_work(user_repository, true), event_publisher);
+        usecase
+            .execute(DeleteUserInput {
+                id: user.id.clone(),
+                if_match: Some(EntityTagCondition::Any),
+            })
+            .await?;
+
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_with_stale_entity_tag_fails() {
+        let user = User::new("Test User".into(), "test@example.com".into());
+        let mut user_repository = MockUserRepositoryInterface::new();
+        user_repository.expect_find_by_id().returning({
+            let user = user.clone();
+            move |_user_id| Ok(user.clone())
+        });
+        user_repository.expect_delete().never();
+
+        let mut usecase = DeleteUserUsecase::new(
+            mocked_unit_of_work(user_repository, false),
+            MockEventPublisherInterface::new(),
+        );
+        let result = usecase
+            .execute(DeleteUserInput {
+                id: user.id.clone(),
+                if_match: Some(EntityTagCondition::parse(r#""stale""#)),
+            })
+            .await;
+
+        match result.unwrap_err().downcast_ref::<UserConcurrencyError>() {
+            Some(UserConcurrencyError::PreconditionFailed) => {}
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use domain::{
+    entity::outbox_message::OutboxRetryPolicy,
+    interface::{
+        outbox_handler_interface::OutboxHandlerInterface,
+        outbox_repository_interface::OutboxRepositoryInterface,
+    },
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchOutboxOutput {
+    pub delivered: usize,
+    pub retried: usize,
+    pub dead: usize,
+}
+
+impl DispatchOutboxOutput {
+    pub fn claimed(&self) -> usize {
+        self.delivered + self.retried + self.dead
+    }
+}
+
+pub struct DispatchOutboxUsecase<T, U>
+where
+    T: OutboxRepositoryInterface,
+    U: OutboxHandlerInterface,
+{
+    outbox_repository: T,
+    handler: U,
+    retry_policy: OutboxRetryPolicy,
+    batch_size: i64,
+    lease: Duration,
+}
+
+impl<T, U> DispatchOutboxUsecase<T, U>
+where
+    T: OutboxRepositoryInterface,
+    U: OutboxHandlerInterface,
+{
+    /// `lease` should comfortably exceed the time `handler` needs for one message, or a slow
+    /// delivery may be attempted twice.
+    pub fn new(
+        outbox_repository: T,
+        handler: U,
+        retry_policy: OutboxRetryPolicy,
+        batch_size: i64,
+        lease: Duration,
+    ) -> Self {
+        DispatchOutboxUsecase {
+            outbox_repository,
+            handler,
+            retry_policy,
+            batch_size,
+            lease,
+        }
+    }
+
+    /// Delivers one batch of due messages.
+    #[tracing::instrument(name = "DispatchOutboxUsecase::execute", skip_all)]
+    pub async fn execute(&self) -> anyhow::Result<DispatchOutboxOutput> {
+        let messages = self
+            .outbox_repository
+            .claim_due(&self.handler.topics(), self.batch_size, self.lease)
+            .await?;
+
+        let mut output = DispatchOutboxOutput::default();
+        for message in messages {
+            match self.handler.handle(&message).await {
+                Ok(()) => {
+                    self.outbox_repository.mark_delivered(&message.id).await?;
+                    output.delivered += 1;
+                }
+                Err(e) => {
+                    let error = e.to_string();
+                    let retry_at = self
+                        .retry_policy
+                        .next_attempt_at(message.attempts, Utc::now());
+                    self.outbox_repository
+                        .mark_failed(&message.id, &error, retry_at)
+                        .await?;
+
+                    if retry_at.is_some() {
+                        tracing::warn!(
+                            outbox_id = %message.id,
+                            topic = %message.topic,
+                            attempts = message.attempts,
+                            error = %error,
+                            "outbox delivery failed; will retry"
+                        );
+                        output.retried += 1;
+                    } else {
+                        tracing::error!(
+                            outbox_id = %message.id,
+                            topic = %message.topic,
+                            attempts = message.attempts,
+                            error = %error,
+                            "outbox delivery failed; moved to dead letter"
+                        );
+                        output.dead += 1;
+                    }
+                }
+            }
+        }
+
+        anyhow::Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::{
+        entity::{outbox_message::OutboxMessage, value_object::user_id::UserId},
+        event::domain_event::DomainEvent,
+        interface::{
+            outbox_handler_interface::MockOutboxHandlerInterface,
+            outbox_repository_interface::MockOutboxRepositoryInterface,
+        },
+    };
+
+    use super::*;
+
+    const RETRY_POLICY: OutboxRetryPolicy = OutboxRetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_secs(10),
+        max_delay: Duration::from_secs(60),
+    };
+
+    fn claimed_message(attempts: i32) -> OutboxMessage {
+        let mut message = OutboxMessage::from_event(&DomainEvent::user_created(UserId::new()));
+        message.attempts = attempts;
+        message
+    }
+
+    fn usecase(
+        outbox_repository: MockOutboxRepositoryInterface,
+        mut handler: MockOutboxHandlerInterface,
+    ) -> DispatchOutboxUsecase<MockOutboxRepositoryInterface, MockOutboxHandlerInterface> {
+        handler
+            .expect_topics()
+            .returning(|| vec!["user.created".to_owned()]);
+        DispatchOutboxUsecase::new(
+            outbox_repository,
+            handler,
+            RETRY_POLICY,
+            10,
+            Duration::from_secs(60),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_outbox_delivers_messages() -> anyhow::Result<()> {
+        let mut mocked_outbox_repository = MockOutboxRepositoryInterface::new();
+        let mut mocked_handler = MockOutboxHandlerInterface::new();
+        let message = claimed_message(1);
+        let message_id = message.id;
+
+        mocked_outbox_repository
+            .expect_claim_due()
+            .withf(|topics, limit, _lease| topics == ["user.created"] && *limit == 10)
+            .returning(move |_topics, _limit, _lease| Ok(vec![message.clone()]));
+        mocked_handler
+            .expect_handle()
+            .times(1)
+            .returning(|_message| Ok(()));
+        mocked_outbox_repository
+            .expect_mark_delivered()
+            .withf(move |id| *id == message_id)
+            .times(1)
+            .returning(|_id| Ok(()));
+
+        let output = usecase(mocked_outbox_repository, mocked_handler)
+            .execute()
+            .await?;
+
+        assert_eq!(
+            output,
+            DispatchOutboxOutput {
+                delivered: 1,
+                retried: 0,
+                dead: 0
+            }
+        );
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_outbox_schedules_retry_with_backoff() -> anyhow::Result<()> {
+        let mut mocked_outbox_repository = MockOutboxRepositoryInterface::new();
+        let mut mocked_handler = MockOutboxHandlerInterface::new();
+        let message = claimed_message(2);
+        let before = Utc::now();
+
+        mocked_outbox_repository
+            .expect_claim_due()
+            .returning(move |_topics, _limit, _lease| Ok(vec![message.clone()]));
+        mocked_handler
+            .expect_handle()
+            .returning(|_message| Err(anyhow::Error::msg("smtp unavailable")));
+        mocked_outbox_repository
+            .expect_mark_failed()
+            .withf(move |_id, error, retry_at| {
+                error == "smtp unavailable"
+                    && retry_at
+                        .is_some_and(|retry_at| retry_at >= before + chrono::Duration::seconds(20))
+            })
+            .times(1)
+            .returning(|_id, _error, _retry_at| Ok(()));
+
+        let output = usecase(mocked_outbox_repository, mocked_handler)
+            .execute()
+            .await?;
+
+        assert_eq!(output.retried, 1);
+        assert_eq!(output.claimed(), 1);
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_outbox_dead_letters_after_max_attempts() -> anyhow::Result<()> {
+        let mut mocked_outbox_repository = MockOutboxRepositoryInterface::new();
+        let mut mocked_handler = MockOutboxHandlerInterface::new();
+        let message = claimed_message(RETRY_POLICY.max_attempts);
+
+        mocked_outbox_repository
+            .expect_claim_due()
+            .returning(move |_topics, _limit, _lease| Ok(vec![message.clone()]));
+        mocked_handler
+            .expect_handle()
+            .returning(|_message| Err(anyhow::Error::msg("smtp unavailable")));
+        mocked_outbox_repository
+            .expect_mark_failed()
+            .withf(|_id, _error, retry_at| retry_at.is_none())
+            .times(1)
+            .returning(|_id, _error, _retry_at| Ok(()));
+
+        let output = usecase(mocked_outbox_repository, mocked_handler)
+            .execute()
+            .await?;
+
+        assert_eq!(output.dead, 1);
+        anyhow::Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use domain::{entity::user::User, interface::user_repository_interface::UserRepositoryInterface};
+use futures_util::stream::BoxStream;
+
+/// The users in export order, read as the stream is polled.
+pub type ExportUsersOutput = BoxStream<'static, anyhow::Result<User>>;
+
+pub struct ExportUsersUsecase<T>
+where
+    T: UserRepositoryInterface,
+{
+    user_repository: T,
+}
+
+impl<T> ExportUsersUsecase<T>
+where
+    T: UserRepositoryInterface,
+{
+    pub fn new(user_repository: T) -> Self {
+        ExportUsersUsecase { user_repository }
+    }
+
+    #[tracing::instrument(name = "ExportUsersUsecase::execute", skip_all)]
+    pub fn execute(&self) -> ExportUsersOutput {
+        self.user_repository.stream_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{StreamExt, TryStreamExt};
+
+    use super::*;
+    use domain::interface::user_repository_interface::MockUserRepositoryInterface;
+
+    #[tokio::test]
+    async fn test_export_users_usecase_streams_the_repository() -> anyhow::Result<()> {
+        let mut mocked_user_repository = MockUserRepositoryInterface::new();
+        let user1 = User::new("Test User1".into(), "test1@example.com".into());
+        let user2 = User::new("Test User2".into(), "test2@example.com".into());
+
+        let users = vec![user1.clone(), user2.clone()];
+        mocked_user_repository
+            .expect_stream_all()
+            .times(1)
+            .returning(move || {
+                futures_util::stream::iter(users.clone().into_iter().map(Ok)).boxed()
+            });
+        mocked_user_repository.expect_find_all().never();
+
+        let usecase = ExportUsersUsecase::new(mocked_user_repository);
+        let exported: Vec<User> = usecase.execute().try_collect().await?;
+
+        assert_eq!(exported, vec![user1, user2]);
+        anyhow::Ok(())
+    }
+}
@@ -17,6 +17,7 @@ where
         FindAllUserUsecase { user_repository }
     }
 
+    #[tracing::instrument(name = "FindAllUserUsecase::execute", skip_all)]
     pub async fn execute(&self) -> anyhow::Result<FindAllUserOutput> {
         let users = self.user_repository.find_all().await?;
         anyhow::Ok(FindAllUserOutput(users))
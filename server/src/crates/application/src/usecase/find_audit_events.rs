@@ -0,0 +1,76 @@
+use domain::{
+    entity::audit_event::{AuditEventFilter, AuditEventPage},
+    interface::audit_log_repository_interface::AuditLogRepositoryInterface,
+};
+
+pub type FindAuditEventsInput = AuditEventFilter;
+
+pub type FindAuditEventsOutput = AuditEventPage;
+pub struct FindAuditEventsUsecase<T>
+where
+    T: AuditLogRepositoryInterface,
+{
+    audit_log_repository: T,
+}
+
+impl<T> FindAuditEventsUsecase<T>
+where
+    T: AuditLogRepositoryInterface,
+{
+    pub fn new(audit_log_repository: T) -> Self {
+        FindAuditEventsUsecase {
+            audit_log_repository,
+        }
+    }
+
+    #[tracing::instrument(name = "FindAuditEventsUsecase::execute", skip_all)]
+    pub async fn execute(
+        &self,
+        find_audit_events_input: FindAuditEventsInput,
+    ) -> anyhow::Result<FindAuditEventsOutput> {
+        let page = self
+            .audit_log_repository
+            .find(&find_audit_events_input)
+            .await?;
+        anyhow::Ok(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::{
+        entity::audit_event::AuditAction,
+        interface::audit_log_repository_interface::MockAuditLogRepositoryInterface,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_find_audit_events_usecase_passes_filter_through() -> anyhow::Result<()> {
+        let mut mocked_audit_log_repository = MockAuditLogRepositoryInterface::new();
+        mocked_audit_log_repository
+            .expect_find()
+            .withf(|filter| filter.action == Some(AuditAction::UserCreated) && filter.limit == 10)
+            .times(1)
+            .returning(|_| {
+                Ok(AuditEventPage {
+                    events: vec![],
+                    next_cursor: None,
+                })
+            });
+
+        let usecase = FindAuditEventsUsecase::new(mocked_audit_log_repository);
+        let output = usecase
+            .execute(AuditEventFilter {
+                action: Some(AuditAction::UserCreated),
+                limit: 10,
+                ..AuditEventFilter::default()
+            })
+            .await?;
+
+        assert_eq!(output.events, vec![]);
+        assert_eq!(output.next_cursor, None);
+
+        Ok(())
+    }
+}
@@ -21,6 +21,7 @@ where
         FindUserByIdUsecase { user_repository }
     }
 
+    #[tracing::instrument(name = "FindUserByIdUsecase::execute", skip_all)]
     pub async fn execute(
         &self,
         find_user_by_id_input: FindUserByIdInput,
@@ -44,7 +45,7 @@ mod tests {
     #[tokio::test]
     async fn test_find_by_id_usecase_successful() -> anyhow::Result<()> {
         let mut mocked_user_repository = MockUserRepositoryInterface::new();
-        let user = User::new("Test User".into(), format!("test@example.com"));
+        let user = User::new("Test User".into(), "test@example.com".into());
         let user_id = user.id.clone();
 
         mocked_user_repository.expect_find_by_id().returning({
@@ -0,0 +1,434 @@
+use std::collections::{BTreeMap, HashSet};
+
+use domain::{
+    entity::user::User,
+    error::user_error::UserEmailDuplicateValidationError,
+    event::domain_event::DomainEvent,
+    interface::{
+        event_publisher_interface::EventPublisherInterface,
+        unit_of_work_interface::{TransactionInterface, UnitOfWorkInterface},
+        user_email_duplicate_validator_interface::UserEmailDuplicateValidatorInterface,
+        user_repository_interface::UserRepositoryInterface,
+    },
+};
+
+use crate::{audit_log, usecase::create_user::CreateUserInput};
+
+/// Messages per field, keyed like the members of a `validate` problem.
+pub type ImportRowErrors = BTreeMap<String, Vec<String>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Creates every row or, if any row fails, none of them.
+    Transaction,
+    /// Creates each valid row on its own, whatever happens to the others.
+    PerRow,
+}
+
+#[derive(Debug)]
+pub struct ImportUsersInput {
+    /// Rows in file order; rows that failed parsing or validation carry their errors.
+    pub rows: Vec<Result<CreateUserInput, ImportRowErrors>>,
+    pub mode: ImportMode,
+}
+
+#[derive(Debug)]
+pub enum ImportRowOutcome {
+    Created(User),
+    Invalid(ImportRowErrors),
+    /// The email is already taken, by a stored user or an earlier row of the same import.
+    Duplicate,
+    /// Valid, but not created because another row failed a transactional import.
+    NotImported,
+    /// Valid, but the insert failed for a reason other than a duplicate email.
+    Failed,
+}
+
+pub type ImportUsersOutput = Vec<ImportRowOutcome>;
+
+pub struct ImportUsersUsecase<T, U>
+where
+    T: UnitOfWorkInterface,
+    U: EventPublisherInterface,
+{
+    unit_of_work: T,
+    event_publisher: U,
+}
+
+impl<T, U> ImportUsersUsecase<T, U>
+where
+    T: UnitOfWorkInterface,
+    U: EventPublisherInterface,
+{
+    pub fn new(unit_of_work: T, event_publisher: U) -> Self {
+        ImportUsersUsecase {
+            unit_of_work,
+            event_publisher,
+        }
+    }
+
+    #[tracing::instrument(
+        name = "ImportUsersUsecase::execute",
+        skip_all,
+        fields(rows = import_users_input.rows.len(), mode = ?import_users_input.mode)
+    )]
+    pub async fn execute(
+        &mut self,
+        import_users_input: ImportUsersInput,
+    ) -> anyhow::Result<ImportUsersOutput> {
+        let mut seen_emails = HashSet::new();
+        let rows = import_users_input
+            .rows
+            .into_iter()
+            .map(|row| match row {
+                Ok(input) if !seen_emails.insert(input.email.clone()) => {
+                    PendingRow::Done(ImportRowOutcome::Duplicate)
+                }
+                Ok(input) => PendingRow::Insert(User::new(input.name, input.email)),
+                Err(errors) => PendingRow::Done(ImportRowOutcome::Invalid(errors)),
+            })
+            .collect();
+
+        let outcomes = match import_users_input.mode {
+            ImportMode::Transaction => self.import_all(rows).await?,
+            ImportMode::PerRow => self.import_each(rows).await?,
+        };
+
+        // The users are already committed, so a failing subscriber must not fail the import.
+        for outcome in &outcomes {
+            if let ImportRowOutcome::Created(user) = outcome {
+                let event = DomainEvent::user_created(user.id.clone());
+                if let Err(e) = self.event_publisher.publish(&event).await {
+                    tracing::warn!(error = %e, "failed to publish user created event");
+                }
+            }
+        }
+        anyhow::Ok(outcomes)
+    }
+
+    /// Checks every row first and inserts only if all of them can be, in one transaction.
+    async fn import_all(&self, mut rows: Vec<PendingRow>) -> anyhow::Result<ImportUsersOutput> {
+        let tx = self.unit_of_work.begin().await?;
+        for row in &mut rows {
+            if let PendingRow::Insert(user) = row
+                && is_email_taken(tx.as_ref(), &user.email).await?
+            {
+                *row = PendingRow::Done(ImportRowOutcome::Duplicate);
+            }
+        }
+
+        if rows.iter().any(|row| matches!(row, PendingRow::Done(_))) {
+            // The transaction is rolled back when dropped; nothing was written anyway.
+            return Ok(rows
+                .into_iter()
+                .map(|row| match row {
+                    PendingRow::Insert(_) => ImportRowOutcome::NotImported,
+                    PendingRow::Done(outcome) => outcome,
+                })
+                .collect());
+        }
+
+        let mut outcomes = Vec::with_capacity(rows.len());
+        for (index, row) in rows.iter().enumerate() {
+            let PendingRow::Insert(user) = row else {
+                continue;
+            };
+            match insert(tx.as_ref(), user).await {
+                Ok(created) => outcomes.push(ImportRowOutcome::Created(created)),
+                Err(e) => {
+                    // Rolls back the rows inserted so far, which become `NotImported` again.
+                    drop(tx);
+                    let failed = self.insert_failed(user, e).await;
+                    let mut outcomes: Vec<_> =
+                        rows.iter().map(|_| ImportRowOutcome::NotImported).collect();
+                    outcomes[index] = failed;
+                    return Ok(outcomes);
+                }
+            }
+        }
+        tx.commit().await?;
+
+        Ok(outcomes)
+    }
+
+    /// Inserts each row in a transaction of its own, as `CreateUserUsecase` would.
+    async fn import_each(&self, rows: Vec<PendingRow>) -> anyhow::Result<ImportUsersOutput> {
+        let mut outcomes = Vec::with_capacity(rows.len());
+        for row in rows {
+            let outcome = match row {
+                PendingRow::Done(outcome) => outcome,
+                PendingRow::Insert(user) => {
+                    let tx = self.unit_of_work.begin().await?;
+                    if is_email_taken(tx.as_ref(), &user.email).await? {
+                        ImportRowOutcome::Duplicate
+                    } else {
+                        match insert(tx.as_ref(), &user).await {
+                            Ok(created) => {
+                                tx.commit().await?;
+                                ImportRowOutcome::Created(created)
+                            }
+                            Err(e) => {
+                                drop(tx);
+                                self.insert_failed(&user, e).await
+                            }
+                        }
+                    }
+                }
+            };
+            outcomes.push(outcome);
+        }
+
+        Ok(outcomes)
+    }
+
+    /// Reports a row whose insert failed as `Duplicate` if a concurrent request has taken its
+    /// email since it was checked, and as `Failed` otherwise, rather than failing the import.
+    async fn insert_failed(&self, user: &User, error: anyhow::Error) -> ImportRowOutcome {
+        tracing::warn!(error = %error, "failed to import user");
+        let taken = match self.unit_of_work.begin().await {
+            Ok(tx) => is_email_taken(tx.as_ref(), &user.email).await,
+            Err(e) => Err(e),
+        };
+        match taken {
+            Ok(true) => ImportRowOutcome::Duplicate,
+            Ok(false) => ImportRowOutcome::Failed,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to recheck the email of a user");
+                ImportRowOutcome::Failed
+            }
+        }
+    }
+}
+
+/// A row that is either ready to insert or already settled.
+enum PendingRow {
+    Insert(User),
+    Done(ImportRowOutcome),
+}
+
+/// Creates `user` and records it in the audit log, both within `tx`.
+async fn insert(
+    tx: &(dyn TransactionInterface + Send + Sync),
+    user: &User,
+) -> anyhow::Result<User> {
+    let created = tx.user_repository().create(user).await?;
+    audit_log::record(tx, &DomainEvent::user_created(created.id.clone())).await?;
+    Ok(created)
+}
+
+async fn is_email_taken(
+    tx: &(dyn TransactionInterface + Send + Sync),
+    email: &str,
+) -> anyhow::Result<bool> {
+    match tx
+        .user_email_duplicate_validator()
+        .validate_user_email_duplicate(email)
+        .await
+    {
+        Ok(()) => Ok(false),
+        Err(UserEmailDuplicateValidationError::AlreadyExists) => Ok(true),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use domain::interface::{
+        audit_log_repository_interface::MockAuditLogRepositoryInterface,
+        event_publisher_interface::MockEventPublisherInterface,
+        unit_of_work_interface::{MockTransactionInterface, MockUnitOfWorkInterface},
+        user_email_duplicate_validator_interface::MockUserEmailDuplicateValidatorInterface,
+        user_repository_interface::MockUserRepositoryInterface,
+    };
+
+    /// A unit of work whose transactions all hand out the given mocks; `begins` is how many
+    /// transactions the import is expected to start.
+    fn mocked_unit_of_work(
+        user_repository: MockUserRepositoryInterface,
+        user_email_duplicate_validator: MockUserEmailDuplicateValidatorInterface,
+        begins: usize,
+    ) -> MockUnitOfWorkInterface {
+        let user_repository = Arc::new(user_repository);
+        let user_email_duplicate_validator = Arc::new(user_email_duplicate_validator);
+        let mut unit_of_work = MockUnitOfWorkInterface::new();
+        unit_of_work
+            .expect_begin()
+            .times(begins)
+            .returning(move || {
+                let user_repository = user_repository.clone();
+                let user_email_duplicate_validator = user_email_duplicate_validator.clone();
+                let mut tx = MockTransactionInterface::new();
+                tx.expect_user_repository()
+                    .returning(move || user_repository.clone());
+                tx.expect_user_email_duplicate_validator()
+                    .returning(move || user_email_duplicate_validator.clone());
+                tx.expect_audit_log_repository().returning(|| {
+                    let mut audit_log_repository = MockAuditLogRepositoryInterface::new();
+                    audit_log_repository.expect_append().returning(|_| Ok(()));
+                    Arc::new(audit_log_repository)
+                });
+                tx.expect_commit().returning(|| Ok(()));
+                Ok(Box::new(tx))
+            });
+        unit_of_work
+    }
+
+    /// Reports `taken@example.com` as an existing user's email.
+    fn duplicate_validator() -> MockUserEmailDuplicateValidatorInterface {
+        let mut user_email_duplicate_validator = MockUserEmailDuplicateValidatorInterface::new();
+        user_email_duplicate_validator
+            .expect_validate_user_email_duplicate()
+            .returning(|email| match email {
+                "taken@example.com" => Err(UserEmailDuplicateValidationError::AlreadyExists),
+                _ => Ok(()),
+            });
+        user_email_duplicate_validator
+    }
+
+    fn input(mode: ImportMode) -> ImportUsersInput {
+        let row = |email: &str| Ok(CreateUserInput::new("Imported User".into(), email.into()));
+        ImportUsersInput {
+            rows: vec![
+                row("first@example.com"),
+                row("taken@example.com"),
+                row("first@example.com"),
+                Err(BTreeMap::from([(
+                    "email".to_owned(),
+                    vec!["Invalid email".to_owned()],
+                )])),
+                row("second@example.com"),
+            ],
+            mode,
+        }
+    }
+
+    fn kinds(outcomes: &[ImportRowOutcome]) -> Vec<&'static str> {
+        outcomes
+            .iter()
+            .map(|outcome| match outcome {
+                ImportRowOutcome::Created(_) => "created",
+                ImportRowOutcome::Invalid(_) => "invalid",
+                ImportRowOutcome::Duplicate => "duplicate",
+                ImportRowOutcome::NotImported => "not_imported",
+                ImportRowOutcome::Failed => "failed",
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_transactional_import_creates_nothing_if_a_row_fails() -> anyhow::Result<()> {
+        let mut user_repository = MockUserRepositoryInterface::new();
+        user_repository.expect_create().never();
+        let mut event_publisher = MockEventPublisherInterface::new();
+        event_publisher.expect_publish().never();
+
+        let mut usecase = ImportUsersUsecase::new(
+            mocked_unit_of_work(user_repository, duplicate_validator(), 1),
+            event_publisher,
+        );
+        let outcomes = usecase.execute(input(ImportMode::Transaction)).await?;
+
+        assert_eq!(
+            kinds(&outcomes),
+            [
+                "not_imported",
+                "duplicate",
+                "duplicate",
+                "invalid",
+                "not_imported"
+            ]
+        );
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_per_row_import_creates_every_valid_row() -> anyhow::Result<()> {
+        let mut user_repository = MockUserRepositoryInterface::new();
+        user_repository
+            .expect_create()
+            .times(2)
+            .returning(|user| Ok(user.clone()));
+        let mut event_publisher = MockEventPublisherInterface::new();
+        event_publisher
+            .expect_publish()
+            .withf(|event| event.topic() == "user.created")
+            .times(2)
+            .returning(|_event| Ok(()));
+
+        let mut usecase = ImportUsersUsecase::new(
+            mocked_unit_of_work(user_repository, duplicate_validator(), 3),
+            event_publisher,
+        );
+        let outcomes = usecase.execute(input(ImportMode::PerRow)).await?;
+
+        assert_eq!(
+            kinds(&outcomes),
+            ["created", "duplicate", "duplicate", "invalid", "created"]
+        );
+        anyhow::Ok(())
+    }
+
+    /// Fails the insert of `second@example.com`.
+    fn failing_user_repository(inserts: usize) -> MockUserRepositoryInterface {
+        let mut user_repository = MockUserRepositoryInterface::new();
+        user_repository
+            .expect_create()
+            .times(inserts)
+            .returning(|user| match user.email.as_str() {
+                "second@example.com" => Err(anyhow::anyhow!("value too long for type")),
+                _ => Ok(user.clone()),
+            });
+        user_repository
+    }
+
+    fn valid_input(mode: ImportMode) -> ImportUsersInput {
+        let row = |email: &str| Ok(CreateUserInput::new("Imported User".into(), email.into()));
+        ImportUsersInput {
+            rows: vec![
+                row("first@example.com"),
+                row("second@example.com"),
+                row("third@example.com"),
+            ],
+            mode,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_per_row_import_reports_a_failed_insert() -> anyhow::Result<()> {
+        let mut event_publisher = MockEventPublisherInterface::new();
+        event_publisher
+            .expect_publish()
+            .times(2)
+            .returning(|_event| Ok(()));
+
+        // One transaction per row, and one more to recheck the email of the failed row.
+        let mut usecase = ImportUsersUsecase::new(
+            mocked_unit_of_work(failing_user_repository(3), duplicate_validator(), 4),
+            event_publisher,
+        );
+        let outcomes = usecase.execute(valid_input(ImportMode::PerRow)).await?;
+
+        assert_eq!(kinds(&outcomes), ["created", "failed", "created"]);
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transactional_import_reports_a_failed_insert() -> anyhow::Result<()> {
+        let mut event_publisher = MockEventPublisherInterface::new();
+        event_publisher.expect_publish().never();
+
+        let mut usecase = ImportUsersUsecase::new(
+            mocked_unit_of_work(failing_user_repository(2), duplicate_validator(), 2),
+            event_publisher,
+        );
+        let outcomes = usecase
+            .execute(valid_input(ImportMode::Transaction))
+            .await?;
+
+        assert_eq!(kinds(&outcomes), ["not_imported", "failed", "not_imported"]);
+        anyhow::Ok(())
+    }
+}
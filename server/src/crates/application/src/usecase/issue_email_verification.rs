@@ -0,0 +1,192 @@
+use chrono::{Duration, Utc};
+use domain::{
+    entity::{
+        email_verification_token::EmailVerificationToken, user::User, value_object::user_id::UserId,
+    },
+    error::email_verification_error::EmailVerificationError,
+    interface::{
+        email_verification_token_repository_interface::EmailVerificationTokenRepositoryInterface,
+        mailer_interface::MailerInterface, user_repository_interface::UserRepositoryInterface,
+    },
+};
+
+use crate::mail_template::EmailVerificationMail;
+
+pub type IssueEmailVerificationInput = UserId;
+
+pub type IssueEmailVerificationOutput = User;
+
+pub struct IssueEmailVerificationUsecase<T, U, V>
+where
+    T: UserRepositoryInterface,
+    U: EmailVerificationTokenRepositoryInterface,
+    V: MailerInterface,
+{
+    user_repository: T,
+    email_verification_token_repository: U,
+    mailer: V,
+    verification_url: String,
+    ttl: Duration,
+}
+
+impl<T, U, V> IssueEmailVerificationUsecase<T, U, V>
+where
+    T: UserRepositoryInterface,
+    U: EmailVerificationTokenRepositoryInterface,
+    V: MailerInterface,
+{
+    /// `verification_url` is the page the emailed link points at; the token is appended as the
+    /// `token` query parameter.
+    pub fn new(
+        user_repository: T,
+        email_verification_token_repository: U,
+        mailer: V,
+        verification_url: String,
+        ttl: Duration,
+    ) -> Self {
+        IssueEmailVerificationUsecase {
+            user_repository,
+            email_verification_token_repository,
+            mailer,
+            verification_url,
+            ttl,
+        }
+    }
+
+    #[tracing::instrument(name = "IssueEmailVerificationUsecase::execute", skip_all)]
+    pub async fn execute(
+        &self,
+        issue_email_verification_input: IssueEmailVerificationInput,
+    ) -> anyhow::Result<IssueEmailVerificationOutput> {
+        let user = self
+            .user_repository
+            .find_by_id(&issue_email_verification_input)
+            .await?;
+        if user.is_email_verified() {
+            return Err(EmailVerificationError::AlreadyVerified.into());
+        }
+
+        let (token, raw_token) =
+            EmailVerificationToken::issue(user.id.clone(), Utc::now(), self.ttl);
+        self.email_verification_token_repository
+            .create(&token)
+            .await?;
+
+        let separator = if self.verification_url.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        let verification_url = format!("{}{}token={}", self.verification_url, separator, raw_token);
+        let mail = EmailVerificationMail {
+            name: &user.name,
+            verification_url: &verification_url,
+            expires_in: self.ttl,
+        }
+        .render(user.email.clone());
+        self.mailer.send(&mail).await?;
+
+        anyhow::Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use domain::interface::{
+        email_verification_token_repository_interface::MockEmailVerificationTokenRepositoryInterface,
+        mailer_interface::MockMailerInterface,
+        user_repository_interface::MockUserRepositoryInterface,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_issue_email_verification_usecase_successful() -> anyhow::Result<()> {
+        let mut mocked_user_repository = MockUserRepositoryInterface::new();
+        let mut mocked_token_repository = MockEmailVerificationTokenRepositoryInterface::new();
+        let mut mocked_mailer = MockMailerInterface::new();
+        let user = User::new("Test User".into(), "test@example.com".into());
+        let user_id = user.id.clone();
+        let stored_token_hash = Arc::new(Mutex::new(None));
+
+        mocked_user_repository.expect_find_by_id().returning({
+            let user = user.clone();
+            move |_user_id| Ok(user.clone())
+        });
+        mocked_token_repository
+            .expect_create()
+            .withf({
+                let user_id = user_id.clone();
+                move |token| token.user_id == user_id && token.consumed_at.is_none()
+            })
+            .times(1)
+            .returning({
+                let stored_token_hash = stored_token_hash.clone();
+                move |token| {
+                    *stored_token_hash.lock().unwrap() = Some(token.token_hash.clone());
+                    Ok(())
+                }
+            });
+        mocked_mailer
+            .expect_send()
+            .withf({
+                let stored_token_hash = stored_token_hash.clone();
+                move |mail| {
+                    let raw_token = mail
+                        .text_body
+                        .split("https://example.com/verify?token=")
+                        .nth(1)
+                        .and_then(|rest| rest.split_whitespace().next())
+                        .unwrap_or_default();
+                    mail.to == "test@example.com"
+                        && stored_token_hash.lock().unwrap().as_deref()
+                            == Some(EmailVerificationToken::hash(raw_token).as_str())
+                }
+            })
+            .times(1)
+            .returning(|_mail| Ok(()));
+
+        let usecase = IssueEmailVerificationUsecase::new(
+            mocked_user_repository,
+            mocked_token_repository,
+            mocked_mailer,
+            "https://example.com/verify".into(),
+            Duration::hours(24),
+        );
+        let output = usecase.execute(user_id).await?;
+
+        assert_eq!(output, user);
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_issue_email_verification_for_verified_user_fails() {
+        let mut mocked_user_repository = MockUserRepositoryInterface::new();
+        let mocked_token_repository = MockEmailVerificationTokenRepositoryInterface::new();
+        let mut mocked_mailer = MockMailerInterface::new();
+        let mut user = User::new("Test User".into(), "test@example.com".into());
+        user.email_verified_at = Some(Utc::now());
+        let user_id = user.id.clone();
+
+        mocked_user_repository
+            .expect_find_by_id()
+            .returning(move |_user_id| Ok(user.clone()));
+        mocked_mailer.expect_send().never();
+
+        let usecase = IssueEmailVerificationUsecase::new(
+            mocked_user_repository,
+            mocked_token_repository,
+            mocked_mailer,
+            "https://example.com/verify".into(),
+            Duration::hours(24),
+        );
+        let result = usecase.execute(user_id).await;
+
+        match result.unwrap_err().downcast_ref::<EmailVerificationError>() {
+            Some(EmailVerificationError::AlreadyVerified) => {}
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::fmt;
+
+use domain::{
+    entity::{user::User, value_object::user_id::UserId},
+    error::user_error::UserConcurrencyError,
+    event::domain_event::DomainEvent,
+    interface::{
+        event_publisher_interface::EventPublisherInterface,
+        unit_of_work_interface::UnitOfWorkInterface,
+        user_email_duplicate_validator_interface::UserEmailDuplicateValidatorInterface,
+        user_repository_interface::UserRepositoryInterface,
+    },
+    redact::Redacted,
+};
+
+use crate::{
+    audit_log,
+    entity_tag::{EntityTagCondition, user_entity_tag},
+};
+
+pub struct UpdateUserInput {
+    pub id: UserId,
+    pub name: String,
+    pub email: String,
+    /// Only update the user if its current entity tag matches.
+    pub if_match: Option<EntityTagCondition>,
+}
+
+impl fmt::Debug for UpdateUserInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateUserInput")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("email", &Redacted)
+            .field("if_match", &self.if_match)
+            .finish()
+    }
+}
+
+pub type UpdateUserOutput = User;
+
+pub struct UpdateUserUsecase<T, U>
+where
+    T: UnitOfWorkInterface,
+    U: EventPublisherInterface,
+{
+    unit_of_work: T,
+    event_publisher: U,
+}
+
+impl<T, U> UpdateUserUsecase<T, U>
+where
+    T: UnitOfWorkInterface,
+    U: EventPublisherInterface,
+{
+    pub fn new(unit_of_work: T, event_publisher: U) -> Self {
+        UpdateUserUsecase {
+            unit_of_work,
+            event_publisher,
+        }
+    }
+
+    #[tracing::instrument(name = "UpdateUserUsecase::execute", skip_all)]
+    pub async fn execute(
+        &mut self,
+        update_user_input: UpdateUserInput,
+    ) -> anyhow::Result<UpdateUserOutput> {
+        let tx = self.unit_of_work.begin().await?;
+        let user_repository = tx.user_repository();
+        let mut user = user_repository.find_by_id(&update_user_input.id).await?;
+        if let Some(if_match) = &update_user_input.if_match
+            && !if_match.matches_strong(&user_entity_tag(&user))
+        {
+            return Err(UserConcurrencyError::PreconditionFailed.into());
+        }
+        if update_user_input.email != user.email {
+            tx.user_email_duplicate_validator()
+                .validate_user_email_duplicate(&update_user_input.email)
+                .await?;
+        }
+
+        user.update(update_user_input.name, update_user_input.email);
+        let updated_user = user_repository.update(&user).await?;
+        drop(user_repository);
+        let event = DomainEvent::user_updated(updated_user.id.clone());
+        audit_log::record(tx.as_ref(), &event).await?;
+        tx.commit().await?;
+
+        if let Err(e) = self.event_publisher.publish(&event).await {
+            tracing::warn!(error = %e, "failed to publish user updated event");
+        }
+        anyhow::Ok(updated_user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use domain::{
+        error::user_error::UserEmailDuplicateValidationError,
+        interface::{
+            audit_log_repository_interface::MockAuditLogRepositoryInterface,
+            event_publisher_interface::MockEventPublisherInterface,
+            unit_of_work_interface::{MockTransactionInterface, MockUnitOfWorkInterface},
+            user_email_duplicate_validator_interface::MockUserEmailDuplicateValidatorInterface,
+            user_repository_interface::MockUserRepositoryInterface,
+        },
+    };
+
+    /// A unit of work whose single transaction hands out the given mocks and expects to be
+    /// committed only if `commits` is set.
+    fn mocked_unit_of_work(
+        user_repository: MockUserRepositoryInterface,
+        user_email_duplicate_validator: MockUserEmailDuplicateValidatorInterface,
+        commits: bool,
+    ) -> MockUnitOfWorkInterface {
+        let user_repository = Arc::new(user_repository);
+        let user_email_duplicate_validator = Arc::new(user_email_duplicate_validator);
+        let mut tx = MockTransactionInterface::new();
+        tx.expect_user_repository()
+            .returning(move || user_repository.clone());
+        tx.expect_user_email_duplicate_validator()
+            .returning(move || user_email_duplicate_validator.clone());
+        tx.expect_audit_log_repository().returning(|| {
+            let mut audit_log_repository = MockAuditLogRepositoryInterface::new();
+            audit_log_repository.expect_append().returning(|_| Ok(()));
+            Arc::new(audit_log_repository)
+        });
+        tx.expect_commit()
+            .times(usize::from(commits))
+            .returning(|| Ok(()));
+
+        let mut unit_of_work = MockUnitOfWorkInterface::new();
+        let mut tx = Some(tx);
+        unit_of_work
+            .expect_begin()
+            .times(1)
+            .returning(move || Ok(Box::new(tx.take().expect("begin should be called once"))));
+        unit_of_work
+    }
+
+    fn stored_user_repository(user: &User) -> MockUserRepositoryInterface {
+        let mut user_repository = MockUserRepositoryInterface::new();
+        user_repository.expect_find_by_id().returning({
+            let user = user.clone();
+            move |_user_id| Ok(user.clone())
+        });
+        user_repository
+    }
+
+    #[tokio::test]
+    async fn test_update_user_usecase_successful() -> anyhow::Result<()> {
+        let user = User::new("Test User".into(), "test@example.com".into());
+        let mut user_repository = stored_user_repository(&user);
+        user_repository
+            .expect_update()
+            .withf(|user| user.name == "Renamed User" && user.email == "renamed@example.com")
+            .times(1)
+            .returning(|user| Ok(user.clone()));
+        let mut user_email_duplicate_validator = MockUserEmailDuplicateValidatorInterface::new();
+        user_email_duplicate_validator
+            .expect_validate_user_email_duplicate()
+            .withf(|email| email == "renamed@example.com")
+            .times(1)
+            .returning(|_email| Ok(()));
+        let mut event_publisher = MockEventPublisherInterface::new();
+        event_publisher
+            .expect_publish()
+            .withf(|event| event.topic() == "user.updated")
+            .times(1)
+            .returning(|_event| Ok(()));
+
+        let mut usecase = UpdateUserUsecase::new(
+            mocked_unit_of_work(user_repository, user_email_duplicate_validator, true),
+            event_publisher,
+        );
+        let result = usecase
+            .execute(UpdateUserInput {
+                id: user.id.clone(),
+                name: "Renamed User".into(),
+                email: "renamed@example.com".into(),
+                if_match: Some(EntityTagCondition::parse(&user_entity_tag(&user))),
+            })
+            .await?;
+
+        assert_eq!(
+            (result.id, result.name.as_str(), result.email.as_str()),
+            (user.id, "Renamed User", "renamed@example.com")
+        );
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_user_with_stale_entity_tag_fails() {
+        let user = User::new("Test User".into(), "test@example.com".into());
+        let mut user_repository = stored_user_repository(&user);
+        user_repository.expect_update().never();
+        let mut event_publisher = MockEventPublisherInterface::new();
+        event_publisher.expect_publish().never();
+
+        let mut usecase = UpdateUserUsecase::new(
+            mocked_unit_of_work(
+                user_repository,
+                MockUserEmailDuplicateValidatorInterface::new(),
+                false,
+            ),
+            event_publisher,
+        );
+        let result = usecase
+            .execute(UpdateUserInput {
+                id: user.id.clone(),
+                name: "Renamed User".into(),
+                email: user.email.clone(),
+                if_match: Some(EntityTagCondition::parse(r#""stale""#)),
+            })
+            .await;
+
+        match result.unwrap_err().downcast_ref::<UserConcurrencyError>() {
+            Some(UserConcurrencyError::PreconditionFailed) => {}
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_user_to_taken_email_fails() {
+        let user = User::new("Test User".into(), "test@example.com".into());
+        let mut user_repository = stored_user_repository(&user);
+        user_repository.expect_update().never();
+        let mut user_email_duplicate_validator = MockUserEmailDuplicateValidatorInterface::new();
+        user_email_duplicate_validator
+            .expect_validate_user_email_duplicate()
+            .returning(|_email| Err(UserEmailDuplicateValidationError::AlreadyExists));
+
+        let mut usecase = UpdateUserUsecase::new(
+            mocked_unit_of_work(user_repository, user_email_duplicate_validator, false),
+            MockEventPublisherInterface::new(),
+        );
+        let result = usecase
+            .execute(UpdateUserInput {
+                id: user.id.clone(),
+                name: user.name.clone(),
+                email: "taken@example.com".into(),
+                if_match: None,
+            })
+            .await;
+
+        match result
+            .unwrap_err()
+            .downcast_ref::<UserEmailDuplicateValidationError>()
+        {
+            Some(UserEmailDuplicateValidationError::AlreadyExists) => {}
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
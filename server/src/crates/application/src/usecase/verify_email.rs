@@ -0,0 +1,163 @@
+use chrono::Utc;
+use domain::{
+    entity::{email_verification_token::EmailVerificationToken, user::User},
+    error::email_verification_error::EmailVerificationError,
+    event::domain_event::DomainEvent,
+    interface::{
+        email_verification_token_repository_interface::EmailVerificationTokenRepositoryInterface,
+        event_publisher_interface::EventPublisherInterface,
+    },
+};
+
+use crate::audit_log;
+
+pub type VerifyEmailInput = String;
+
+pub type VerifyEmailOutput = User;
+
+pub struct VerifyEmailUsecase<T, U>
+where
+    T: EmailVerificationTokenRepositoryInterface,
+    U: EventPublisherInterface,
+{
+    email_verification_token_repository: T,
+    event_publisher: U,
+}
+
+impl<T, U> VerifyEmailUsecase<T, U>
+where
+    T: EmailVerificationTokenRepositoryInterface,
+    U: EventPublisherInterface,
+{
+    pub fn new(email_verification_token_repository: T, event_publisher: U) -> Self {
+        VerifyEmailUsecase {
+            email_verification_token_repository,
+            event_publisher,
+        }
+    }
+
+    #[tracing::instrument(name = "VerifyEmailUsecase::execute", skip_all)]
+    pub async fn execute(
+        &self,
+        verify_email_input: VerifyEmailInput,
+    ) -> anyhow::Result<VerifyEmailOutput> {
+        let token_hash = EmailVerificationToken::hash(&verify_email_input);
+        let token = self
+            .email_verification_token_repository
+            .find_by_hash(&token_hash)
+            .await?
+            .ok_or(EmailVerificationError::InvalidToken)?;
+
+        let now = Utc::now();
+        token.ensure_usable(now)?;
+        let event = DomainEvent::user_updated(token.user_id.clone());
+        let user = self
+            .email_verification_token_repository
+            .consume(&token, now, &audit_log::audit_event(&event))
+            .await?;
+
+        if let Err(e) = self.event_publisher.publish(&event).await {
+            tracing::warn!(error = %e, "failed to publish user updated event");
+        }
+        anyhow::Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use domain::{
+        entity::value_object::user_id::UserId,
+        interface::{
+            email_verification_token_repository_interface::MockEmailVerificationTokenRepositoryInterface,
+            event_publisher_interface::MockEventPublisherInterface,
+        },
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_verify_email_usecase_successful() -> anyhow::Result<()> {
+        let mut mocked_token_repository = MockEmailVerificationTokenRepositoryInterface::new();
+        let mut user = User::new("Test User".into(), "test@example.com".into());
+        let (token, raw_token) =
+            EmailVerificationToken::issue(user.id.clone(), Utc::now(), Duration::hours(1));
+
+        mocked_token_repository
+            .expect_find_by_hash()
+            .withf({
+                let token_hash = token.token_hash.clone();
+                move |hash| hash == token_hash
+            })
+            .returning(move |_hash| Ok(Some(token.clone())));
+        mocked_token_repository
+            .expect_consume()
+            .withf(|token, _now, audit_event| {
+                audit_event.subject_id.as_ref() == Some(&token.user_id)
+            })
+            .times(1)
+            .returning({
+                let user = user.clone();
+                move |_token, now, _audit_event| {
+                    let mut user = user.clone();
+                    user.email_verified_at = Some(now);
+                    Ok(user)
+                }
+            });
+
+        let mut mocked_event_publisher = MockEventPublisherInterface::new();
+        mocked_event_publisher
+            .expect_publish()
+            .withf(|event| event.topic() == "user.updated")
+            .times(1)
+            .returning(|_event| Ok(()));
+
+        let usecase = VerifyEmailUsecase::new(mocked_token_repository, mocked_event_publisher);
+        let result = usecase.execute(raw_token).await?;
+
+        user.email_verified_at = result.email_verified_at;
+        assert_eq!(result, user);
+        assert!(result.is_email_verified());
+        anyhow::Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_email_with_unknown_token_fails() {
+        let mut mocked_token_repository = MockEmailVerificationTokenRepositoryInterface::new();
+        mocked_token_repository
+            .expect_find_by_hash()
+            .returning(|_hash| Ok(None));
+
+        let usecase =
+            VerifyEmailUsecase::new(mocked_token_repository, MockEventPublisherInterface::new());
+        let result = usecase.execute("unknown".into()).await;
+
+        match result.unwrap_err().downcast_ref::<EmailVerificationError>() {
+            Some(EmailVerificationError::InvalidToken) => {}
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_email_with_expired_token_fails() {
+        let mut mocked_token_repository = MockEmailVerificationTokenRepositoryInterface::new();
+        let (token, raw_token) = EmailVerificationToken::issue(
+            UserId::new(),
+            Utc::now() - Duration::hours(2),
+            Duration::hours(1),
+        );
+        mocked_token_repository
+            .expect_find_by_hash()
+            .returning(move |_hash| Ok(Some(token.clone())));
+        mocked_token_repository.expect_consume().never();
+
+        let usecase =
+            VerifyEmailUsecase::new(mocked_token_repository, MockEventPublisherInterface::new());
+        let result = usecase.execute(raw_token).await;
+
+        match result.unwrap_err().downcast_ref::<EmailVerificationError>() {
+            Some(EmailVerificationError::Expired) => {}
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt;
+
+use application::request_response::{
+    create_user_request::CreateUserRequestBody, create_user_response::CreateUserResponseBody,
+    csrf_token_response::CsrfTokenResponseBody,
+    export_users_request::{ExportUsersFormat, ExportUsersRequestQuery},
+    find_all_user_response::FindAllUserResponseBody,
+    find_audit_events_request::FindAuditEventsRequestQuery,
+    find_audit_events_response::FindAuditEventsResponseBody,
+    find_user_by_id_response::FindUserByIdResponseBody,
+    import_users_request::{ImportUsersMode, ImportUsersRequestQuery},
+    import_users_response::ImportUsersResponseBody, problem_response::ProblemResponseBody,
+    update_user_request::UpdateUserRequestBody, update_user_response::UpdateUserResponseBody,
+    verify_email_response::VerifyEmailResponseBody,
+};
+use domain::redact::Redacted;
+use reqwest::{
+    IntoUrl, RequestBuilder, Response,
+    header::{CONTENT_TYPE, ETAG, IF_MATCH},
+};
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use crate::error::{ClientError, ProblemKind};
+
+/// Calls the API with one method per route. Error statuses come back as
+/// [`ClientError::Problem`] whenever the server answered with `application/problem+json`.
+///
+/// Requests carry neither cookies nor an `Origin`, which is what the server's CSRF checks
+/// expect from other services.
+#[derive(Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    admin_token: Option<String>,
+}
+
+/// A response body along with its `ETag`, ready to pass back as `if_match`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tagged<T> {
+    pub body: T,
+    pub etag: Option<String>,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.base_url)
+            .field("admin_token", &self.admin_token.as_ref().map(|_| Redacted))
+            .finish()
+    }
+}
+
+impl Client {
+    pub fn new(base_url: impl IntoUrl) -> Result<Self, ClientError> {
+        let base_url = base_url.into_url()?;
+
+        Ok(Client {
+            http: reqwest::Client::new(),
+            base_url: base_url.as_str().trim_end_matches('/').to_owned(),
+            admin_token: None,
+        })
+    }
+
+    /// Uses a preconfigured client, e.g. one with timeouts or a proxy.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Sent as a bearer token to the admin routes only.
+    pub fn with_admin_token(mut self, token: impl Into<String>) -> Self {
+        self.admin_token = Some(token.into());
+        self
+    }
+
+    pub async fn create_user(
+        &self,
+        body: &CreateUserRequestBody,
+    ) -> Result<CreateUserResponseBody, ClientError> {
+        json(self.http.post(self.url("/v1/users")).json(body)).await
+    }
+
+    pub async fn find_all_users(&self) -> Result<FindAllUserResponseBody, ClientError> {
+        json(self.http.get(self.url("/v1/users"))).await
+    }
+
+    pub async fn find_user_by_id(
+        &self,
+        id: Uuid,
+    ) -> Result<Tagged<FindUserByIdResponseBody>, ClientError> {
+        tagged(self.http.get(self.url(&format!("/v1/users/{}", id)))).await
+    }
+
+    /// Admin only. Pass the `ETag` of the user as last read to fail with
+    /// [`ProblemKind::PreconditionFailed`] instead of overwriting someone else's change.
+    pub async fn update_user(
+        &self,
+        id: Uuid,
+        body: &UpdateUserRequestBody,
+        if_match: Option<&str>,
+    ) -> Result<Tagged<UpdateUserResponseBody>, ClientError> {
+        let request = self
+            .admin(self.http.put(self.url(&format!("/v1/users/{}", id))))
+            .json(body);
+
+        tagged(with_if_match(request, if_match)).await
+    }
+
+    /// Admin only; `if_match` works as for [`Client::update_user`].
+    pub async fn delete_user(&self, id: Uuid, if_match: Option<&str>) -> Result<(), ClientError> {
+        let request = self.admin(self.http.delete(self.url(&format!("/v1/users/{}", id))));
+        send(with_if_match(request, if_match)).await?;
+
+        Ok(())
+    }
+
+    /// Admin only. Creates a user per row as [`Client::create_user`] would, reporting each
+    /// row's outcome instead of failing on the first bad one.
+    pub async fn import_users(
+        &self,
+        rows: &[CreateUserRequestBody],
+        mode: ImportUsersMode,
+    ) -> Result<ImportUsersResponseBody, ClientError> {
+        json(
+            self.admin(self.http.post(self.url("/v1/users:import")))
+                .query(&ImportUsersRequestQuery { mode: Some(mode) })
+                .json(rows),
+        )
+        .await
+    }
+
+    /// Admin only. Returns the whole export as text; the server streams it, but this waits for
+    /// the end.
+    pub async fn export_users(&self, format: ExportUsersFormat) -> Result<String, ClientError> {
+        Ok(send(
+            self.admin(self.http.get(self.url("/v1/users/export")))
+                .query(&ExportUsersRequestQuery { format }),
+        )
+        .await?
+        .text()
+        .await?)
+    }
+
+    /// Admin only. Mails the user a fresh verification link.
+    pub async fn issue_email_verification(&self, id: Uuid) -> Result<(), ClientError> {
+        let url = self.url(&format!("/v1/users/{}/verification", id));
+        send(self.admin(self.http.post(url))).await?;
+
+        Ok(())
+    }
+
+    pub async fn issue_csrf_token(&self) -> Result<CsrfTokenResponseBody, ClientError> {
+        json(self.http.get(self.url("/v1/auth/csrf"))).await
+    }
+
+    pub async fn verify_email(&self, token: &str) -> Result<VerifyEmailResponseBody, ClientError> {
+        json(
+            self.http
+                .get(self.url("/v1/auth/verify-email"))
+                .query(&[("token", token)]),
+        )
+        .await
+    }
+
+    pub async fn find_audit_events(
+        &self,
+        query: &FindAuditEventsRequestQuery,
+    ) -> Result<FindAuditEventsResponseBody, ClientError> {
+        json(
+            self.admin(self.http.get(self.url("/v1/audit-events")))
+                .query(query),
+        )
+        .await
+    }
+
+    /// Admin only. Returns the Prometheus text exposition as is.
+    pub async fn metrics(&self) -> Result<String, ClientError> {
+        Ok(send(self.admin(self.http.get(self.url("/metrics"))))
+            .await?
+            .text()
+            .await?)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn admin(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.admin_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+fn with_if_match(request: RequestBuilder, if_match: Option<&str>) -> RequestBuilder {
+    match if_match {
+        Some(etag) => request.header(IF_MATCH, etag),
+        None => request,
+    }
+}
+
+async fn json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, ClientError> {
+    Ok(send(request).await?.json().await?)
+}
+
+async fn tagged<T: DeserializeOwned>(request: RequestBuilder) -> Result<Tagged<T>, ClientError> {
+    let response = send(request).await?;
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(str::to_owned);
+
+    Ok(Tagged {
+        body: response.json().await?,
+        etag,
+    })
+}
+
+async fn send(request: RequestBuilder) -> Result<Response, ClientError> {
+    let response = request.send().await?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let is_problem = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .is_some_and(|content_type| content_type == "application/problem+json");
+    let body = response.text().await?;
+    if is_problem && let Ok(problem) = serde_json::from_str::<ProblemResponseBody>(&body) {
+        return Err(ClientError::Problem {
+            status,
+            kind: ProblemKind::from_type(&problem.problem_type),
+            problem: Box::new(problem),
+        });
+    }
+
+    Err(ClientError::UnexpectedResponse { status, body })
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, http::StatusCode, routing::get};
+
+    use super::*;
+
+    async fn serve(router: Router) -> Client {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let address = listener
+            .local_addr()
+            .expect("listener should have an address");
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        Client::new(format!("http://{}/", address)).expect("base URL should parse")
+    }
+
+    #[tokio::test]
+    async fn test_error_without_problem_body_is_unexpected_response() {
+        let client = serve(Router::new().route(
+            "/v1/users",
+            get(|| async { (StatusCode::BAD_GATEWAY, "upstream unavailable") }),
+        ))
+        .await;
+
+        let error = client
+            .find_all_users()
+            .await
+            .expect_err("a 502 should be an error");
+
+        match error {
+            ClientError::UnexpectedResponse { status, body } => {
+                assert_eq!(
+                    (status, body.as_str()),
+                    (StatusCode::BAD_GATEWAY, "upstream unavailable")
+                )
+            }
+            other => panic!("expected an unexpected response, got {}", other),
+        }
+    }
+
+    #[test]
+    fn test_debug_redacts_admin_token() {
+        let client = Client::new("http://localhost:8080")
+            .expect("base URL should parse")
+            .with_admin_token("admin-secret");
+
+        assert!(!format!("{:?}", client).contains("admin-secret"));
+    }
+}
@@ -0,0 +1,103 @@
+use application::{problem_type, request_response::problem_response::ProblemResponseBody};
+use reqwest::StatusCode;
+
+/// The problem `type`s this client knows, so callers can match on them instead of on URIs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProblemKind {
+    Validate,
+    Duplicate,
+    NotFound,
+    NoContent,
+    InvalidJson,
+    MethodNotAllowed,
+    BadRequest,
+    NotAcceptable,
+    UnsupportedMediaType,
+    Conflict,
+    PreconditionFailed,
+    Unauthorized,
+    Forbidden,
+    InternalServerError,
+    Csrf,
+    TooManyRequests,
+    InvalidVerificationToken,
+    EmailAlreadyVerified,
+    IdempotencyKeyMismatch,
+    IdempotencyKeyInUse,
+    /// A type added to the server after this client was built.
+    Unknown,
+}
+
+impl ProblemKind {
+    pub fn from_type(problem_type: &str) -> Self {
+        match problem_type {
+            problem_type::VALIDATE => ProblemKind::Validate,
+            problem_type::DUPLICATE => ProblemKind::Duplicate,
+            problem_type::NOT_FOUND => ProblemKind::NotFound,
+            problem_type::NO_CONTENT => ProblemKind::NoContent,
+            problem_type::INVALID_JSON => ProblemKind::InvalidJson,
+            problem_type::METHOD_NOT_ALLOWED => ProblemKind::MethodNotAllowed,
+            problem_type::BAD_REQUEST => ProblemKind::BadRequest,
+            problem_type::NOT_ACCEPTABLE => ProblemKind::NotAcceptable,
+            problem_type::UNSUPPORTED_MEDIA_TYPE => ProblemKind::UnsupportedMediaType,
+            problem_type::CONFLICT => ProblemKind::Conflict,
+            problem_type::PRECONDITION_FAILED => ProblemKind::PreconditionFailed,
+            problem_type::UNAUTHORIZED => ProblemKind::Unauthorized,
+            problem_type::FORBIDDEN => ProblemKind::Forbidden,
+            problem_type::INTERNAL_SERVER_ERROR => ProblemKind::InternalServerError,
+            problem_type::CSRF => ProblemKind::Csrf,
+            problem_type::TOO_MANY_REQUESTS => ProblemKind::TooManyRequests,
+            problem_type::INVALID_VERIFICATION_TOKEN => ProblemKind::InvalidVerificationToken,
+            problem_type::EMAIL_ALREADY_VERIFIED => ProblemKind::EmailAlreadyVerified,
+            problem_type::IDEMPOTENCY_KEY_MISMATCH => ProblemKind::IdempotencyKeyMismatch,
+            problem_type::IDEMPOTENCY_KEY_IN_USE => ProblemKind::IdempotencyKeyInUse,
+            _ => ProblemKind::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("{status}: {}", .problem.title)]
+    Problem {
+        status: StatusCode,
+        kind: ProblemKind,
+        problem: Box<ProblemResponseBody>,
+    },
+
+    /// An error status without a problem body, e.g. from a proxy in front of the server.
+    #[error("Unexpected {status} response")]
+    UnexpectedResponse { status: StatusCode, body: String },
+
+    #[error("Request failed: {0}")]
+    Request(#[from] reqwest::Error),
+}
+
+impl ClientError {
+    pub fn problem_kind(&self) -> Option<ProblemKind> {
+        match self {
+            ClientError::Problem { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_problem_type_has_a_kind() {
+        let unknown: Vec<&str> = problem_type::ALL
+            .iter()
+            .copied()
+            .filter(|&t| ProblemKind::from_type(t) == ProblemKind::Unknown)
+            .collect();
+
+        assert_eq!(unknown, Vec::<&str>::new());
+        assert_eq!(
+            ProblemKind::from_type("https://example.com/problems/from-the-future"),
+            ProblemKind::Unknown
+        );
+    }
+}
@@ -1,2 +1,9 @@
+pub mod audit_event;
+pub mod email_verification_token;
+pub mod idempotency;
+pub mod mail;
+pub mod outbox_message;
+pub mod rate_limit;
+pub mod request_context;
 pub mod user;
-pub mod value_object;
\ No newline at end of file
+pub mod value_object;
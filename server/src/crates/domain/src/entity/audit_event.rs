@@ -0,0 +1,238 @@
+use std::{fmt, net::IpAddr};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    entity::{request_context::RequestContext, value_object::user_id::UserId},
+    event::domain_event::DomainEvent,
+    redact::Redacted,
+};
+
+pub const MAX_USER_AGENT_LEN: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    UserCreated,
+    UserUpdated,
+    UserDeleted,
+    UserLoggedIn,
+    UserRoleChanged,
+    SessionRevoked,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::UserCreated => "user.created",
+            AuditAction::UserUpdated => "user.updated",
+            AuditAction::UserDeleted => "user.deleted",
+            AuditAction::UserLoggedIn => "user.logged_in",
+            AuditAction::UserRoleChanged => "user.role_changed",
+            AuditAction::SessionRevoked => "session.revoked",
+        }
+    }
+}
+
+impl std::str::FromStr for AuditAction {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "user.created" => Ok(AuditAction::UserCreated),
+            "user.updated" => Ok(AuditAction::UserUpdated),
+            "user.deleted" => Ok(AuditAction::UserDeleted),
+            "user.logged_in" => Ok(AuditAction::UserLoggedIn),
+            "user.role_changed" => Ok(AuditAction::UserRoleChanged),
+            "session.revoked" => Ok(AuditAction::SessionRevoked),
+            other => Err(anyhow::anyhow!("unknown audit action: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A security-relevant action, recorded once and never changed.
+#[derive(Clone, PartialEq)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub action: AuditAction,
+    /// The authenticated user who performed the action; `None` for anonymous requests and
+    /// background jobs.
+    pub actor_id: Option<UserId>,
+    /// The user the action was performed on.
+    pub subject_id: Option<UserId>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl fmt::Debug for AuditEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditEvent")
+            .field("id", &self.id)
+            .field("action", &self.action)
+            .field("actor_id", &self.actor_id)
+            .field("subject_id", &self.subject_id)
+            .field("ip", &self.ip.as_ref().map(|_| Redacted))
+            .field("user_agent", &self.user_agent)
+            .field("occurred_at", &self.occurred_at)
+            .finish()
+    }
+}
+
+impl AuditEvent {
+    pub fn new(
+        action: AuditAction,
+        subject_id: Option<UserId>,
+        context: &RequestContext,
+        occurred_at: DateTime<Utc>,
+    ) -> Self {
+        AuditEvent {
+            id: Uuid::new_v4(),
+            action,
+            actor_id: context.actor_id.clone(),
+            subject_id,
+            ip: context.ip.as_deref().and_then(normalize_ip),
+            user_agent: context.user_agent.as_deref().map(truncate_user_agent),
+            occurred_at,
+        }
+    }
+
+    pub fn from_event(event: &DomainEvent, context: &RequestContext) -> Self {
+        let action = match event {
+            DomainEvent::UserCreated(_) => AuditAction::UserCreated,
+            DomainEvent::UserUpdated(_) => AuditAction::UserUpdated,
+            DomainEvent::UserDeleted(_) => AuditAction::UserDeleted,
+        };
+
+        Self::new(
+            action,
+            Some(event.user_id().clone()),
+            context,
+            event.occurred_at(),
+        )
+    }
+}
+
+/// Only a well-formed address is recorded, so whatever a client put in a forwarding header can
+/// never make the entry unwritable.
+fn normalize_ip(ip: &str) -> Option<String> {
+    ip.trim().parse::<IpAddr>().ok().map(|ip| ip.to_string())
+}
+
+fn truncate_user_agent(user_agent: &str) -> String {
+    let mut end = user_agent.len().min(MAX_USER_AGENT_LEN);
+    while !user_agent.is_char_boundary(end) {
+        end -= 1;
+    }
+    user_agent[..end].to_owned()
+}
+
+/// Narrows an audit log query; entries are returned newest first, starting below `before`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AuditEventFilter {
+    pub action: Option<AuditAction>,
+    pub actor_id: Option<UserId>,
+    pub subject_id: Option<UserId>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Cursor returned as `next_cursor` by the previous page.
+    pub before: Option<i64>,
+    pub limit: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEventPage {
+    pub events: Vec<AuditEvent>,
+    /// Pass as `before` to fetch the next page; `None` on the last page.
+    pub next_cursor: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_action_round_trips() {
+        for action in [
+            AuditAction::UserCreated,
+            AuditAction::UserUpdated,
+            AuditAction::UserDeleted,
+            AuditAction::UserLoggedIn,
+            AuditAction::UserRoleChanged,
+            AuditAction::SessionRevoked,
+        ] {
+            assert_eq!(action.as_str().parse::<AuditAction>().unwrap(), action);
+        }
+        assert!("user.renamed".parse::<AuditAction>().is_err());
+    }
+
+    #[test]
+    fn test_from_event_copies_request_context() {
+        let user_id = UserId::new();
+        let actor_id = UserId::new();
+        let event = DomainEvent::user_created(user_id.clone());
+        let context = RequestContext {
+            actor_id: Some(actor_id.clone()),
+            ip: Some("203.0.113.7".to_owned()),
+            user_agent: Some("curl/8.0".to_owned()),
+        };
+
+        let audit_event = AuditEvent::from_event(&event, &context);
+
+        assert_eq!(audit_event.action, AuditAction::UserCreated);
+        assert_eq!(audit_event.actor_id, Some(actor_id));
+        assert_eq!(audit_event.subject_id, Some(user_id));
+        assert_eq!(audit_event.ip.as_deref(), Some("203.0.113.7"));
+        assert_eq!(audit_event.user_agent.as_deref(), Some("curl/8.0"));
+        assert_eq!(audit_event.occurred_at, event.occurred_at());
+    }
+
+    #[test]
+    fn test_user_agent_is_truncated_on_a_char_boundary() {
+        let context = RequestContext {
+            user_agent: Some("é".repeat(MAX_USER_AGENT_LEN)),
+            ..RequestContext::default()
+        };
+
+        let audit_event = AuditEvent::new(AuditAction::SessionRevoked, None, &context, Utc::now());
+
+        let user_agent = audit_event.user_agent.unwrap();
+        assert_eq!(user_agent.len(), MAX_USER_AGENT_LEN);
+        assert!(user_agent.chars().all(|c| c == 'é'));
+    }
+
+    #[test]
+    fn test_ip_that_is_not_an_address_is_dropped() {
+        for ip in [
+            "203.0.113.7, 198.51.100.9",
+            "a".repeat(46).as_str(),
+            "unknown",
+        ] {
+            let context = RequestContext {
+                ip: Some(ip.to_owned()),
+                ..RequestContext::default()
+            };
+
+            let audit_event = AuditEvent::new(AuditAction::UserCreated, None, &context, Utc::now());
+
+            assert_eq!(audit_event.ip, None, "ip: {}", ip);
+        }
+    }
+
+    #[test]
+    fn test_debug_redacts_ip() {
+        let context = RequestContext {
+            ip: Some("203.0.113.7".to_owned()),
+            ..RequestContext::default()
+        };
+        let audit_event = AuditEvent::new(AuditAction::UserLoggedIn, None, &context, Utc::now());
+
+        assert!(!format!("{:?}", audit_event).contains("203.0.113.7"));
+    }
+}
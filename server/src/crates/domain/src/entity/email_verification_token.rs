@@ -0,0 +1,106 @@
+use std::fmt;
+
+use chrono::{DateTime, Duration, Utc};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::value_object::user_id::UserId;
+use crate::{error::email_verification_error::EmailVerificationError, redact::Redacted};
+
+const TOKEN_BYTES: usize = 32;
+
+/// A single-use token proving control of a user's email address. Only the SHA-256 hash of the
+/// token is kept; the raw value is handed out once by [`EmailVerificationToken::issue`].
+#[derive(Clone, PartialEq)]
+pub struct EmailVerificationToken {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub consumed_at: Option<DateTime<Utc>>,
+}
+
+impl fmt::Debug for EmailVerificationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmailVerificationToken")
+            .field("id", &self.id)
+            .field("user_id", &self.user_id)
+            .field("token_hash", &Redacted)
+            .field("expires_at", &self.expires_at)
+            .field("consumed_at", &self.consumed_at)
+            .finish()
+    }
+}
+
+impl EmailVerificationToken {
+    /// Returns the token to store together with the raw value to send to the user.
+    pub fn issue(user_id: UserId, now: DateTime<Utc>, ttl: Duration) -> (Self, String) {
+        let mut bytes = [0u8; TOKEN_BYTES];
+        rand::rng().fill_bytes(&mut bytes);
+        let raw_token = hex(&bytes);
+
+        let token = EmailVerificationToken {
+            id: Uuid::new_v4(),
+            user_id,
+            token_hash: Self::hash(&raw_token),
+            expires_at: now + ttl,
+            consumed_at: None,
+        };
+
+        (token, raw_token)
+    }
+
+    pub fn hash(raw_token: &str) -> String {
+        hex(&Sha256::digest(raw_token.as_bytes()))
+    }
+
+    pub fn ensure_usable(&self, now: DateTime<Utc>) -> Result<(), EmailVerificationError> {
+        if self.consumed_at.is_some() {
+            return Err(EmailVerificationError::InvalidToken);
+        }
+        if self.expires_at <= now {
+            return Err(EmailVerificationError::Expired);
+        }
+        Ok(())
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issued_token_stores_only_the_hash() {
+        let now = Utc::now();
+        let (token, raw_token) =
+            EmailVerificationToken::issue(UserId::new(), now, Duration::hours(24));
+
+        assert_eq!(raw_token.len(), TOKEN_BYTES * 2);
+        assert_ne!(token.token_hash, raw_token);
+        assert_eq!(token.token_hash, EmailVerificationToken::hash(&raw_token));
+        assert_eq!(token.expires_at, now + Duration::hours(24));
+        assert!(!format!("{:?}", token).contains(&token.token_hash));
+    }
+
+    #[test]
+    fn expired_or_consumed_token_is_not_usable() {
+        let now = Utc::now();
+        let (mut token, _) = EmailVerificationToken::issue(UserId::new(), now, Duration::hours(1));
+        assert!(token.ensure_usable(now).is_ok());
+        assert!(matches!(
+            token.ensure_usable(now + Duration::hours(1)),
+            Err(EmailVerificationError::Expired)
+        ));
+
+        token.consumed_at = Some(now);
+        assert!(matches!(
+            token.ensure_usable(now),
+            Err(EmailVerificationError::InvalidToken)
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::redact::Redacted;
+
+/// Identifies one logical request: the client-chosen `Idempotency-Key`, scoped to the route it
+/// was sent to and to whoever sent it, so two callers can never see each other's responses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey {
+    pub key: String,
+    /// Method and path, e.g. `POST /v1/users`.
+    pub route: String,
+    pub principal: String,
+}
+
+/// The first response to a request, replayed as is when the request is retried.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IdempotentResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl fmt::Debug for IdempotentResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdempotentResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .field("body", &Redacted)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    /// [`request_hash`] of the body the key was first used with.
+    pub request_hash: String,
+    /// `None` while the first request is still being processed.
+    pub response: Option<IdempotentResponse>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyClaim {
+    /// The key was unused or had expired; the caller processes the request and stores the
+    /// response.
+    Claimed,
+    /// The key is in use, and the caller replays or rejects based on this record.
+    Existing(IdempotencyRecord),
+}
+
+/// The SHA-256 of a request body, compared to tell a retry from a reuse of the key.
+pub fn request_hash(body: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_hash_distinguishes_bodies() {
+        let hash = request_hash(br#"{"name":"a"}"#);
+
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, request_hash(br#"{"name":"a"}"#));
+        assert_ne!(hash, request_hash(br#"{"name":"b"}"#));
+    }
+}
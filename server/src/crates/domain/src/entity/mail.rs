@@ -0,0 +1,54 @@
+use std::fmt;
+
+use crate::redact::Redacted;
+
+/// A rendered message ready to hand to a mailer. Bodies are kept out of `Debug` output since
+/// they usually carry single-use links.
+#[derive(Clone, PartialEq)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub text_body: String,
+    pub html_body: String,
+}
+
+impl fmt::Debug for Mail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mail")
+            .field("to", &Redacted)
+            .field("subject", &self.subject)
+            .field("text_body", &Redacted)
+            .field("html_body", &Redacted)
+            .finish()
+    }
+}
+
+impl Mail {
+    pub fn new(to: String, subject: String, text_body: String, html_body: String) -> Self {
+        Mail {
+            to,
+            subject,
+            text_body,
+            html_body,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mail_debug_redacts_recipient_and_bodies() {
+        let mail = Mail::new(
+            "test@example.com".into(),
+            "Hello".into(),
+            "secret link".into(),
+            "<p>secret link</p>".into(),
+        );
+        let debug = format!("{:?}", mail);
+        assert!(debug.contains("Hello"));
+        assert!(!debug.contains("test@example.com"));
+        assert!(!debug.contains("secret link"));
+    }
+}
@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::event::domain_event::DomainEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxStatus {
+    Pending,
+    Delivered,
+    /// Gave up after exhausting the retry policy; kept for inspection and manual replay.
+    Dead,
+}
+
+impl OutboxStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::Delivered => "delivered",
+            OutboxStatus::Dead => "dead",
+        }
+    }
+}
+
+impl std::str::FromStr for OutboxStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(OutboxStatus::Pending),
+            "delivered" => Ok(OutboxStatus::Delivered),
+            "dead" => Ok(OutboxStatus::Dead),
+            other => Err(anyhow::anyhow!("unknown outbox status: {}", other)),
+        }
+    }
+}
+
+/// A message recorded in the same transaction as the change it describes and delivered
+/// afterwards, so side effects such as emails only happen for committed writes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxMessage {
+    pub id: Uuid,
+    pub topic: String,
+    pub payload: serde_json::Value,
+    pub status: OutboxStatus,
+    /// Number of delivery attempts started so far, including the current one.
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+impl OutboxMessage {
+    pub fn new(topic: &str, payload: serde_json::Value) -> Self {
+        OutboxMessage {
+            id: Uuid::new_v4(),
+            topic: topic.to_owned(),
+            payload,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            next_attempt_at: Utc::now(),
+            last_error: None,
+        }
+    }
+
+    pub fn from_event(event: &DomainEvent) -> Self {
+        Self::new(event.topic(), event.payload())
+    }
+}
+
+/// Exponential backoff: the n-th failed attempt is retried after `base_delay * 2^(n-1)`, capped
+/// at `max_delay`, until `max_attempts` attempts have failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutboxRetryPolicy {
+    pub max_attempts: i32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl OutboxRetryPolicy {
+    /// Returns when to retry after `attempts` failed attempts, or `None` once the message
+    /// should be dead-lettered.
+    pub fn next_attempt_at(&self, attempts: i32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if attempts >= self.max_attempts {
+            return None;
+        }
+
+        let exponent = attempts.saturating_sub(1).clamp(0, 30) as u32;
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(exponent))
+            .min(self.max_delay);
+        Some(now + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::value_object::user_id::UserId;
+
+    #[test]
+    fn retry_policy_backs_off_exponentially_until_dead() {
+        let policy = OutboxRetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(10),
+            max_delay: Duration::from_secs(60),
+        };
+        let now = Utc::now();
+
+        assert_eq!(
+            policy.next_attempt_at(1, now),
+            Some(now + chrono::Duration::seconds(10))
+        );
+        assert_eq!(
+            policy.next_attempt_at(2, now),
+            Some(now + chrono::Duration::seconds(20))
+        );
+        assert_eq!(
+            policy.next_attempt_at(4, now),
+            Some(now + chrono::Duration::seconds(60))
+        );
+        assert_eq!(policy.next_attempt_at(5, now), None);
+    }
+
+    #[test]
+    fn message_from_event_carries_topic_and_payload() {
+        let event = DomainEvent::user_created(UserId::new());
+        let message = OutboxMessage::from_event(&event);
+
+        assert_eq!(message.topic, "user.created");
+        assert_eq!(message.status, OutboxStatus::Pending);
+        assert_eq!(message.payload, event.payload());
+    }
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+/// Token bucket settings: up to `capacity` requests in a burst, refilled evenly over `period`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitPolicy {
+    pub capacity: u32,
+    pub period: Duration,
+}
+
+impl RateLimitPolicy {
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        RateLimitPolicy { capacity, period }
+    }
+
+    pub fn tokens_per_second(&self) -> f64 {
+        f64::from(self.capacity) / self.period.as_secs_f64()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitDecision {
+    Allowed { remaining: u32 },
+    Limited { retry_after: Duration },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limit_policy_tokens_per_second() {
+        let policy = RateLimitPolicy::new(10, Duration::from_secs(60));
+        assert!((policy.tokens_per_second() - 10.0 / 60.0).abs() < f64::EPSILON);
+    }
+}
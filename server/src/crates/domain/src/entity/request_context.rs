@@ -0,0 +1,26 @@
+use std::fmt;
+
+use uuid::Uuid;
+
+use crate::{entity::value_object::user_id::UserId, redact::Redacted};
+
+/// Recorded as the actor of requests made with the admin API token, which belongs to no user.
+pub const ADMIN_TOKEN_ACTOR_ID: UserId = UserId(Uuid::max());
+
+/// Who made the current request and from where, as recorded in the audit log.
+#[derive(Clone, PartialEq, Default)]
+pub struct RequestContext {
+    pub actor_id: Option<UserId>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl fmt::Debug for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestContext")
+            .field("actor_id", &self.actor_id)
+            .field("ip", &self.ip.as_ref().map(|_| Redacted))
+            .field("user_agent", &self.user_agent)
+            .finish()
+    }
+}
@@ -1,19 +1,75 @@
+use std::fmt;
+
+use chrono::{DateTime, SubsecRound, Utc};
+
 use super::value_object::user_id::UserId;
+use crate::redact::Redacted;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct User {
     pub id: UserId,
     pub name: String,
     pub email: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    /// When the user was last written.
+    pub updated_at: DateTime<Utc>,
+    /// Counts the writes to the user, starting at 1. Repositories only write a user whose stored
+    /// version is still this one and return it incremented.
+    pub version: i64,
+}
+
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("User")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("email", &Redacted)
+            .field("email_verified_at", &self.email_verified_at)
+            .field("updated_at", &self.updated_at)
+            .field("version", &self.version)
+            .finish()
+    }
 }
 
 impl User {
     pub fn new(name: String, email: String) -> Self {
         let id = UserId::new();
-        User { id, name, email }
+        User {
+            id,
+            name,
+            email,
+            email_verified_at: None,
+            updated_at: now(),
+            version: 1,
+        }
+    }
+
+    /// Replaces the name and email. A changed email address has not been verified yet.
+    pub fn update(&mut self, name: String, email: String) {
+        if email != self.email {
+            self.email_verified_at = None;
+        }
+        self.name = name;
+        self.email = email;
+        self.touch();
+    }
+
+    /// Records a write to the user.
+    pub fn touch(&mut self) {
+        self.updated_at = now();
+    }
+
+    pub fn is_email_verified(&self) -> bool {
+        self.email_verified_at.is_some()
     }
 }
 
+/// The current time at the microsecond precision Postgres stores, so a user reads back exactly
+/// as it was written.
+fn now() -> DateTime<Utc> {
+    Utc::now().trunc_subsecs(6)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -23,5 +79,34 @@ mod tests {
         let user = User::new("Test User".into(), "test@example.com".into());
         assert_eq!(user.name, "Test User");
         assert_eq!(user.email, "test@example.com");
+        assert!(!user.is_email_verified());
+    }
+
+    #[test]
+    fn user_debug_redacts_email() {
+        let user = User::new("Test User".into(), "test@example.com".into());
+        let debug = format!("{:?}", user);
+        assert!(debug.contains("Test User"));
+        assert!(!debug.contains("test@example.com"));
+        assert!(debug.contains("[redacted]"));
+    }
+
+    #[test]
+    fn updating_email_resets_verification() {
+        let mut user = User::new("Test User".into(), "test@example.com".into());
+        user.email_verified_at = Some(Utc::now());
+
+        user.update("Renamed User".into(), "test@example.com".into());
+        assert!(user.is_email_verified());
+
+        user.update("Renamed User".into(), "changed@example.com".into());
+        assert_eq!(
+            (
+                user.name.as_str(),
+                user.email.as_str(),
+                user.is_email_verified()
+            ),
+            ("Renamed User", "changed@example.com", false)
+        );
     }
 }
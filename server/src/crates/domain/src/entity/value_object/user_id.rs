@@ -4,7 +4,6 @@ use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 #[repr(transparent)]
-
 pub struct UserId(pub Uuid);
 
 impl UserId {
@@ -13,6 +12,12 @@ impl UserId {
     }
 }
 
+impl Default for UserId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl From<Uuid> for UserId {
     fn from(uuid: Uuid) -> Self {
         UserId(uuid)
@@ -1 +1,2 @@
+pub mod email_verification_error;
 pub mod user_error;
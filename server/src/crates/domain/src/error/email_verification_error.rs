@@ -0,0 +1,14 @@
+#[derive(Debug, thiserror::Error)]
+pub enum EmailVerificationError {
+    #[error("Email verification token is invalid")]
+    InvalidToken,
+
+    #[error("Email verification token has expired")]
+    Expired,
+
+    #[error("User email is already verified")]
+    AlreadyVerified,
+
+    #[error("Unexpected error: {0}")]
+    Unexpected(#[from] sqlx::Error),
+}
@@ -6,3 +6,15 @@ pub enum UserEmailDuplicateValidationError {
     #[error("Unexpected error: {0}")]
     Unexpected(#[from] sqlx::Error),
 }
+
+/// A write based on a revision of the user that is no longer the stored one.
+#[derive(Debug, thiserror::Error)]
+pub enum UserConcurrencyError {
+    /// The client's precondition (an `If-Match` ETag) names an outdated revision.
+    #[error("User has been modified since it was read")]
+    PreconditionFailed,
+
+    /// Another write committed between reading the user and writing it back.
+    #[error("User was modified concurrently")]
+    Conflict,
+}
@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::entity::value_object::user_id::UserId;
+
+pub const USER_CREATED_TOPIC: &str = "user.created";
+pub const USER_UPDATED_TOPIC: &str = "user.updated";
+pub const USER_DELETED_TOPIC: &str = "user.deleted";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserCreated {
+    pub user_id: UserId,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserUpdated {
+    pub user_id: UserId,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserDeleted {
+    pub user_id: UserId,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Something that happened to a user, named in the past tense. Events carry identifiers only;
+/// subscribers load whatever else they need.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DomainEvent {
+    UserCreated(UserCreated),
+    UserUpdated(UserUpdated),
+    UserDeleted(UserDeleted),
+}
+
+impl DomainEvent {
+    pub const TOPICS: [&'static str; 3] =
+        [USER_CREATED_TOPIC, USER_UPDATED_TOPIC, USER_DELETED_TOPIC];
+
+    pub fn user_created(user_id: UserId) -> Self {
+        DomainEvent::UserCreated(UserCreated {
+            user_id,
+            occurred_at: Utc::now(),
+        })
+    }
+
+    pub fn user_updated(user_id: UserId) -> Self {
+        DomainEvent::UserUpdated(UserUpdated {
+            user_id,
+            occurred_at: Utc::now(),
+        })
+    }
+
+    pub fn user_deleted(user_id: UserId) -> Self {
+        DomainEvent::UserDeleted(UserDeleted {
+            user_id,
+            occurred_at: Utc::now(),
+        })
+    }
+
+    pub fn topic(&self) -> &'static str {
+        match self {
+            DomainEvent::UserCreated(_) => USER_CREATED_TOPIC,
+            DomainEvent::UserUpdated(_) => USER_UPDATED_TOPIC,
+            DomainEvent::UserDeleted(_) => USER_DELETED_TOPIC,
+        }
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        match self {
+            DomainEvent::UserCreated(event) => &event.user_id,
+            DomainEvent::UserUpdated(event) => &event.user_id,
+            DomainEvent::UserDeleted(event) => &event.user_id,
+        }
+    }
+
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            DomainEvent::UserCreated(event) => event.occurred_at,
+            DomainEvent::UserUpdated(event) => event.occurred_at,
+            DomainEvent::UserDeleted(event) => event.occurred_at,
+        }
+    }
+
+    /// Serializes the event body; the variant is carried separately as the [`topic`](Self::topic).
+    pub fn payload(&self) -> serde_json::Value {
+        let payload = match self {
+            DomainEvent::UserCreated(event) => serde_json::to_value(event),
+            DomainEvent::UserUpdated(event) => serde_json::to_value(event),
+            DomainEvent::UserDeleted(event) => serde_json::to_value(event),
+        };
+        payload.expect("domain events should serialize to JSON")
+    }
+
+    pub fn from_payload(topic: &str, payload: serde_json::Value) -> Result<Self, anyhow::Error> {
+        Ok(match topic {
+            USER_CREATED_TOPIC => DomainEvent::UserCreated(serde_json::from_value(payload)?),
+            USER_UPDATED_TOPIC => DomainEvent::UserUpdated(serde_json::from_value(payload)?),
+            USER_DELETED_TOPIC => DomainEvent::UserDeleted(serde_json::from_value(payload)?),
+            other => anyhow::bail!("unknown event topic: {}", other),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn domain_event_round_trips_through_topic_and_payload() {
+        let event = DomainEvent::user_created(UserId::new());
+
+        let decoded = DomainEvent::from_payload(event.topic(), event.payload()).unwrap();
+        assert_eq!(decoded, event);
+        assert_eq!(event.payload()["user_id"], event.user_id().to_string());
+    }
+
+    #[test]
+    fn unknown_topic_fails_to_decode() {
+        let event = DomainEvent::user_deleted(UserId::new());
+
+        assert!(DomainEvent::from_payload("user.renamed", event.payload()).is_err());
+        assert!(DomainEvent::TOPICS.contains(&event.topic()));
+    }
+}
@@ -1,2 +1,12 @@
+pub mod audit_log_repository_interface;
+pub mod email_verification_token_repository_interface;
+pub mod event_publisher_interface;
+pub mod event_subscriber_interface;
+pub mod idempotency_store_interface;
+pub mod mailer_interface;
+pub mod outbox_handler_interface;
+pub mod outbox_repository_interface;
+pub mod rate_limit_store_interface;
+pub mod unit_of_work_interface;
 pub mod user_repository_interface;
 pub mod user_email_duplicate_validator_interface;
\ No newline at end of file
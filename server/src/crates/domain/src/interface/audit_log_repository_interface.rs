@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::entity::audit_event::{AuditEvent, AuditEventFilter, AuditEventPage};
+
+/// Append-only: entries can be recorded and read back but never changed or removed.
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait AuditLogRepositoryInterface {
+    async fn append(&self, event: &AuditEvent) -> Result<(), anyhow::Error>;
+    async fn find(&self, filter: &AuditEventFilter) -> Result<AuditEventPage, anyhow::Error>;
+}
+
+#[async_trait::async_trait]
+impl<T> AuditLogRepositoryInterface for Arc<T>
+where
+    T: AuditLogRepositoryInterface + Send + Sync + ?Sized,
+{
+    async fn append(&self, event: &AuditEvent) -> Result<(), anyhow::Error> {
+        (**self).append(event).await
+    }
+
+    async fn find(&self, filter: &AuditEventFilter) -> Result<AuditEventPage, anyhow::Error> {
+        (**self).find(filter).await
+    }
+}
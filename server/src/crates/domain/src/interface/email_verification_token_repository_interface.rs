@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    entity::{
+        audit_event::AuditEvent, email_verification_token::EmailVerificationToken, user::User,
+    },
+    error::email_verification_error::EmailVerificationError,
+};
+
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait EmailVerificationTokenRepositoryInterface {
+    /// Stores `token`, invalidating any token previously issued to the same user.
+    async fn create(&self, token: &EmailVerificationToken) -> Result<(), anyhow::Error>;
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<EmailVerificationToken>, anyhow::Error>;
+    /// Marks `token` as consumed and the owner's email as verified at `now`, and appends
+    /// `audit_event` to the audit log, all at once. Fails with
+    /// [`EmailVerificationError::InvalidToken`] if the token was consumed in the meantime.
+    async fn consume(
+        &self,
+        token: &EmailVerificationToken,
+        now: DateTime<Utc>,
+        audit_event: &AuditEvent,
+    ) -> Result<User, EmailVerificationError>;
+}
+
+#[async_trait::async_trait]
+impl<T> EmailVerificationTokenRepositoryInterface for Arc<T>
+where
+    T: EmailVerificationTokenRepositoryInterface + Send + Sync + ?Sized,
+{
+    async fn create(&self, token: &EmailVerificationToken) -> Result<(), anyhow::Error> {
+        (**self).create(token).await
+    }
+
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<EmailVerificationToken>, anyhow::Error> {
+        (**self).find_by_hash(token_hash).await
+    }
+
+    async fn consume(
+        &self,
+        token: &EmailVerificationToken,
+        now: DateTime<Utc>,
+        audit_event: &AuditEvent,
+    ) -> Result<User, EmailVerificationError> {
+        (**self).consume(token, now, audit_event).await
+    }
+}
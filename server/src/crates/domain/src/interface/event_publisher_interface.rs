@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use crate::event::domain_event::DomainEvent;
+
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait EventPublisherInterface {
+    async fn publish(&self, event: &DomainEvent) -> Result<(), anyhow::Error>;
+}
+
+#[async_trait::async_trait]
+impl<T> EventPublisherInterface for Arc<T>
+where
+    T: EventPublisherInterface + Send + Sync + ?Sized,
+{
+    async fn publish(&self, event: &DomainEvent) -> Result<(), anyhow::Error> {
+        (**self).publish(event).await
+    }
+}
@@ -0,0 +1,10 @@
+use crate::event::domain_event::DomainEvent;
+
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait EventSubscriberInterface {
+    /// Identifies the subscriber in logs when it fails.
+    fn name(&self) -> &'static str;
+    /// Reacts to `event`, ignoring events it has no interest in.
+    async fn handle(&self, event: &DomainEvent) -> Result<(), anyhow::Error>;
+}
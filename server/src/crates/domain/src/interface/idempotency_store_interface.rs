@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+
+use crate::entity::idempotency::{IdempotencyClaim, IdempotencyKey, IdempotentResponse};
+
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait IdempotencyStoreInterface {
+    /// Marks `key` as in flight until `expires_at`, unless a record that has not expired by
+    /// `now` already exists, in which case that record is returned instead.
+    async fn claim(
+        &self,
+        key: &IdempotencyKey,
+        request_hash: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<IdempotencyClaim, anyhow::Error>;
+
+    /// Stores the response of a claimed request, to be replayed until `expires_at`.
+    async fn complete(
+        &self,
+        key: &IdempotencyKey,
+        response: &IdempotentResponse,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), anyhow::Error>;
+
+    /// Drops a claim whose request failed, so it can be retried with the same key.
+    async fn release(&self, key: &IdempotencyKey) -> Result<(), anyhow::Error>;
+
+    /// Deletes the records that expired by `now`, returning how many there were.
+    async fn purge_expired(&self, now: DateTime<Utc>) -> Result<u64, anyhow::Error>;
+}
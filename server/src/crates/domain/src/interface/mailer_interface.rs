@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use crate::entity::mail::Mail;
+
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait MailerInterface {
+    async fn send(&self, mail: &Mail) -> Result<(), anyhow::Error>;
+}
+
+/// Lets a backend chosen at startup (`Arc<dyn MailerInterface + Send + Sync>`) be passed to
+/// usecases that are generic over the mailer.
+#[async_trait::async_trait]
+impl<T> MailerInterface for Arc<T>
+where
+    T: MailerInterface + Send + Sync + ?Sized,
+{
+    async fn send(&self, mail: &Mail) -> Result<(), anyhow::Error> {
+        (**self).send(mail).await
+    }
+}
@@ -0,0 +1,11 @@
+use crate::entity::outbox_message::OutboxMessage;
+
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait OutboxHandlerInterface {
+    /// Topics this handler delivers; messages on other topics are left for other dispatchers.
+    fn topics(&self) -> Vec<String>;
+    /// Delivers `message`. Handlers must tolerate redelivery, since a message whose outcome
+    /// could not be recorded is attempted again.
+    async fn handle(&self, message: &OutboxMessage) -> Result<(), anyhow::Error>;
+}
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::entity::outbox_message::OutboxMessage;
+
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait OutboxRepositoryInterface {
+    /// Claims up to `limit` due pending messages on `topics`, counting a delivery attempt and
+    /// hiding them from other dispatchers for `lease` in case this one dies mid-delivery.
+    async fn claim_due(
+        &self,
+        topics: &[String],
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<OutboxMessage>, anyhow::Error>;
+    async fn mark_delivered(&self, id: &Uuid) -> Result<(), anyhow::Error>;
+    /// Records a failed attempt; the message is retried at `retry_at`, or dead-lettered if it
+    /// is `None`.
+    async fn mark_failed(
+        &self,
+        id: &Uuid,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), anyhow::Error>;
+}
@@ -0,0 +1,12 @@
+use crate::entity::rate_limit::{RateLimitDecision, RateLimitPolicy};
+
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait RateLimitStoreInterface {
+    /// Takes one token from the bucket identified by `key`, creating a full bucket on first use.
+    async fn acquire(
+        &self,
+        key: &str,
+        policy: &RateLimitPolicy,
+    ) -> Result<RateLimitDecision, anyhow::Error>;
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::interface::{
+    audit_log_repository_interface::AuditLogRepositoryInterface,
+    user_email_duplicate_validator_interface::UserEmailDuplicateValidatorInterface,
+    user_repository_interface::UserRepositoryInterface,
+};
+
+/// Starts transactions whose repositories share one connection, so a usecase can make several
+/// reads and writes that commit or roll back together.
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait UnitOfWorkInterface {
+    async fn begin(&self) -> Result<Box<dyn TransactionInterface + Send + Sync>, anyhow::Error>;
+}
+
+#[async_trait::async_trait]
+impl<T> UnitOfWorkInterface for Arc<T>
+where
+    T: UnitOfWorkInterface + Send + Sync + ?Sized,
+{
+    async fn begin(&self) -> Result<Box<dyn TransactionInterface + Send + Sync>, anyhow::Error> {
+        (**self).begin().await
+    }
+}
+
+/// Repositories scoped to a single transaction. Dropping it without calling `commit` rolls
+/// every write back; the repositories handed out must be dropped before committing.
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait TransactionInterface {
+    fn user_repository(&self) -> Arc<dyn UserRepositoryInterface + Send + Sync>;
+    fn user_email_duplicate_validator(
+        &self,
+    ) -> Arc<dyn UserEmailDuplicateValidatorInterface + Send + Sync>;
+    /// Appends entries that commit or roll back with the writes they describe.
+    fn audit_log_repository(&self) -> Arc<dyn AuditLogRepositoryInterface + Send + Sync>;
+    async fn commit(self: Box<Self>) -> Result<(), anyhow::Error>;
+    async fn rollback(self: Box<Self>) -> Result<(), anyhow::Error>;
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::error::user_error::UserEmailDuplicateValidationError;
 
 #[mockall::automock]
@@ -5,3 +7,17 @@ use crate::error::user_error::UserEmailDuplicateValidationError;
 pub trait UserEmailDuplicateValidatorInterface {
     async fn validate_user_email_duplicate(&self, email: &str) -> Result<(), UserEmailDuplicateValidationError>;
 }
+
+/// Forwards to a shared validator, such as the trait object held by `AppState`.
+#[async_trait::async_trait]
+impl<T> UserEmailDuplicateValidatorInterface for Arc<T>
+where
+    T: UserEmailDuplicateValidatorInterface + Send + Sync + ?Sized,
+{
+    async fn validate_user_email_duplicate(
+        &self,
+        email: &str,
+    ) -> Result<(), UserEmailDuplicateValidationError> {
+        (**self).validate_user_email_duplicate(email).await
+    }
+}
@@ -1,3 +1,7 @@
+use std::sync::Arc;
+
+use futures_util::stream::BoxStream;
+
 use crate::entity::{user::User, value_object::user_id::UserId};
 
 #[mockall::automock]
@@ -5,5 +9,45 @@ use crate::entity::{user::User, value_object::user_id::UserId};
 pub trait UserRepositoryInterface {
     async fn create(&self, user: &User) -> Result<User, anyhow::Error>;
     async fn find_all(&self) -> Result<Vec<User>, anyhow::Error>;
+    /// Yields the users of [`UserRepositoryInterface::find_all`], in the same order, as they are
+    /// read instead of collecting them first.
+    fn stream_all(&self) -> BoxStream<'static, Result<User, anyhow::Error>>;
     async fn find_by_id(&self, user_id: &UserId) -> Result<User, anyhow::Error>;
+    /// Overwrites the stored user with the same ID and returns it with its version incremented.
+    /// Fails with `UserConcurrencyError::Conflict` if the stored version is no longer
+    /// `user.version`.
+    async fn update(&self, user: &User) -> Result<User, anyhow::Error>;
+    /// Fails with `UserConcurrencyError::Conflict` like [`UserRepositoryInterface::update`].
+    async fn delete(&self, user: &User) -> Result<(), anyhow::Error>;
+}
+
+/// Lets `AppState` hold whichever backend was configured as an `Arc<dyn ...>`.
+#[async_trait::async_trait]
+impl<T> UserRepositoryInterface for Arc<T>
+where
+    T: UserRepositoryInterface + Send + Sync + ?Sized,
+{
+    async fn create(&self, user: &User) -> Result<User, anyhow::Error> {
+        (**self).create(user).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<User>, anyhow::Error> {
+        (**self).find_all().await
+    }
+
+    fn stream_all(&self) -> BoxStream<'static, Result<User, anyhow::Error>> {
+        (**self).stream_all()
+    }
+
+    async fn find_by_id(&self, user_id: &UserId) -> Result<User, anyhow::Error> {
+        (**self).find_by_id(user_id).await
+    }
+
+    async fn update(&self, user: &User) -> Result<User, anyhow::Error> {
+        (**self).update(user).await
+    }
+
+    async fn delete(&self, user: &User) -> Result<(), anyhow::Error> {
+        (**self).delete(user).await
+    }
 }
@@ -1,3 +1,7 @@
 pub mod entity;
+pub mod event;
 pub mod interface;
-pub mod error;
\ No newline at end of file
+pub mod error;
+pub mod redact;
+#[cfg(feature = "testing")]
+pub mod testing;
@@ -0,0 +1,10 @@
+use std::fmt;
+
+/// Stands in for emails, tokens and other secrets in `Debug` output so they never reach the logs.
+pub struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
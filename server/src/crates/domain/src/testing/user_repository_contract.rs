@@ -0,0 +1,300 @@
+//! Behaviour every [`UserRepositoryInterface`] implementation must share, so fakes used in tests
+//! can be trusted to act like the Postgres repository.
+//!
+//! Each case only asserts on users it created itself, so the suite can run against a shared
+//! database alongside other tests.
+
+use futures_util::TryStreamExt;
+
+use crate::{
+    entity::{user::User, value_object::user_id::UserId},
+    error::user_error::UserConcurrencyError,
+    interface::user_repository_interface::UserRepositoryInterface,
+};
+
+fn unique_user(name: &str) -> User {
+    User::new(
+        name.to_owned(),
+        format!("contract+{}@example.com", uuid::Uuid::new_v4()),
+    )
+}
+
+fn assert_not_found(result: Result<User, anyhow::Error>) {
+    match result {
+        Err(e) => assert!(
+            matches!(
+                e.downcast_ref::<sqlx::Error>(),
+                Some(sqlx::Error::RowNotFound)
+            ),
+            "a missing user should be reported as `sqlx::Error::RowNotFound`, got: {}",
+            e
+        ),
+        Ok(user) => panic!("expected no user, found {}", user.id),
+    }
+}
+
+fn assert_conflict<T>(result: Result<T, anyhow::Error>) {
+    match result {
+        Err(e) => assert!(
+            matches!(
+                e.downcast_ref::<UserConcurrencyError>(),
+                Some(UserConcurrencyError::Conflict)
+            ),
+            "a stale version should be reported as `UserConcurrencyError::Conflict`, got: {}",
+            e
+        ),
+        Ok(_) => panic!("expected a write with a stale version to be rejected"),
+    }
+}
+
+pub async fn create_returns_the_stored_user(repository: &impl UserRepositoryInterface) {
+    let user = unique_user("Contract User");
+
+    let created = repository.create(&user).await.expect("should create user");
+    assert_eq!(created, user);
+
+    let found = repository
+        .find_by_id(&user.id)
+        .await
+        .expect("created user should be found");
+    assert_eq!(found, user);
+}
+
+pub async fn create_rejects_duplicate_email(repository: &impl UserRepositoryInterface) {
+    let user = unique_user("Contract User");
+    repository.create(&user).await.expect("should create user");
+    let duplicate = User::new("Contract Duplicate".to_owned(), user.email.clone());
+
+    let result = repository.create(&duplicate).await;
+
+    assert!(
+        result.is_err(),
+        "a second user with the same email should be rejected"
+    );
+    assert_not_found(repository.find_by_id(&duplicate.id).await);
+}
+
+pub async fn find_by_id_reports_missing_user(repository: &impl UserRepositoryInterface) {
+    assert_not_found(repository.find_by_id(&UserId::new()).await);
+}
+
+pub async fn update_overwrites_the_stored_user(repository: &impl UserRepositoryInterface) {
+    let mut user = unique_user("Contract User");
+    repository.create(&user).await.expect("should create user");
+    user.update(
+        "Contract Renamed".to_owned(),
+        format!("contract+{}@example.com", uuid::Uuid::new_v4()),
+    );
+
+    let updated = repository.update(&user).await.expect("should update user");
+    let expected = User {
+        version: user.version + 1,
+        ..user.clone()
+    };
+    assert_eq!(updated, expected);
+
+    let found = repository
+        .find_by_id(&user.id)
+        .await
+        .expect("updated user should be found");
+    assert_eq!(found, expected);
+}
+
+pub async fn update_and_delete_reject_stale_version(repository: &impl UserRepositoryInterface) {
+    let stale = unique_user("Contract User");
+    repository.create(&stale).await.expect("should create user");
+    let mut first = stale.clone();
+    first.update("Contract First".to_owned(), stale.email.clone());
+    let first = repository.update(&first).await.expect("should update user");
+
+    let mut second = stale.clone();
+    second.update("Contract Second".to_owned(), stale.email.clone());
+    assert_conflict(repository.update(&second).await);
+    assert_conflict(repository.delete(&stale).await);
+
+    let found = repository
+        .find_by_id(&stale.id)
+        .await
+        .expect("user should still be found");
+    assert_eq!(found, first);
+}
+
+pub async fn update_rejects_duplicate_email(repository: &impl UserRepositoryInterface) {
+    let taken = unique_user("Contract User");
+    repository.create(&taken).await.expect("should create user");
+    let user = unique_user("Contract Other");
+    repository.create(&user).await.expect("should create user");
+
+    let mut changed = user.clone();
+    changed.update(changed.name.clone(), taken.email.clone());
+    let result = repository.update(&changed).await;
+
+    assert!(
+        result.is_err(),
+        "an update to an email in use should be rejected"
+    );
+    let found = repository
+        .find_by_id(&user.id)
+        .await
+        .expect("user should still be found");
+    assert_eq!(found, user);
+}
+
+pub async fn update_reports_missing_user(repository: &impl UserRepositoryInterface) {
+    let user = unique_user("Contract User");
+
+    assert_not_found(repository.update(&user).await);
+}
+
+pub async fn delete_removes_the_user(repository: &impl UserRepositoryInterface) {
+    let user = unique_user("Contract User");
+    repository.create(&user).await.expect("should create user");
+
+    repository.delete(&user).await.expect("should delete user");
+
+    assert_not_found(repository.find_by_id(&user.id).await);
+    assert_not_found(repository.delete(&user).await.map(|()| user.clone()));
+}
+
+pub async fn find_all_orders_by_name(repository: &impl UserRepositoryInterface) {
+    // A shared prefix keeps this case's users together however the names of others sort; it is
+    // kept short since the `user` table caps names at 40 characters.
+    let prefix = format!(
+        "Contract {} ",
+        &uuid::Uuid::new_v4().simple().to_string()[..12]
+    );
+    let mut users = Vec::new();
+    for name in ["Carol", "Alice", "Bob"] {
+        let user = unique_user(&format!("{}{}", prefix, name));
+        repository.create(&user).await.expect("should create user");
+        users.push(user);
+    }
+
+    let names: Vec<String> = repository
+        .find_all()
+        .await
+        .expect("should find all users")
+        .into_iter()
+        .filter(|user| users.iter().any(|u| u.id == user.id))
+        .map(|user| user.name)
+        .collect();
+
+    assert_eq!(
+        names,
+        vec![
+            format!("{}Alice", prefix),
+            format!("{}Bob", prefix),
+            format!("{}Carol", prefix),
+        ]
+    );
+}
+
+pub async fn stream_all_matches_find_all(repository: &impl UserRepositoryInterface) {
+    let mut users = Vec::new();
+    for name in ["Contract Streamed Bob", "Contract Streamed Alice"] {
+        let user = unique_user(name);
+        repository.create(&user).await.expect("should create user");
+        users.push(user);
+    }
+    let own = |user: &User| users.iter().any(|u| u.id == user.id);
+
+    let found: Vec<User> = repository
+        .find_all()
+        .await
+        .expect("should find all users")
+        .into_iter()
+        .filter(own)
+        .collect();
+    let streamed: Vec<User> = repository
+        .stream_all()
+        .try_filter(|user| std::future::ready(own(user)))
+        .try_collect()
+        .await
+        .expect("should stream all users");
+
+    assert_eq!(streamed.len(), 2);
+    assert_eq!(streamed, found);
+}
+
+/// Expands to one `#[tokio::test]` per contract case, each run against the repository that
+/// `$repository` (an expression yielding a future) resolves to.
+#[macro_export]
+macro_rules! user_repository_contract_tests {
+    ($repository:expr) => {
+        #[tokio::test]
+        async fn contract_create_returns_the_stored_user() {
+            $crate::testing::user_repository_contract::create_returns_the_stored_user(
+                &$repository.await,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn contract_create_rejects_duplicate_email() {
+            $crate::testing::user_repository_contract::create_rejects_duplicate_email(
+                &$repository.await,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn contract_find_by_id_reports_missing_user() {
+            $crate::testing::user_repository_contract::find_by_id_reports_missing_user(
+                &$repository.await,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn contract_update_overwrites_the_stored_user() {
+            $crate::testing::user_repository_contract::update_overwrites_the_stored_user(
+                &$repository.await,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn contract_update_rejects_duplicate_email() {
+            $crate::testing::user_repository_contract::update_rejects_duplicate_email(
+                &$repository.await,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn contract_update_reports_missing_user() {
+            $crate::testing::user_repository_contract::update_reports_missing_user(
+                &$repository.await,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn contract_update_and_delete_reject_stale_version() {
+            $crate::testing::user_repository_contract::update_and_delete_reject_stale_version(
+                &$repository.await,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn contract_delete_removes_the_user() {
+            $crate::testing::user_repository_contract::delete_removes_the_user(&$repository.await)
+                .await;
+        }
+
+        #[tokio::test]
+        async fn contract_find_all_orders_by_name() {
+            $crate::testing::user_repository_contract::find_all_orders_by_name(&$repository.await)
+                .await;
+        }
+
+        #[tokio::test]
+        async fn contract_stream_all_matches_find_all() {
+            $crate::testing::user_repository_contract::stream_all_matches_find_all(
+                &$repository.await,
+            )
+            .await;
+        }
+    };
+}
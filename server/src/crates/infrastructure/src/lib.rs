@@ -1,2 +1,5 @@
+pub mod mailer;
 pub mod model;
-pub mod repository;
\ No newline at end of file
+pub mod repository;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
@@ -0,0 +1,24 @@
+pub mod console_mailer;
+pub mod file_mailer;
+pub mod in_memory_mailer;
+pub mod smtp_mailer;
+
+use domain::entity::mail::Mail;
+use lettre::{
+    Message,
+    message::{Mailbox, MultiPart},
+};
+
+/// Builds a `multipart/alternative` message so clients pick the HTML or the text body.
+pub(crate) fn to_message(from: &Mailbox, mail: &Mail) -> Result<Message, anyhow::Error> {
+    let message = Message::builder()
+        .from(from.clone())
+        .to(mail.to.parse()?)
+        .subject(mail.subject.as_str())
+        .multipart(MultiPart::alternative_plain_html(
+            mail.text_body.clone(),
+            mail.html_body.clone(),
+        ))?;
+
+    Ok(message)
+}
@@ -0,0 +1,28 @@
+use std::io::Write;
+
+use domain::{entity::mail::Mail, interface::mailer_interface::MailerInterface};
+
+/// Prints the text body of every message to stdout. Meant for local development only: unlike
+/// the logs, the output includes recipients and single-use links.
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleMailer;
+
+impl ConsoleMailer {
+    pub fn new() -> Self {
+        ConsoleMailer
+    }
+}
+
+#[async_trait::async_trait]
+impl MailerInterface for ConsoleMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), anyhow::Error> {
+        let mut stdout = std::io::stdout().lock();
+        writeln!(
+            stdout,
+            "----- mail -----\nTo: {}\nSubject: {}\n\n{}\n----------------",
+            mail.to, mail.subject, mail.text_body
+        )?;
+
+        Ok(())
+    }
+}
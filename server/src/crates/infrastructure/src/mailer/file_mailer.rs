@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use domain::{entity::mail::Mail, interface::mailer_interface::MailerInterface};
+use lettre::message::Mailbox;
+
+use super::to_message;
+
+/// Writes every message as an `.eml` file into `dir` instead of sending it, for local
+/// development without an SMTP server.
+#[derive(Debug, Clone)]
+pub struct FileMailer {
+    dir: PathBuf,
+    from: Mailbox,
+}
+
+impl FileMailer {
+    pub fn new(dir: PathBuf, from: &str) -> Result<Self, anyhow::Error> {
+        Ok(FileMailer {
+            dir,
+            from: from.parse()?,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl MailerInterface for FileMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), anyhow::Error> {
+        let message = to_message(&self.from, mail)?;
+        tokio::fs::create_dir_all(&self.dir).await?;
+
+        let file_name = format!(
+            "{}-{}.eml",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+            uuid::Uuid::new_v4()
+        );
+        let path = self.dir.join(file_name);
+        tokio::fs::write(&path, message.formatted()).await?;
+        tracing::info!(path = %path.display(), "mail written to file");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_send_writes_eml_file() {
+        let dir = std::env::temp_dir().join(format!("file-mailer-{}", uuid::Uuid::new_v4()));
+        let mailer = FileMailer::new(dir.clone(), "no-reply@example.com").unwrap();
+
+        mailer
+            .send(&Mail::new(
+                "test@example.com".into(),
+                "Hello".into(),
+                "Hello in text".into(),
+                "<p>Hello in HTML</p>".into(),
+            ))
+            .await
+            .expect("mail should be written");
+
+        let entries = std::fs::read_dir(&dir)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        let contents = std::fs::read_to_string(entries[0].path()).unwrap();
+        assert!(contents.contains("To: test@example.com"));
+        assert!(contents.contains("Subject: Hello"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
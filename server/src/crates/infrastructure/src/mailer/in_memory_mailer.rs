@@ -0,0 +1,57 @@
+use std::sync::{Arc, Mutex};
+
+use domain::{entity::mail::Mail, interface::mailer_interface::MailerInterface};
+
+/// Captures messages instead of sending them so tests can assert on what would have been sent.
+/// Clones share the same outbox.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryMailer {
+    sent: Arc<Mutex<Vec<Mail>>>,
+}
+
+impl InMemoryMailer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sent(&self) -> Vec<Mail> {
+        self.sent.lock().expect("mailer lock poisoned").clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl MailerInterface for InMemoryMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), anyhow::Error> {
+        self.sent
+            .lock()
+            .map_err(|_| anyhow::Error::msg("mailer lock poisoned"))?
+            .push(mail.clone());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sent_mail_is_captured_across_clones() {
+        let mailer = InMemoryMailer::new();
+        let clone = mailer.clone();
+
+        clone
+            .send(&Mail::new(
+                "test@example.com".into(),
+                "Hello".into(),
+                "text".into(),
+                "<p>html</p>".into(),
+            ))
+            .await
+            .unwrap();
+
+        let sent = mailer.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].subject, "Hello");
+    }
+}
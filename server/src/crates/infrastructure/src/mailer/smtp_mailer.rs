@@ -0,0 +1,169 @@
+use std::fmt;
+
+use domain::{entity::mail::Mail, interface::mailer_interface::MailerInterface, redact::Redacted};
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Tokio1Executor, message::Mailbox,
+    transport::smtp::authentication::Credentials,
+};
+
+use super::to_message;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// Plain text; only for relays on a trusted network such as a local Mailpit.
+    None,
+    StartTls,
+    Tls,
+}
+
+#[derive(Clone)]
+pub struct SmtpMailerSettings {
+    pub host: String,
+    pub port: u16,
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+}
+
+impl fmt::Debug for SmtpMailerSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmtpMailerSettings")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("tls", &self.tls)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| Redacted))
+            .field("from", &self.from)
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(settings: SmtpMailerSettings) -> Result<Self, anyhow::Error> {
+        let builder = match settings.tls {
+            SmtpTls::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&settings.host)
+            }
+            SmtpTls::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.host)?
+            }
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&settings.host)?,
+        };
+        let builder = builder.port(settings.port);
+        let builder = match (settings.username, settings.password) {
+            (Some(username), Some(password)) => {
+                builder.credentials(Credentials::new(username, password))
+            }
+            _ => builder,
+        };
+
+        Ok(SmtpMailer {
+            transport: builder.build(),
+            from: settings.from.parse()?,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl MailerInterface for SmtpMailer {
+    #[tracing::instrument(name = "SmtpMailer::send", skip_all, fields(otel.kind = "client"))]
+    async fn send(&self, mail: &Mail) -> Result<(), anyhow::Error> {
+        let message = to_message(&self.from, mail)?;
+        self.transport.send(message).await.map_err(|e| {
+            tracing::error!(error = %e, "failed to send mail");
+            anyhow::Error::msg("Failed to send mail")
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    /// Accepts a single SMTP session and returns the DATA section it received.
+    async fn fake_smtp_server(listener: TcpListener) -> String {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut data = String::new();
+        let mut in_data = false;
+
+        writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            if in_data {
+                if line == "." {
+                    in_data = false;
+                    writer.write_all(b"250 OK\r\n").await.unwrap();
+                } else {
+                    data.push_str(&line);
+                    data.push('\n');
+                }
+                continue;
+            }
+
+            let command = line.to_ascii_uppercase();
+            let reply: &[u8] = if command.starts_with("EHLO") {
+                b"250 localhost\r\n"
+            } else if command.starts_with("DATA") {
+                in_data = true;
+                b"354 End data with <CR><LF>.<CR><LF>\r\n"
+            } else if command.starts_with("QUIT") {
+                writer.write_all(b"221 Bye\r\n").await.unwrap();
+                break;
+            } else {
+                b"250 OK\r\n"
+            };
+            writer.write_all(reply).await.unwrap();
+        }
+
+        data
+    }
+
+    #[tokio::test]
+    async fn test_send_mail_over_smtp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(fake_smtp_server(listener));
+
+        let mailer = SmtpMailer::new(SmtpMailerSettings {
+            host: "127.0.0.1".into(),
+            port,
+            tls: SmtpTls::None,
+            username: None,
+            password: None,
+            from: "Server <no-reply@example.com>".into(),
+        })
+        .expect("mailer should build");
+        mailer
+            .send(&Mail::new(
+                "test@example.com".into(),
+                "Hello".into(),
+                "Hello in text".into(),
+                "<p>Hello in HTML</p>".into(),
+            ))
+            .await
+            .expect("mail should be sent");
+        drop(mailer);
+
+        let data = server.await.unwrap();
+        assert!(data.contains("To: test@example.com"));
+        assert!(data.contains("Subject: Hello"));
+        assert!(data.contains("multipart/alternative"));
+        assert!(data.contains("Hello in text"));
+        assert!(data.contains("<p>Hello in HTML</p>"));
+    }
+}
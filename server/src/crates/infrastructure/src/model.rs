@@ -1 +1,5 @@
-pub mod user_model;
\ No newline at end of file
+pub mod audit_event_model;
+pub mod email_verification_token_model;
+pub mod idempotency_record_model;
+pub mod outbox_message_model;
+pub mod user_model;
@@ -0,0 +1,95 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use domain::{
+    entity::{audit_event::AuditEvent, value_object::user_id::UserId},
+    redact::Redacted,
+};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow)]
+pub struct AuditEventModel {
+    /// Insertion order, used as the pagination cursor.
+    pub seq: i64,
+    pub id: Uuid,
+    pub action: String,
+    pub actor_id: Option<Uuid>,
+    pub subject_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl fmt::Debug for AuditEventModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditEventModel")
+            .field("seq", &self.seq)
+            .field("id", &self.id)
+            .field("action", &self.action)
+            .field("actor_id", &self.actor_id)
+            .field("subject_id", &self.subject_id)
+            .field("ip", &self.ip.as_ref().map(|_| Redacted))
+            .field("user_agent", &self.user_agent)
+            .field("occurred_at", &self.occurred_at)
+            .finish()
+    }
+}
+
+impl TryFrom<AuditEventModel> for AuditEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(model: AuditEventModel) -> Result<Self, Self::Error> {
+        Ok(AuditEvent {
+            id: model.id,
+            action: model.action.parse()?,
+            actor_id: model.actor_id.map(UserId::from),
+            subject_id: model.subject_id.map(UserId::from),
+            ip: model.ip,
+            user_agent: model.user_agent,
+            occurred_at: model.occurred_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::entity::audit_event::AuditAction;
+
+    use super::*;
+
+    #[test]
+    fn audit_event_model_to_audit_event_conversion_works() {
+        let subject_id = Uuid::new_v4();
+        let model = AuditEventModel {
+            seq: 1,
+            id: Uuid::new_v4(),
+            action: "user.updated".to_owned(),
+            actor_id: None,
+            subject_id: Some(subject_id),
+            ip: Some("203.0.113.7".to_owned()),
+            user_agent: None,
+            occurred_at: Utc::now(),
+        };
+
+        let event = AuditEvent::try_from(model).unwrap();
+        assert_eq!(event.action, AuditAction::UserUpdated);
+        assert_eq!(event.subject_id, Some(UserId::from(subject_id)));
+        assert_eq!(event.ip.as_deref(), Some("203.0.113.7"));
+    }
+
+    #[test]
+    fn unknown_action_fails_conversion() {
+        let model = AuditEventModel {
+            seq: 1,
+            id: Uuid::new_v4(),
+            action: "user.renamed".to_owned(),
+            actor_id: None,
+            subject_id: None,
+            ip: None,
+            user_agent: None,
+            occurred_at: Utc::now(),
+        };
+
+        assert!(AuditEvent::try_from(model).is_err());
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use domain::{
+    entity::{email_verification_token::EmailVerificationToken, value_object::user_id::UserId},
+    redact::Redacted,
+};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow)]
+pub struct EmailVerificationTokenModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub consumed_at: Option<DateTime<Utc>>,
+}
+
+impl fmt::Debug for EmailVerificationTokenModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmailVerificationTokenModel")
+            .field("id", &self.id)
+            .field("user_id", &self.user_id)
+            .field("token_hash", &Redacted)
+            .field("expires_at", &self.expires_at)
+            .field("consumed_at", &self.consumed_at)
+            .finish()
+    }
+}
+
+impl From<EmailVerificationTokenModel> for EmailVerificationToken {
+    fn from(model: EmailVerificationTokenModel) -> Self {
+        EmailVerificationToken {
+            id: model.id,
+            user_id: UserId::from(model.user_id),
+            token_hash: model.token_hash,
+            expires_at: model.expires_at,
+            consumed_at: model.consumed_at,
+        }
+    }
+}
+
+impl From<EmailVerificationToken> for EmailVerificationTokenModel {
+    fn from(token: EmailVerificationToken) -> Self {
+        EmailVerificationTokenModel {
+            id: token.id,
+            user_id: token.user_id.into(),
+            token_hash: token.token_hash,
+            expires_at: token.expires_at,
+            consumed_at: token.consumed_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn email_verification_token_model_round_trip() {
+        let (token, _) =
+            EmailVerificationToken::issue(UserId::new(), Utc::now(), chrono::Duration::hours(1));
+
+        let model = EmailVerificationTokenModel::from(token.clone());
+        assert_eq!(model.user_id, Uuid::from(token.user_id.clone()));
+        assert_eq!(EmailVerificationToken::from(model), token);
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use domain::{
+    entity::idempotency::{IdempotencyRecord, IdempotentResponse},
+    redact::Redacted,
+};
+
+#[derive(sqlx::FromRow)]
+pub struct IdempotencyRecordModel {
+    pub request_hash: String,
+    pub response_status: Option<i16>,
+    /// `[name, value]` pairs, in the order the response sent them.
+    pub response_headers: Option<serde_json::Value>,
+    pub response_body: Option<Vec<u8>>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl fmt::Debug for IdempotencyRecordModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdempotencyRecordModel")
+            .field("request_hash", &self.request_hash)
+            .field("response_status", &self.response_status)
+            .field("response_headers", &self.response_headers)
+            .field(
+                "response_body",
+                &self.response_body.as_ref().map(|_| Redacted),
+            )
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+impl TryFrom<IdempotencyRecordModel> for IdempotencyRecord {
+    type Error = anyhow::Error;
+
+    fn try_from(model: IdempotencyRecordModel) -> Result<Self, Self::Error> {
+        let response = match (
+            model.response_status,
+            model.response_headers,
+            model.response_body,
+        ) {
+            (Some(status), Some(headers), Some(body)) => Some(IdempotentResponse {
+                status: u16::try_from(status)?,
+                headers: serde_json::from_value(headers)?,
+                body,
+            }),
+            (None, None, None) => None,
+            _ => anyhow::bail!("idempotency record has a partial response"),
+        };
+
+        Ok(IdempotencyRecord {
+            request_hash: model.request_hash,
+            response,
+            expires_at: model.expires_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idempotency_record_model_to_idempotency_record_conversion_works() {
+        let model = IdempotencyRecordModel {
+            request_hash: "a".repeat(64),
+            response_status: Some(201),
+            response_headers: Some(serde_json::json!([["content-type", "application/json"]])),
+            response_body: Some(b"{}".to_vec()),
+            expires_at: Utc::now(),
+        };
+
+        let record = IdempotencyRecord::try_from(model).unwrap();
+        assert_eq!(
+            record.response,
+            Some(IdempotentResponse {
+                status: 201,
+                headers: vec![("content-type".to_owned(), "application/json".to_owned())],
+                body: b"{}".to_vec(),
+            })
+        );
+    }
+
+    #[test]
+    fn partial_response_fails_conversion() {
+        let model = IdempotencyRecordModel {
+            request_hash: "a".repeat(64),
+            response_status: Some(201),
+            response_headers: None,
+            response_body: None,
+            expires_at: Utc::now(),
+        };
+
+        assert!(IdempotencyRecord::try_from(model).is_err());
+    }
+}
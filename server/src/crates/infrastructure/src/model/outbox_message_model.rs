@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use domain::entity::outbox_message::OutboxMessage;
+use uuid::Uuid;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct OutboxMessageModel {
+    pub id: Uuid,
+    pub topic: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+impl TryFrom<OutboxMessageModel> for OutboxMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(model: OutboxMessageModel) -> Result<Self, Self::Error> {
+        Ok(OutboxMessage {
+            id: model.id,
+            topic: model.topic,
+            payload: model.payload,
+            status: model.status.parse()?,
+            attempts: model.attempts,
+            next_attempt_at: model.next_attempt_at,
+            last_error: model.last_error,
+        })
+    }
+}
+
+impl From<OutboxMessage> for OutboxMessageModel {
+    fn from(message: OutboxMessage) -> Self {
+        OutboxMessageModel {
+            id: message.id,
+            topic: message.topic,
+            payload: message.payload,
+            status: message.status.as_str().to_owned(),
+            attempts: message.attempts,
+            next_attempt_at: message.next_attempt_at,
+            last_error: message.last_error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::{
+        entity::{outbox_message::OutboxStatus, value_object::user_id::UserId},
+        event::domain_event::DomainEvent,
+    };
+
+    use super::*;
+
+    #[test]
+    fn outbox_message_model_round_trip() {
+        let message = OutboxMessage::from_event(&DomainEvent::user_created(UserId::new()));
+
+        let model = OutboxMessageModel::from(message.clone());
+        assert_eq!(model.status, "pending");
+        assert_eq!(OutboxMessage::try_from(model).unwrap(), message);
+    }
+
+    #[test]
+    fn unknown_status_fails_conversion() {
+        let mut model = OutboxMessageModel::from(OutboxMessage::from_event(
+            &DomainEvent::user_created(UserId::new()),
+        ));
+        model.status = "unknown".into();
+
+        assert!(OutboxMessage::try_from(model).is_err());
+        assert_eq!("dead".parse::<OutboxStatus>().unwrap(), OutboxStatus::Dead);
+    }
+}
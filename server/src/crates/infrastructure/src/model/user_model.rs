@@ -1,11 +1,33 @@
-use domain::entity::{user::User, value_object::user_id::UserId};
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use domain::{
+    entity::{user::User, value_object::user_id::UserId},
+    redact::Redacted,
+};
 use uuid::Uuid;
 
-#[derive(Debug, sqlx::FromRow)]
+#[derive(sqlx::FromRow)]
 pub struct UserModel {
     pub id: Uuid,
     pub name: String,
     pub email: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i64,
+}
+
+impl fmt::Debug for UserModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserModel")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("email", &Redacted)
+            .field("email_verified_at", &self.email_verified_at)
+            .field("updated_at", &self.updated_at)
+            .field("version", &self.version)
+            .finish()
+    }
 }
 
 impl TryFrom<UserModel> for User {
@@ -16,6 +38,9 @@ impl TryFrom<UserModel> for User {
             id: UserId::from(model.id),
             name: model.name,
             email: model.email,
+            email_verified_at: model.email_verified_at,
+            updated_at: model.updated_at,
+            version: model.version,
         })
     }
 }
@@ -26,6 +51,9 @@ impl From<User> for UserModel {
             id: user.id.into(),
             name: user.name,
             email: user.email,
+            email_verified_at: user.email_verified_at,
+            updated_at: user.updated_at,
+            version: user.version,
         }
     }
 }
@@ -41,6 +69,9 @@ mod tests {
             id: uuid,
             name: "Test User".to_string(),
             email: "test@example.com".to_string(),
+            email_verified_at: None,
+            updated_at: Utc::now(),
+            version: 1,
         };
 
         let user = User::try_from(model).unwrap();
@@ -56,6 +87,9 @@ mod tests {
             id: UserId::from(uuid),
             name: "Test User".to_string(),
             email: "test@example.com".to_string(),
+            email_verified_at: None,
+            updated_at: Utc::now(),
+            version: 1,
         };
 
         let model: UserModel = user.into();
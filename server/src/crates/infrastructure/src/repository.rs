@@ -1,2 +1,14 @@
+pub mod audit_log_repository_with_pg;
+pub mod email_verification_token_repository_with_pg;
+pub mod idempotency_store_with_pg;
+pub mod in_memory_audit_log_repository;
+pub mod in_memory_email_verification_token_repository;
+pub mod in_memory_idempotency_store;
+pub mod in_memory_rate_limit_store;
+pub mod in_memory_unit_of_work;
+pub mod in_memory_user_email_duplicate_validator;
+pub mod in_memory_user_repository;
+pub mod outbox_repository_with_pg;
+pub mod unit_of_work_with_pg;
 pub mod user_repository_with_pg;
-pub mod user_email_duplicate_validator_with_pg;
\ No newline at end of file
+pub mod user_email_duplicate_validator_with_pg;
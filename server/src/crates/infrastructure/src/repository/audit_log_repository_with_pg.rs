@@ -0,0 +1,266 @@
+use crate::model::audit_event_model::AuditEventModel;
+use domain::entity::audit_event::{AuditEvent, AuditEventFilter, AuditEventPage};
+use domain::interface::audit_log_repository_interface::AuditLogRepositoryInterface;
+
+#[derive(Debug, Clone)]
+pub struct AuditLogRepositoryWithPg {
+    db: sqlx::PgPool,
+}
+
+impl AuditLogRepositoryWithPg {
+    pub fn new(db: sqlx::PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditLogRepositoryInterface for AuditLogRepositoryWithPg {
+    #[tracing::instrument(
+        name = "AuditLogRepositoryWithPg::append",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "INSERT", action = %event.action)
+    )]
+    async fn append(&self, event: &AuditEvent) -> Result<(), anyhow::Error> {
+        let mut conn = self.db.acquire().await?;
+        insert_audit_event(&mut conn, event)
+            .await
+            .map_err(|_| anyhow::Error::msg("Failed to append audit event"))
+    }
+
+    #[tracing::instrument(
+        name = "AuditLogRepositoryWithPg::find",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn find(&self, filter: &AuditEventFilter) -> Result<AuditEventPage, anyhow::Error> {
+        let mut conn = self.db.acquire().await?;
+        select_audit_events(&mut conn, filter).await
+    }
+}
+
+/// Logs a failure and hands back the database error, for callers that report it in their own
+/// error type.
+pub(crate) async fn insert_audit_event(
+    conn: &mut sqlx::PgConnection,
+    event: &AuditEvent,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO audit_log (id, action, actor_id, subject_id, ip, user_agent, occurred_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        event.id,
+        event.action.as_str(),
+        event.actor_id.as_ref().map(|id| id.0),
+        event.subject_id.as_ref().map(|id| id.0),
+        event.ip,
+        event.user_agent,
+        event.occurred_at
+    )
+    .execute(conn)
+    .await
+    .inspect_err(|e| tracing::error!(error = %e, "failed to append audit event"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn select_audit_events(
+    conn: &mut sqlx::PgConnection,
+    filter: &AuditEventFilter,
+) -> Result<AuditEventPage, anyhow::Error> {
+    // One extra row tells whether another page follows.
+    let mut rows = sqlx::query_as!(
+        AuditEventModel,
+        r#"
+        SELECT seq, id, action, actor_id, subject_id, ip, user_agent, occurred_at
+        FROM audit_log
+        WHERE ($1::text IS NULL OR action = $1)
+          AND ($2::uuid IS NULL OR actor_id = $2)
+          AND ($3::uuid IS NULL OR subject_id = $3)
+          AND ($4::timestamptz IS NULL OR occurred_at >= $4)
+          AND ($5::timestamptz IS NULL OR occurred_at < $5)
+          AND ($6::bigint IS NULL OR seq < $6)
+        ORDER BY seq DESC
+        LIMIT $7
+        "#,
+        filter.action.map(|action| action.as_str()),
+        filter.actor_id.as_ref().map(|id| id.0),
+        filter.subject_id.as_ref().map(|id| id.0),
+        filter.from,
+        filter.to,
+        filter.before,
+        filter.limit + 1
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "failed to fetch audit events");
+        anyhow::Error::msg("Failed to fetch audit events")
+    })?;
+
+    let next_cursor = if rows.len() as i64 > filter.limit {
+        rows.truncate(filter.limit as usize);
+        rows.last().map(|row| row.seq)
+    } else {
+        None
+    };
+    let events = rows
+        .into_iter()
+        .map(AuditEvent::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to convert AuditEventModel to AuditEvent");
+            anyhow::Error::msg("Data conversion failed")
+        })?;
+
+    Ok(AuditEventPage {
+        events,
+        next_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::test_database::TestDatabase;
+    use chrono::Utc;
+    use domain::{
+        entity::{
+            audit_event::{AuditAction, AuditEvent, AuditEventFilter},
+            request_context::RequestContext,
+            value_object::user_id::UserId,
+        },
+        interface::audit_log_repository_interface::AuditLogRepositoryInterface,
+    };
+
+    use super::AuditLogRepositoryWithPg;
+
+    /// Events about a subject of their own, so parallel tests can filter down to exactly theirs.
+    async fn append_events(
+        repository: &AuditLogRepositoryWithPg,
+        subject_id: &UserId,
+        actions: &[AuditAction],
+    ) -> Vec<AuditEvent> {
+        let context = RequestContext {
+            actor_id: None,
+            ip: Some("203.0.113.7".to_owned()),
+            user_agent: Some("curl/8.0".to_owned()),
+        };
+        let mut events = Vec::new();
+        for action in actions {
+            let event = AuditEvent::new(*action, Some(subject_id.clone()), &context, Utc::now());
+            repository
+                .append(&event)
+                .await
+                .expect("should append audit event");
+            events.push(event);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn test_find_filters_by_subject_and_action_newest_first() {
+        let pool = TestDatabase::create().await.expect("database should connect");
+        let repository = AuditLogRepositoryWithPg::new(pool.clone());
+        let subject_id = UserId::new();
+        let events = append_events(
+            &repository,
+            &subject_id,
+            &[
+                AuditAction::UserCreated,
+                AuditAction::UserUpdated,
+                AuditAction::UserUpdated,
+            ],
+        )
+        .await;
+
+        let page = repository
+            .find(&AuditEventFilter {
+                subject_id: Some(subject_id.clone()),
+                limit: 10,
+                ..AuditEventFilter::default()
+            })
+            .await
+            .expect("should find audit events");
+        assert_eq!(
+            page.events.iter().map(|e| e.id).collect::<Vec<_>>(),
+            events.iter().rev().map(|e| e.id).collect::<Vec<_>>()
+        );
+        assert_eq!(page.next_cursor, None);
+
+        let page = repository
+            .find(&AuditEventFilter {
+                action: Some(AuditAction::UserCreated),
+                subject_id: Some(subject_id),
+                limit: 10,
+                ..AuditEventFilter::default()
+            })
+            .await
+            .expect("should find audit events");
+        assert_eq!(
+            page.events.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![events[0].id]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_paginates_with_cursor() {
+        let pool = TestDatabase::create().await.expect("database should connect");
+        let repository = AuditLogRepositoryWithPg::new(pool.clone());
+        let subject_id = UserId::new();
+        let events = append_events(
+            &repository,
+            &subject_id,
+            &[
+                AuditAction::UserCreated,
+                AuditAction::UserUpdated,
+                AuditAction::UserLoggedIn,
+            ],
+        )
+        .await;
+        let filter = AuditEventFilter {
+            subject_id: Some(subject_id),
+            limit: 2,
+            ..AuditEventFilter::default()
+        };
+
+        let first = repository.find(&filter).await.expect("should find");
+        assert_eq!(
+            first.events.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![events[2].id, events[1].id]
+        );
+        assert!(first.next_cursor.is_some());
+
+        let second = repository
+            .find(&AuditEventFilter {
+                before: first.next_cursor,
+                ..filter
+            })
+            .await
+            .expect("should find");
+        assert_eq!(
+            second.events.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![events[0].id]
+        );
+        assert_eq!(second.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_rejects_updates_and_deletes() {
+        let pool = TestDatabase::create().await.expect("database should connect");
+        let repository = AuditLogRepositoryWithPg::new(pool.clone());
+        let events = append_events(&repository, &UserId::new(), &[AuditAction::UserDeleted]).await;
+
+        let updated = sqlx::query!(
+            r#"UPDATE audit_log SET action = 'user.created' WHERE id = $1"#,
+            events[0].id
+        )
+        .execute(&*pool)
+        .await;
+        assert!(updated.is_err());
+
+        let deleted = sqlx::query!(r#"DELETE FROM audit_log WHERE id = $1"#, events[0].id)
+            .execute(&*pool)
+            .await;
+        assert!(deleted.is_err());
+    }
+}
@@ -0,0 +1,273 @@
+use chrono::{DateTime, Utc};
+use domain::{
+    entity::{
+        audit_event::AuditEvent, email_verification_token::EmailVerificationToken, user::User,
+    },
+    error::email_verification_error::EmailVerificationError,
+    interface::email_verification_token_repository_interface::EmailVerificationTokenRepositoryInterface,
+};
+
+use crate::{
+    model::{email_verification_token_model::EmailVerificationTokenModel, user_model::UserModel},
+    repository::audit_log_repository_with_pg::insert_audit_event,
+};
+
+#[derive(Debug, Clone)]
+pub struct EmailVerificationTokenRepositoryWithPg {
+    db: sqlx::PgPool,
+}
+
+impl EmailVerificationTokenRepositoryWithPg {
+    pub fn new(db: sqlx::PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailVerificationTokenRepositoryInterface for EmailVerificationTokenRepositoryWithPg {
+    #[tracing::instrument(
+        name = "EmailVerificationTokenRepositoryWithPg::create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "INSERT", user_id = %token.user_id)
+    )]
+    async fn create(&self, token: &EmailVerificationToken) -> Result<(), anyhow::Error> {
+        let token_model = EmailVerificationTokenModel::from(token.clone());
+        let mut tx = self.db.begin().await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM email_verification_token
+            WHERE user_id = $1 AND consumed_at IS NULL
+            "#,
+            token_model.user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO email_verification_token (id, user_id, token_hash, expires_at, consumed_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            token_model.id,
+            token_model.user_id,
+            token_model.token_hash,
+            token_model.expires_at,
+            token_model.consumed_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to insert email verification token");
+            anyhow::Error::msg("Failed to insert email verification token")
+        })?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "EmailVerificationTokenRepositoryWithPg::find_by_hash",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<EmailVerificationToken>, anyhow::Error> {
+        let row = sqlx::query_as!(
+            EmailVerificationTokenModel,
+            r#"
+            SELECT id, user_id, token_hash, expires_at, consumed_at
+            FROM email_verification_token
+            WHERE token_hash = $1
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to fetch email verification token");
+            anyhow::Error::msg("Failed to fetch email verification token")
+        })?;
+
+        Ok(row.map(EmailVerificationToken::from))
+    }
+
+    #[tracing::instrument(
+        name = "EmailVerificationTokenRepositoryWithPg::consume",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "UPDATE", user_id = %token.user_id)
+    )]
+    async fn consume(
+        &self,
+        token: &EmailVerificationToken,
+        now: DateTime<Utc>,
+        audit_event: &AuditEvent,
+    ) -> Result<User, EmailVerificationError> {
+        let mut tx = self.db.begin().await?;
+
+        // The `consumed_at IS NULL` guard makes a concurrent second use of the token a no-op.
+        let consumed = sqlx::query!(
+            r#"
+            UPDATE email_verification_token SET consumed_at = $2
+            WHERE id = $1 AND consumed_at IS NULL
+            "#,
+            token.id,
+            now
+        )
+        .execute(&mut *tx)
+        .await?;
+        if consumed.rows_affected() == 0 {
+            return Err(EmailVerificationError::InvalidToken);
+        }
+
+        let row = sqlx::query_as!(
+            UserModel,
+            r#"
+            UPDATE "user"
+            SET email_verified_at = COALESCE(email_verified_at, $2), updated_at = now(),
+                version = version + 1
+            WHERE id = $1
+            RETURNING id, name, email, email_verified_at, updated_at, version
+            "#,
+            token.user_id.0,
+            now
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        insert_audit_event(&mut tx, audit_event).await?;
+
+        tx.commit().await?;
+        tracing::info!(user_id = %token.user_id, "email verified");
+
+        User::try_from(row)
+            .map_err(|e| EmailVerificationError::Unexpected(sqlx::Error::Decode(e.into())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::test_database::TestDatabase;
+    use chrono::{Duration, Utc};
+    use domain::{
+        entity::{
+            audit_event::{AuditAction, AuditEvent},
+            email_verification_token::EmailVerificationToken,
+            request_context::RequestContext,
+            user::User,
+        },
+        error::email_verification_error::EmailVerificationError,
+        interface::{
+            email_verification_token_repository_interface::EmailVerificationTokenRepositoryInterface,
+            user_repository_interface::UserRepositoryInterface,
+        },
+    };
+
+    use super::EmailVerificationTokenRepositoryWithPg;
+    use crate::repository::user_repository_with_pg::UserRepositoryWithPg;
+
+    async fn create_user(pool: &sqlx::PgPool) -> User {
+        let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
+        UserRepositoryWithPg::new(pool.clone())
+            .create(&User::new("Test User".into(), email))
+            .await
+            .expect("should create user")
+    }
+
+    #[tokio::test]
+    async fn test_create_and_find_token_successfully() {
+        let pool = TestDatabase::create().await.expect("database should connect");
+        let user = create_user(&pool).await;
+        let repository = EmailVerificationTokenRepositoryWithPg::new(pool.clone());
+        let (token, raw_token) =
+            EmailVerificationToken::issue(user.id.clone(), Utc::now(), Duration::hours(1));
+
+        repository
+            .create(&token)
+            .await
+            .expect("should create token");
+        let found = repository
+            .find_by_hash(&EmailVerificationToken::hash(&raw_token))
+            .await
+            .expect("should find token")
+            .expect("token should exist");
+
+        assert_eq!(found.id, token.id);
+        assert_eq!(found.user_id, user.id);
+        assert!(found.consumed_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_token_replaces_pending_token() {
+        let pool = TestDatabase::create().await.expect("database should connect");
+        let user = create_user(&pool).await;
+        let repository = EmailVerificationTokenRepositoryWithPg::new(pool.clone());
+        let (first, _) =
+            EmailVerificationToken::issue(user.id.clone(), Utc::now(), Duration::hours(1));
+        let (second, _) =
+            EmailVerificationToken::issue(user.id.clone(), Utc::now(), Duration::hours(1));
+
+        repository
+            .create(&first)
+            .await
+            .expect("should create token");
+        repository
+            .create(&second)
+            .await
+            .expect("should create token");
+
+        assert!(
+            repository
+                .find_by_hash(&first.token_hash)
+                .await
+                .expect("should query token")
+                .is_none()
+        );
+        assert!(
+            repository
+                .find_by_hash(&second.token_hash)
+                .await
+                .expect("should query token")
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_consume_token_verifies_email_once() {
+        let pool = TestDatabase::create().await.expect("database should connect");
+        let user = create_user(&pool).await;
+        let repository = EmailVerificationTokenRepositoryWithPg::new(pool.clone());
+        let now = Utc::now();
+        let (token, _) = EmailVerificationToken::issue(user.id.clone(), now, Duration::hours(1));
+        repository
+            .create(&token)
+            .await
+            .expect("should create token");
+
+        let audit_event = AuditEvent::new(
+            AuditAction::UserUpdated,
+            Some(user.id.clone()),
+            &RequestContext::default(),
+            now,
+        );
+
+        let verified = repository
+            .consume(&token, now, &audit_event)
+            .await
+            .expect("should consume token");
+        assert_eq!(verified.id, user.id);
+        assert!(verified.is_email_verified());
+
+        let result = repository.consume(&token, now, &audit_event).await;
+        assert!(matches!(result, Err(EmailVerificationError::InvalidToken)));
+        let audit_events = sqlx::query_scalar!(
+            r#"SELECT count(*) AS "count!" FROM audit_log WHERE id = $1"#,
+            audit_event.id
+        )
+        .fetch_one(&*pool)
+        .await
+        .expect("should count audit events");
+        assert_eq!(audit_events, 1);
+    }
+}
@@ -0,0 +1,306 @@
+use chrono::{DateTime, Utc};
+use domain::{
+    entity::idempotency::{
+        IdempotencyClaim, IdempotencyKey, IdempotencyRecord, IdempotentResponse,
+    },
+    interface::idempotency_store_interface::IdempotencyStoreInterface,
+};
+
+use crate::model::idempotency_record_model::IdempotencyRecordModel;
+
+/// Shares idempotency keys between every server instance using the same database.
+#[derive(Debug, Clone)]
+pub struct IdempotencyStoreWithPg {
+    db: sqlx::PgPool,
+}
+
+impl IdempotencyStoreWithPg {
+    pub fn new(db: sqlx::PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyStoreInterface for IdempotencyStoreWithPg {
+    #[tracing::instrument(
+        name = "IdempotencyStoreWithPg::claim",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "INSERT")
+    )]
+    async fn claim(
+        &self,
+        key: &IdempotencyKey,
+        request_hash: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<IdempotencyClaim, anyhow::Error> {
+        // The row read back can vanish if its request fails and releases it in between, in which
+        // case the key is free to claim again.
+        loop {
+            let claimed = sqlx::query_scalar!(
+                r#"
+                INSERT INTO idempotency_key (key, route, principal, request_hash, expires_at)
+                VALUES ($1, $2, $3, $4, $6)
+                ON CONFLICT (key, route, principal) DO UPDATE
+                SET request_hash = EXCLUDED.request_hash, response_status = NULL,
+                    response_headers = NULL, response_body = NULL, created_at = now(),
+                    expires_at = EXCLUDED.expires_at
+                WHERE idempotency_key.expires_at <= $5
+                RETURNING true AS "claimed!"
+                "#,
+                key.key,
+                key.route,
+                key.principal,
+                request_hash,
+                now,
+                expires_at
+            )
+            .fetch_optional(&self.db)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "failed to claim idempotency key");
+                anyhow::Error::msg("Failed to claim idempotency key")
+            })?;
+            if claimed.is_some() {
+                return Ok(IdempotencyClaim::Claimed);
+            }
+
+            let row = sqlx::query_as!(
+                IdempotencyRecordModel,
+                r#"
+                SELECT request_hash, response_status, response_headers, response_body, expires_at
+                FROM idempotency_key
+                WHERE key = $1 AND route = $2 AND principal = $3
+                "#,
+                key.key,
+                key.route,
+                key.principal
+            )
+            .fetch_optional(&self.db)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "failed to fetch idempotency key");
+                anyhow::Error::msg("Failed to fetch idempotency key")
+            })?;
+            if let Some(row) = row {
+                let record = IdempotencyRecord::try_from(row).map_err(|e| {
+                    tracing::error!(
+                        error = %e,
+                        "failed to convert IdempotencyRecordModel to IdempotencyRecord"
+                    );
+                    anyhow::Error::msg("Data conversion failed")
+                })?;
+                return Ok(IdempotencyClaim::Existing(record));
+            }
+        }
+    }
+
+    #[tracing::instrument(
+        name = "IdempotencyStoreWithPg::complete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "UPDATE")
+    )]
+    async fn complete(
+        &self,
+        key: &IdempotencyKey,
+        response: &IdempotentResponse,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE idempotency_key
+            SET response_status = $4, response_headers = $5, response_body = $6, expires_at = $7
+            WHERE key = $1 AND route = $2 AND principal = $3
+            "#,
+            key.key,
+            key.route,
+            key.principal,
+            i16::try_from(response.status)?,
+            serde_json::to_value(&response.headers)?,
+            response.body,
+            expires_at
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to store idempotent response");
+            anyhow::Error::msg("Failed to store idempotent response")
+        })?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "IdempotencyStoreWithPg::release",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "DELETE")
+    )]
+    async fn release(&self, key: &IdempotencyKey) -> Result<(), anyhow::Error> {
+        sqlx::query!(
+            r#"
+            DELETE FROM idempotency_key
+            WHERE key = $1 AND route = $2 AND principal = $3 AND response_status IS NULL
+            "#,
+            key.key,
+            key.route,
+            key.principal
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to release idempotency key");
+            anyhow::Error::msg("Failed to release idempotency key")
+        })?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "IdempotencyStoreWithPg::purge_expired",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "DELETE")
+    )]
+    async fn purge_expired(&self, now: DateTime<Utc>) -> Result<u64, anyhow::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM idempotency_key
+            WHERE expires_at <= $1
+            "#,
+            now
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to purge expired idempotency keys");
+            anyhow::Error::msg("Failed to purge expired idempotency keys")
+        })?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::test_database::TestDatabase;
+    use chrono::{Duration, Utc};
+    use domain::{
+        entity::idempotency::{IdempotencyClaim, IdempotencyKey, IdempotentResponse},
+        interface::idempotency_store_interface::IdempotencyStoreInterface,
+    };
+
+    use super::IdempotencyStoreWithPg;
+
+    fn key() -> IdempotencyKey {
+        IdempotencyKey {
+            key: uuid::Uuid::new_v4().to_string(),
+            route: "POST /v1/users".to_owned(),
+            principal: "anonymous".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_claim_returns_the_stored_response_once_completed() {
+        let pool = TestDatabase::create()
+            .await
+            .expect("database should connect");
+        let store = IdempotencyStoreWithPg::new(pool.clone());
+        let key = key();
+        let now = Utc::now();
+        let response = IdempotentResponse {
+            status: 201,
+            headers: vec![("content-type".to_owned(), "application/json".to_owned())],
+            body: br#"{"id":"1"}"#.to_vec(),
+        };
+
+        let first = store
+            .claim(&key, "hash", now, now + Duration::seconds(60))
+            .await
+            .expect("should claim");
+        let in_flight = store
+            .claim(&key, "hash", now, now + Duration::seconds(60))
+            .await
+            .expect("should claim");
+        store
+            .complete(&key, &response, now + Duration::hours(24))
+            .await
+            .expect("should complete");
+        let completed = store
+            .claim(&key, "other", now, now + Duration::seconds(60))
+            .await
+            .expect("should claim");
+
+        assert_eq!(first, IdempotencyClaim::Claimed);
+        match (in_flight, completed) {
+            (IdempotencyClaim::Existing(in_flight), IdempotencyClaim::Existing(completed)) => {
+                assert_eq!(in_flight.response, None);
+                assert_eq!(completed.request_hash, "hash");
+                assert_eq!(completed.response, Some(response));
+            }
+            other => panic!("unexpected claims: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_released_or_expired_key_can_be_claimed_again() {
+        let pool = TestDatabase::create()
+            .await
+            .expect("database should connect");
+        let store = IdempotencyStoreWithPg::new(pool.clone());
+        let key = key();
+        let now = Utc::now();
+
+        store
+            .claim(&key, "hash", now, now + Duration::seconds(60))
+            .await
+            .expect("should claim");
+        store.release(&key).await.expect("should release");
+        let after_release = store
+            .claim(&key, "hash", now, now + Duration::seconds(60))
+            .await
+            .expect("should claim");
+        let after_expiry = store
+            .claim(
+                &key,
+                "other",
+                now + Duration::seconds(60),
+                now + Duration::seconds(120),
+            )
+            .await
+            .expect("should claim");
+
+        assert_eq!(
+            (after_release, after_expiry),
+            (IdempotencyClaim::Claimed, IdempotencyClaim::Claimed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired_deletes_only_expired_keys() {
+        let pool = TestDatabase::create()
+            .await
+            .expect("database should connect");
+        let store = IdempotencyStoreWithPg::new(pool.clone());
+        let (expired, live) = (key(), key());
+        let now = Utc::now();
+
+        store
+            .claim(&expired, "hash", now, now + Duration::seconds(60))
+            .await
+            .expect("should claim");
+        store
+            .claim(&live, "hash", now, now + Duration::seconds(120))
+            .await
+            .expect("should claim");
+        let purged = store
+            .purge_expired(now + Duration::seconds(60))
+            .await
+            .expect("should purge");
+        let remaining = sqlx::query_scalar!(r#"SELECT key FROM idempotency_key"#)
+            .fetch_all(&*pool)
+            .await
+            .expect("should select");
+
+        assert_eq!(purged, 1);
+        assert_eq!(remaining, vec![live.key]);
+    }
+}
@@ -0,0 +1,124 @@
+use std::sync::{Mutex, PoisonError};
+
+use domain::{
+    entity::audit_event::{AuditEvent, AuditEventFilter, AuditEventPage},
+    interface::audit_log_repository_interface::AuditLogRepositoryInterface,
+};
+
+/// Keeps the audit log in process memory; an entry's position plus one is its cursor, like the
+/// `seq` column in Postgres.
+#[derive(Debug, Default)]
+pub struct InMemoryAuditLogRepository {
+    events: Mutex<Vec<AuditEvent>>,
+}
+
+impl InMemoryAuditLogRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `event` without going through the fallible trait method.
+    pub(crate) fn record(&self, event: AuditEvent) {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(event);
+    }
+
+    /// Every entry, oldest first.
+    pub(crate) fn events(&self) -> Vec<AuditEvent> {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditLogRepositoryInterface for InMemoryAuditLogRepository {
+    async fn append(&self, event: &AuditEvent) -> Result<(), anyhow::Error> {
+        self.record(event.clone());
+
+        Ok(())
+    }
+
+    async fn find(&self, filter: &AuditEventFilter) -> Result<AuditEventPage, anyhow::Error> {
+        let events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut matching = events
+            .iter()
+            .enumerate()
+            .map(|(index, event)| (index as i64 + 1, event))
+            .rev()
+            .filter(|(seq, event)| {
+                filter.before.is_none_or(|before| *seq < before)
+                    && filter.action.is_none_or(|action| event.action == action)
+                    && filter
+                        .actor_id
+                        .as_ref()
+                        .is_none_or(|actor_id| event.actor_id.as_ref() == Some(actor_id))
+                    && filter
+                        .subject_id
+                        .as_ref()
+                        .is_none_or(|subject_id| event.subject_id.as_ref() == Some(subject_id))
+                    && filter.from.is_none_or(|from| event.occurred_at >= from)
+                    && filter.to.is_none_or(|to| event.occurred_at < to)
+            })
+            .take(filter.limit as usize + 1)
+            .collect::<Vec<_>>();
+
+        let next_cursor = if matching.len() as i64 > filter.limit {
+            matching.truncate(filter.limit as usize);
+            matching.last().map(|(seq, _)| *seq)
+        } else {
+            None
+        };
+
+        Ok(AuditEventPage {
+            events: matching
+                .into_iter()
+                .map(|(_, event)| event.clone())
+                .collect(),
+            next_cursor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use domain::entity::{audit_event::AuditAction, request_context::RequestContext};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_find_paginates_newest_first() {
+        let repository = InMemoryAuditLogRepository::new();
+        let mut events = Vec::new();
+        for action in [
+            AuditAction::UserCreated,
+            AuditAction::UserUpdated,
+            AuditAction::UserLoggedIn,
+        ] {
+            let event = AuditEvent::new(action, None, &RequestContext::default(), Utc::now());
+            repository.append(&event).await.unwrap();
+            events.push(event);
+        }
+        let filter = AuditEventFilter {
+            limit: 2,
+            ..AuditEventFilter::default()
+        };
+
+        let first = repository.find(&filter).await.unwrap();
+        assert_eq!(first.events, vec![events[2].clone(), events[1].clone()]);
+
+        let second = repository
+            .find(&AuditEventFilter {
+                before: first.next_cursor,
+                ..filter
+            })
+            .await
+            .unwrap();
+        assert_eq!(second.events, vec![events[0].clone()]);
+        assert_eq!(second.next_cursor, None);
+    }
+}
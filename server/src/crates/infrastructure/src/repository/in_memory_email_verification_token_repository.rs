@@ -0,0 +1,140 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use chrono::{DateTime, Utc};
+use domain::{
+    entity::{
+        audit_event::AuditEvent, email_verification_token::EmailVerificationToken, user::User,
+    },
+    error::email_verification_error::EmailVerificationError,
+    interface::email_verification_token_repository_interface::EmailVerificationTokenRepositoryInterface,
+};
+
+use crate::repository::{
+    in_memory_audit_log_repository::InMemoryAuditLogRepository,
+    in_memory_user_repository::InMemoryUserRepository,
+};
+
+/// Keeps verification tokens in process memory, keyed by hash, and verifies users of an
+/// [`InMemoryUserRepository`].
+#[derive(Debug)]
+pub struct InMemoryEmailVerificationTokenRepository {
+    user_repository: InMemoryUserRepository,
+    audit_log_repository: Arc<InMemoryAuditLogRepository>,
+    tokens: Mutex<HashMap<String, EmailVerificationToken>>,
+}
+
+impl InMemoryEmailVerificationTokenRepository {
+    pub fn new(
+        user_repository: InMemoryUserRepository,
+        audit_log_repository: Arc<InMemoryAuditLogRepository>,
+    ) -> Self {
+        InMemoryEmailVerificationTokenRepository {
+            user_repository,
+            audit_log_repository,
+            tokens: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailVerificationTokenRepositoryInterface for InMemoryEmailVerificationTokenRepository {
+    async fn create(&self, token: &EmailVerificationToken) -> Result<(), anyhow::Error> {
+        let mut tokens = self.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+        tokens.retain(|_, t| t.user_id != token.user_id || t.consumed_at.is_some());
+        tokens.insert(token.token_hash.clone(), token.clone());
+
+        Ok(())
+    }
+
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<EmailVerificationToken>, anyhow::Error> {
+        let tokens = self.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+
+        Ok(tokens.get(token_hash).cloned())
+    }
+
+    async fn consume(
+        &self,
+        token: &EmailVerificationToken,
+        now: DateTime<Utc>,
+        audit_event: &AuditEvent,
+    ) -> Result<User, EmailVerificationError> {
+        let mut tokens = self.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(stored) = tokens
+            .get_mut(&token.token_hash)
+            .filter(|stored| stored.consumed_at.is_none())
+        else {
+            return Err(EmailVerificationError::InvalidToken);
+        };
+        stored.consumed_at = Some(now);
+
+        let user = self
+            .user_repository
+            .mark_email_verified(&token.user_id, now)
+            .ok_or(EmailVerificationError::Unexpected(sqlx::Error::RowNotFound))?;
+        self.audit_log_repository.record(audit_event.clone());
+
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use domain::{
+        entity::{audit_event::AuditAction, request_context::RequestContext},
+        interface::user_repository_interface::UserRepositoryInterface,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_consume_verifies_user_once() {
+        let user_repository = InMemoryUserRepository::new();
+        let user = user_repository
+            .create(&User::new("Alice".into(), "alice@example.com".into()))
+            .await
+            .unwrap();
+        let audit_log_repository = Arc::new(InMemoryAuditLogRepository::new());
+        let repository = InMemoryEmailVerificationTokenRepository::new(
+            user_repository.clone(),
+            audit_log_repository.clone(),
+        );
+        let now = Utc::now();
+        let audit_event = AuditEvent::new(
+            AuditAction::UserUpdated,
+            Some(user.id.clone()),
+            &RequestContext::default(),
+            now,
+        );
+        let (token, raw) = EmailVerificationToken::issue(user.id.clone(), now, Duration::hours(1));
+        repository.create(&token).await.unwrap();
+
+        let found = repository
+            .find_by_hash(&EmailVerificationToken::hash(&raw))
+            .await
+            .unwrap()
+            .expect("token should be found");
+        let verified = repository.consume(&found, now, &audit_event).await.unwrap();
+        assert_eq!(verified.email_verified_at, Some(now));
+        assert_eq!(
+            user_repository
+                .find_by_id(&user.id)
+                .await
+                .unwrap()
+                .email_verified_at,
+            Some(now)
+        );
+        assert_eq!(audit_log_repository.events(), vec![audit_event.clone()]);
+
+        assert!(matches!(
+            repository.consume(&found, now, &audit_event).await,
+            Err(EmailVerificationError::InvalidToken)
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::{DateTime, Utc};
+use domain::{
+    entity::idempotency::{
+        IdempotencyClaim, IdempotencyKey, IdempotencyRecord, IdempotentResponse,
+    },
+    interface::idempotency_store_interface::IdempotencyStoreInterface,
+};
+
+/// Keeps idempotency keys in process memory, so a retry is only recognised by the same server
+/// instance.
+#[derive(Debug, Default)]
+pub struct InMemoryIdempotencyStore {
+    records: Mutex<HashMap<IdempotencyKey, IdempotencyRecord>>,
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyStoreInterface for InMemoryIdempotencyStore {
+    async fn claim(
+        &self,
+        key: &IdempotencyKey,
+        request_hash: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<IdempotencyClaim, anyhow::Error> {
+        let mut records = self
+            .records
+            .lock()
+            .map_err(|_| anyhow::Error::msg("Idempotency store lock poisoned"))?;
+        records.retain(|_, record| record.expires_at > now);
+
+        if let Some(record) = records.get(key) {
+            return Ok(IdempotencyClaim::Existing(record.clone()));
+        }
+        records.insert(
+            key.clone(),
+            IdempotencyRecord {
+                request_hash: request_hash.to_owned(),
+                response: None,
+                expires_at,
+            },
+        );
+        Ok(IdempotencyClaim::Claimed)
+    }
+
+    async fn complete(
+        &self,
+        key: &IdempotencyKey,
+        response: &IdempotentResponse,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), anyhow::Error> {
+        let mut records = self
+            .records
+            .lock()
+            .map_err(|_| anyhow::Error::msg("Idempotency store lock poisoned"))?;
+        if let Some(record) = records.get_mut(key) {
+            record.response = Some(response.clone());
+            record.expires_at = expires_at;
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &IdempotencyKey) -> Result<(), anyhow::Error> {
+        let mut records = self
+            .records
+            .lock()
+            .map_err(|_| anyhow::Error::msg("Idempotency store lock poisoned"))?;
+        if records
+            .get(key)
+            .is_some_and(|record| record.response.is_none())
+        {
+            records.remove(key);
+        }
+        Ok(())
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> Result<u64, anyhow::Error> {
+        let mut records = self
+            .records
+            .lock()
+            .map_err(|_| anyhow::Error::msg("Idempotency store lock poisoned"))?;
+        let before = records.len();
+        records.retain(|_, record| record.expires_at > now);
+        Ok((before - records.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn key() -> IdempotencyKey {
+        IdempotencyKey {
+            key: "retry-1".to_owned(),
+            route: "POST /v1/users".to_owned(),
+            principal: "anonymous".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_claim_returns_the_stored_response_once_completed() {
+        let store = InMemoryIdempotencyStore::new();
+        let now = Utc::now();
+        let response = IdempotentResponse {
+            status: 201,
+            headers: Vec::new(),
+            body: b"{}".to_vec(),
+        };
+
+        let first = store
+            .claim(&key(), "hash", now, now + Duration::seconds(60))
+            .await
+            .unwrap();
+        store
+            .complete(&key(), &response, now + Duration::hours(24))
+            .await
+            .unwrap();
+        store.release(&key()).await.unwrap();
+        let retry = store
+            .claim(&key(), "hash", now, now + Duration::seconds(60))
+            .await
+            .unwrap();
+
+        assert_eq!(first, IdempotencyClaim::Claimed);
+        assert_eq!(
+            retry,
+            IdempotencyClaim::Existing(IdempotencyRecord {
+                request_hash: "hash".to_owned(),
+                response: Some(response),
+                expires_at: now + Duration::hours(24),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_claim_can_be_claimed_again() {
+        let store = InMemoryIdempotencyStore::new();
+        let now = Utc::now();
+
+        store
+            .claim(&key(), "hash", now, now + Duration::seconds(60))
+            .await
+            .unwrap();
+        let in_flight = store
+            .claim(&key(), "hash", now, now + Duration::seconds(60))
+            .await
+            .unwrap();
+        let expired = store
+            .claim(
+                &key(),
+                "hash",
+                now + Duration::seconds(60),
+                now + Duration::seconds(120),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            in_flight,
+            IdempotencyClaim::Existing(IdempotencyRecord { response: None, .. })
+        ));
+        assert_eq!(expired, IdempotencyClaim::Claimed);
+    }
+}
@@ -0,0 +1,226 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use domain::{
+    entity::rate_limit::{RateLimitDecision, RateLimitPolicy},
+    interface::rate_limit_store_interface::RateLimitStoreInterface,
+};
+use tokio::time::Instant;
+
+/// How often buckets that have refilled completely are dropped; they hold no state worth keeping.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How many buckets are kept at most, full or not, so a flood of distinct keys cannot exhaust
+/// memory.
+const MAX_BUCKETS: usize = 100_000;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    policy: RateLimitPolicy,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.policy.tokens_per_second())
+            .min(f64::from(self.policy.capacity));
+        self.updated_at = now;
+    }
+
+    fn is_full(&self, now: Instant) -> bool {
+        now.duration_since(self.updated_at) >= self.policy.period
+            || self.tokens >= f64::from(self.policy.capacity)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Buckets {
+    by_key: HashMap<String, Bucket>,
+    swept_at: Option<Instant>,
+}
+
+impl Buckets {
+    /// Drops every full bucket, at most once per [`SWEEP_INTERVAL`].
+    fn sweep(&mut self, now: Instant) {
+        if self
+            .swept_at
+            .is_some_and(|swept_at| now.duration_since(swept_at) < SWEEP_INTERVAL)
+        {
+            return;
+        }
+        self.by_key.retain(|_, bucket| !bucket.is_full(now));
+        self.swept_at = Some(now);
+    }
+
+    /// Makes room for a new bucket by dropping the least recently used tenth of them, so the
+    /// cost of sorting is spread over the next inserts.
+    fn evict_least_recently_used(&mut self, max_buckets: usize) {
+        if self.by_key.len() < max_buckets {
+            return;
+        }
+        let mut by_use: Vec<(Instant, String)> = self
+            .by_key
+            .iter()
+            .map(|(key, bucket)| (bucket.updated_at, key.clone()))
+            .collect();
+        by_use.sort_unstable();
+        let evicted = self.by_key.len() - max_buckets + max_buckets.div_ceil(10);
+        for (_, key) in by_use.into_iter().take(evicted) {
+            self.by_key.remove(&key);
+        }
+    }
+}
+
+/// Keeps token buckets in process memory, so limits are per server instance.
+#[derive(Debug)]
+pub struct InMemoryRateLimitStore {
+    buckets: Mutex<Buckets>,
+    max_buckets: usize,
+}
+
+impl InMemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self::with_max_buckets(MAX_BUCKETS)
+    }
+
+    /// Keeps at most `max_buckets` buckets, evicting the least recently used ones beyond that.
+    pub fn with_max_buckets(max_buckets: usize) -> Self {
+        InMemoryRateLimitStore {
+            buckets: Mutex::default(),
+            max_buckets: max_buckets.max(1),
+        }
+    }
+}
+
+impl Default for InMemoryRateLimitStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimitStoreInterface for InMemoryRateLimitStore {
+    async fn acquire(
+        &self,
+        key: &str,
+        policy: &RateLimitPolicy,
+    ) -> Result<RateLimitDecision, anyhow::Error> {
+        let now = Instant::now();
+        let mut buckets = self
+            .buckets
+            .lock()
+            .map_err(|_| anyhow::Error::msg("Rate limit store lock poisoned"))?;
+
+        buckets.sweep(now);
+        if !buckets.by_key.contains_key(key) {
+            buckets.evict_least_recently_used(self.max_buckets);
+        }
+
+        let bucket = buckets.by_key.entry(key.to_owned()).or_insert(Bucket {
+            tokens: f64::from(policy.capacity),
+            policy: *policy,
+            updated_at: now,
+        });
+        // A key is always acquired with the same policy unless the configuration changed.
+        bucket.policy = *policy;
+        bucket.refill(now);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(RateLimitDecision::Allowed {
+                remaining: bucket.tokens as u32,
+            })
+        } else {
+            Ok(RateLimitDecision::Limited {
+                retry_after: Duration::from_secs_f64(
+                    (1.0 - bucket.tokens) / policy.tokens_per_second(),
+                ),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire_limits_after_capacity_is_used() {
+        let store = InMemoryRateLimitStore::new();
+        let policy = RateLimitPolicy::new(2, Duration::from_secs(60));
+
+        assert_eq!(
+            store.acquire("ip:1", &policy).await.unwrap(),
+            RateLimitDecision::Allowed { remaining: 1 }
+        );
+        assert_eq!(
+            store.acquire("ip:1", &policy).await.unwrap(),
+            RateLimitDecision::Allowed { remaining: 0 }
+        );
+        match store.acquire("ip:1", &policy).await.unwrap() {
+            RateLimitDecision::Limited { retry_after } => {
+                assert_eq!(retry_after.as_secs_f64().round(), 30.0);
+            }
+            other => panic!("unexpected decision: {:?}", other),
+        }
+
+        assert_eq!(
+            store.acquire("ip:2", &policy).await.unwrap(),
+            RateLimitDecision::Allowed { remaining: 1 }
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire_refills_over_time() {
+        let store = InMemoryRateLimitStore::new();
+        let policy = RateLimitPolicy::new(1, Duration::from_secs(10));
+
+        store.acquire("email:a", &policy).await.unwrap();
+        assert!(matches!(
+            store.acquire("email:a", &policy).await.unwrap(),
+            RateLimitDecision::Limited { .. }
+        ));
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+
+        assert_eq!(
+            store.acquire("email:a", &policy).await.unwrap(),
+            RateLimitDecision::Allowed { remaining: 0 }
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire_keeps_buckets_of_other_policies_until_they_are_full() {
+        let store = InMemoryRateLimitStore::new();
+        let slow = RateLimitPolicy::new(1, Duration::from_secs(3600));
+        let fast = RateLimitPolicy::new(1, Duration::from_secs(1));
+
+        store.acquire("email:a", &slow).await.unwrap();
+        tokio::time::advance(SWEEP_INTERVAL).await;
+        // Sweeps, which must refill the slow bucket at its own rate rather than the fast one's.
+        store.acquire("ip:1", &fast).await.unwrap();
+
+        assert!(matches!(
+            store.acquire("email:a", &slow).await.unwrap(),
+            RateLimitDecision::Limited { .. }
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire_evicts_least_recently_used_buckets_beyond_the_cap() {
+        let store = InMemoryRateLimitStore::with_max_buckets(10);
+        let policy = RateLimitPolicy::new(1, Duration::from_secs(3600));
+
+        for ip in 0..10 {
+            store.acquire(&format!("ip:{}", ip), &policy).await.unwrap();
+            tokio::time::advance(Duration::from_millis(1)).await;
+        }
+        store.acquire("ip:10", &policy).await.unwrap();
+
+        let buckets = store.buckets.lock().unwrap();
+        assert_eq!(buckets.by_key.len(), 10);
+        assert!(!buckets.by_key.contains_key("ip:0"));
+        assert!(buckets.by_key.contains_key("ip:10"));
+    }
+}
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use domain::{
+    entity::user::User,
+    interface::{
+        audit_log_repository_interface::AuditLogRepositoryInterface,
+        unit_of_work_interface::{TransactionInterface, UnitOfWorkInterface},
+        user_email_duplicate_validator_interface::UserEmailDuplicateValidatorInterface,
+        user_repository_interface::UserRepositoryInterface,
+    },
+};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::repository::{
+    in_memory_audit_log_repository::InMemoryAuditLogRepository,
+    in_memory_user_email_duplicate_validator::InMemoryUserEmailDuplicateValidator,
+    in_memory_user_repository::InMemoryUserRepository,
+};
+
+/// Runs transactions against an [`InMemoryUserRepository`] one at a time: each works on a copy
+/// of the users, and commit applies what it created, updated and deleted along with the audit
+/// entries it appended.
+#[derive(Debug, Clone)]
+pub struct InMemoryUnitOfWork {
+    user_repository: InMemoryUserRepository,
+    audit_log_repository: Arc<InMemoryAuditLogRepository>,
+    lock: Arc<Mutex<()>>,
+}
+
+impl InMemoryUnitOfWork {
+    pub fn new(
+        user_repository: InMemoryUserRepository,
+        audit_log_repository: Arc<InMemoryAuditLogRepository>,
+    ) -> Self {
+        InMemoryUnitOfWork {
+            user_repository,
+            audit_log_repository,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UnitOfWorkInterface for InMemoryUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn TransactionInterface + Send + Sync>, anyhow::Error> {
+        let guard = self.lock.clone().lock_owned().await;
+        let staged = self.user_repository.snapshot();
+
+        Ok(Box::new(InMemoryTransaction {
+            _guard: guard,
+            target: self.user_repository.clone(),
+            original: staged.snapshot(),
+            user_email_duplicate_validator: Arc::new(InMemoryUserEmailDuplicateValidator::new(
+                staged.clone(),
+            )),
+            staged: Arc::new(staged),
+            audit_log_target: self.audit_log_repository.clone(),
+            staged_audit_log: Arc::new(InMemoryAuditLogRepository::new()),
+        }))
+    }
+}
+
+struct InMemoryTransaction {
+    _guard: OwnedMutexGuard<()>,
+    target: InMemoryUserRepository,
+    /// The users as of `begin`, to tell this transaction's writes apart.
+    original: InMemoryUserRepository,
+    staged: Arc<InMemoryUserRepository>,
+    user_email_duplicate_validator: Arc<InMemoryUserEmailDuplicateValidator>,
+    audit_log_target: Arc<InMemoryAuditLogRepository>,
+    /// Only the entries appended in this transaction, which is all its `find` sees.
+    staged_audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+#[async_trait::async_trait]
+impl TransactionInterface for InMemoryTransaction {
+    fn user_repository(&self) -> Arc<dyn UserRepositoryInterface + Send + Sync> {
+        self.staged.clone()
+    }
+
+    fn user_email_duplicate_validator(
+        &self,
+    ) -> Arc<dyn UserEmailDuplicateValidatorInterface + Send + Sync> {
+        self.user_email_duplicate_validator.clone()
+    }
+
+    fn audit_log_repository(&self) -> Arc<dyn AuditLogRepositoryInterface + Send + Sync> {
+        self.staged_audit_log.clone()
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), anyhow::Error> {
+        // Writes made outside any transaction may have landed since `begin`; only the users
+        // this transaction changed are copied back. Deletes go first so their emails are free.
+        let original = self.original.find_all().await?;
+        let staged = self.staged.find_all().await?;
+        // Writes name the version read at `begin`, so a user changed outside the transaction
+        // since then fails the commit with a conflict.
+        for user in &original {
+            if !staged.iter().any(|u| u.id == user.id) {
+                self.target.delete(user).await?;
+            }
+        }
+        for user in staged {
+            match original.iter().find(|u| u.id == user.id) {
+                None => {
+                    self.target.create(&user).await?;
+                }
+                Some(before) if *before != user => {
+                    self.target
+                        .update(&User {
+                            version: before.version,
+                            ..user
+                        })
+                        .await?;
+                }
+                Some(_) => {}
+            }
+        }
+        for event in self.staged_audit_log.events() {
+            self.audit_log_target.append(&event).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::entity::{
+        audit_event::{AuditAction, AuditEvent},
+        request_context::RequestContext,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_commit_publishes_staged_users_and_audit_events() {
+        let user_repository = InMemoryUserRepository::new();
+        let audit_log_repository = Arc::new(InMemoryAuditLogRepository::new());
+        let unit_of_work =
+            InMemoryUnitOfWork::new(user_repository.clone(), audit_log_repository.clone());
+        let user = User::new("Alice".into(), "alice@example.com".into());
+        let audit_event = AuditEvent::new(
+            AuditAction::UserCreated,
+            Some(user.id.clone()),
+            &RequestContext::default(),
+            chrono::Utc::now(),
+        );
+
+        let tx = unit_of_work.begin().await.unwrap();
+        tx.user_repository().create(&user).await.unwrap();
+        tx.audit_log_repository()
+            .append(&audit_event)
+            .await
+            .unwrap();
+        assert!(user_repository.find_all().await.unwrap().is_empty());
+        assert!(audit_log_repository.events().is_empty());
+        tx.commit().await.unwrap();
+
+        assert_eq!(user_repository.find_all().await.unwrap(), vec![user]);
+        assert_eq!(audit_log_repository.events(), vec![audit_event]);
+    }
+
+    #[tokio::test]
+    async fn test_rolled_back_and_dropped_writes_are_discarded() {
+        let user_repository = InMemoryUserRepository::new();
+        let unit_of_work = InMemoryUnitOfWork::new(
+            user_repository.clone(),
+            Arc::new(InMemoryAuditLogRepository::new()),
+        );
+
+        let tx = unit_of_work.begin().await.unwrap();
+        tx.user_repository()
+            .create(&User::new("Alice".into(), "alice@example.com".into()))
+            .await
+            .unwrap();
+        tx.rollback().await.unwrap();
+
+        {
+            let tx = unit_of_work.begin().await.unwrap();
+            tx.user_repository()
+                .create(&User::new("Bob".into(), "bob@example.com".into()))
+                .await
+                .unwrap();
+        }
+
+        assert!(user_repository.find_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_commit_applies_staged_updates_and_deletes() {
+        let user_repository = InMemoryUserRepository::new();
+        let unit_of_work = InMemoryUnitOfWork::new(
+            user_repository.clone(),
+            Arc::new(InMemoryAuditLogRepository::new()),
+        );
+        let mut alice = User::new("Alice".into(), "alice@example.com".into());
+        let bob = User::new("Bob".into(), "bob@example.com".into());
+        user_repository.create(&alice).await.unwrap();
+        user_repository.create(&bob).await.unwrap();
+
+        let tx = unit_of_work.begin().await.unwrap();
+        alice.update("Alice Renamed".into(), "alice@example.com".into());
+        tx.user_repository().update(&alice).await.unwrap();
+        tx.user_repository().delete(&bob).await.unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(
+            user_repository.find_all().await.unwrap(),
+            vec![User {
+                version: alice.version + 1,
+                ..alice
+            }]
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use domain::{
+    error::user_error::UserEmailDuplicateValidationError,
+    interface::user_email_duplicate_validator_interface::UserEmailDuplicateValidatorInterface,
+};
+
+use crate::repository::in_memory_user_repository::InMemoryUserRepository;
+
+/// Checks emails against the users of an [`InMemoryUserRepository`].
+#[derive(Debug, Clone)]
+pub struct InMemoryUserEmailDuplicateValidator {
+    user_repository: InMemoryUserRepository,
+}
+
+impl InMemoryUserEmailDuplicateValidator {
+    pub fn new(user_repository: InMemoryUserRepository) -> Self {
+        InMemoryUserEmailDuplicateValidator { user_repository }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserEmailDuplicateValidatorInterface for InMemoryUserEmailDuplicateValidator {
+    async fn validate_user_email_duplicate(
+        &self,
+        email: &str,
+    ) -> Result<(), UserEmailDuplicateValidationError> {
+        if self.user_repository.email_exists(email) {
+            return Err(UserEmailDuplicateValidationError::AlreadyExists);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::{
+        entity::user::User, interface::user_repository_interface::UserRepositoryInterface,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_validate_sees_users_created_through_the_repository() {
+        let user_repository = InMemoryUserRepository::new();
+        let validator = InMemoryUserEmailDuplicateValidator::new(user_repository.clone());
+        assert!(
+            validator
+                .validate_user_email_duplicate("alice@example.com")
+                .await
+                .is_ok()
+        );
+
+        user_repository
+            .create(&User::new("Alice".into(), "alice@example.com".into()))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            validator
+                .validate_user_email_duplicate("alice@example.com")
+                .await,
+            Err(UserEmailDuplicateValidationError::AlreadyExists)
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use chrono::{DateTime, Utc};
+use domain::{
+    entity::{user::User, value_object::user_id::UserId},
+    error::user_error::UserConcurrencyError,
+    interface::user_repository_interface::UserRepositoryInterface,
+};
+use futures_util::{StreamExt, stream::BoxStream};
+
+/// Keeps users in process memory, for tests and demos that run without Postgres. Clones share
+/// the same users.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryUserRepository {
+    users: Arc<Mutex<HashMap<UserId, User>>>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A repository starting with a copy of this one's users, used to stage a transaction.
+    pub(crate) fn snapshot(&self) -> Self {
+        InMemoryUserRepository {
+            users: Arc::new(Mutex::new(self.users().clone())),
+        }
+    }
+
+    pub(crate) fn email_exists(&self, email: &str) -> bool {
+        self.users().values().any(|user| user.email == email)
+    }
+
+    /// Sets the user's `email_verified_at` unless it is already set, like the Postgres token
+    /// repository does.
+    pub(crate) fn mark_email_verified(&self, user_id: &UserId, now: DateTime<Utc>) -> Option<User> {
+        let mut users = self.users();
+        let user = users.get_mut(user_id)?;
+        user.email_verified_at.get_or_insert(now);
+        user.touch();
+        user.version += 1;
+        Some(user.clone())
+    }
+
+    /// Every user, ordered by name like the Postgres repository.
+    fn sorted_users(&self) -> Vec<User> {
+        let mut users: Vec<User> = self.users().values().cloned().collect();
+        users.sort_by(|a, b| a.name.cmp(&b.name));
+        users
+    }
+
+    /// A panic while the lock was held cannot leave the map half-updated, so a poisoned lock is
+    /// still safe to use.
+    fn users(&self) -> MutexGuard<'_, HashMap<UserId, User>> {
+        self.users.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepositoryInterface for InMemoryUserRepository {
+    async fn create(&self, user: &User) -> Result<User, anyhow::Error> {
+        let mut users = self.users();
+        // Mirrors the primary key and the unique email constraint of the `user` table.
+        if users.contains_key(&user.id) || users.values().any(|u| u.email == user.email) {
+            return Err(anyhow::Error::msg("Failed to insert user"));
+        }
+        users.insert(user.id.clone(), user.clone());
+
+        Ok(user.clone())
+    }
+
+    async fn find_all(&self) -> Result<Vec<User>, anyhow::Error> {
+        Ok(self.sorted_users())
+    }
+
+    /// Streams a copy taken up front, so the lock is not held while the stream is consumed.
+    fn stream_all(&self) -> BoxStream<'static, Result<User, anyhow::Error>> {
+        futures_util::stream::iter(self.sorted_users().into_iter().map(Ok)).boxed()
+    }
+
+    async fn find_by_id(&self, user_id: &UserId) -> Result<User, anyhow::Error> {
+        // The same error as the Postgres repository, which handlers map to 404.
+        self.users()
+            .get(user_id)
+            .cloned()
+            .ok_or_else(|| anyhow::Error::new(sqlx::Error::RowNotFound))
+    }
+
+    async fn update(&self, user: &User) -> Result<User, anyhow::Error> {
+        let mut users = self.users();
+        ensure_version(&users, user)?;
+        if users
+            .values()
+            .any(|u| u.id != user.id && u.email == user.email)
+        {
+            return Err(anyhow::Error::msg("Failed to update user"));
+        }
+        let updated = User {
+            version: user.version + 1,
+            ..user.clone()
+        };
+        users.insert(user.id.clone(), updated.clone());
+
+        Ok(updated)
+    }
+
+    async fn delete(&self, user: &User) -> Result<(), anyhow::Error> {
+        let mut users = self.users();
+        ensure_version(&users, user)?;
+        users.remove(&user.id);
+
+        Ok(())
+    }
+}
+
+/// The errors the Postgres repository reports when a versioned write matches no row.
+fn ensure_version(users: &HashMap<UserId, User>, user: &User) -> Result<(), anyhow::Error> {
+    match users.get(&user.id) {
+        None => Err(anyhow::Error::new(sqlx::Error::RowNotFound)),
+        Some(stored) if stored.version != user.version => {
+            Err(anyhow::Error::new(UserConcurrencyError::Conflict))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    domain::user_repository_contract_tests!(async { InMemoryUserRepository::new() });
+
+    #[tokio::test]
+    async fn test_snapshot_does_not_share_users() {
+        let repository = InMemoryUserRepository::new();
+        let snapshot = repository.snapshot();
+
+        snapshot
+            .create(&User::new("Alice".into(), "alice@example.com".into()))
+            .await
+            .unwrap();
+
+        assert!(repository.find_all().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,259 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use domain::{
+    entity::outbox_message::OutboxMessage,
+    interface::outbox_repository_interface::OutboxRepositoryInterface,
+};
+use uuid::Uuid;
+
+use crate::model::outbox_message_model::OutboxMessageModel;
+
+#[derive(Debug, Clone)]
+pub struct OutboxRepositoryWithPg {
+    db: sqlx::PgPool,
+}
+
+impl OutboxRepositoryWithPg {
+    pub fn new(db: sqlx::PgPool) -> Self {
+        Self { db }
+    }
+}
+
+/// Records `message` as part of the caller's transaction, so it is only delivered if the
+/// surrounding write commits.
+pub(crate) async fn insert_outbox_message(
+    tx: &mut sqlx::PgConnection,
+    message: &OutboxMessage,
+) -> Result<(), sqlx::Error> {
+    let message_model = OutboxMessageModel::from(message.clone());
+    sqlx::query!(
+        r#"
+        INSERT INTO outbox (id, topic, payload, status, attempts, next_attempt_at, last_error)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        message_model.id,
+        message_model.topic,
+        message_model.payload,
+        message_model.status,
+        message_model.attempts,
+        message_model.next_attempt_at,
+        message_model.last_error
+    )
+    .execute(tx)
+    .await?;
+
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl OutboxRepositoryInterface for OutboxRepositoryWithPg {
+    #[tracing::instrument(
+        name = "OutboxRepositoryWithPg::claim_due",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "UPDATE")
+    )]
+    async fn claim_due(
+        &self,
+        topics: &[String],
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<OutboxMessage>, anyhow::Error> {
+        let lease_secs = lease.as_secs_f64();
+        // `SKIP LOCKED` lets several dispatchers claim disjoint batches without waiting.
+        let rows = sqlx::query_as!(
+            OutboxMessageModel,
+            r#"
+            UPDATE outbox
+            SET attempts = attempts + 1,
+                next_attempt_at = now() + make_interval(secs => $3)
+            WHERE id IN (
+                SELECT id FROM outbox
+                WHERE status = 'pending' AND next_attempt_at <= now() AND topic = ANY($1)
+                ORDER BY next_attempt_at ASC
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, topic, payload, status, attempts, next_attempt_at, last_error
+            "#,
+            topics,
+            limit,
+            lease_secs
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to claim outbox messages");
+            anyhow::Error::msg("Failed to claim outbox messages")
+        })?;
+
+        rows.into_iter().map(OutboxMessage::try_from).collect()
+    }
+
+    #[tracing::instrument(
+        name = "OutboxRepositoryWithPg::mark_delivered",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "UPDATE", outbox_id = %id)
+    )]
+    async fn mark_delivered(&self, id: &Uuid) -> Result<(), anyhow::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE outbox SET status = 'delivered', delivered_at = now(), last_error = NULL
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to mark outbox message as delivered");
+            anyhow::Error::msg("Failed to mark outbox message as delivered")
+        })?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "OutboxRepositoryWithPg::mark_failed",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "UPDATE", outbox_id = %id)
+    )]
+    async fn mark_failed(
+        &self,
+        id: &Uuid,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE outbox
+            SET status = CASE WHEN $3::timestamptz IS NULL THEN 'dead' ELSE 'pending' END,
+                next_attempt_at = COALESCE($3, next_attempt_at),
+                last_error = $2
+            WHERE id = $1
+            "#,
+            id,
+            error,
+            retry_at
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to mark outbox message as failed");
+            anyhow::Error::msg("Failed to mark outbox message as failed")
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::test_database::TestDatabase;
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use domain::{
+        entity::outbox_message::{OutboxMessage, OutboxStatus},
+        interface::outbox_repository_interface::OutboxRepositoryInterface,
+    };
+
+    use super::{OutboxRepositoryWithPg, insert_outbox_message};
+
+    /// Inserts a message on a topic of its own so parallel tests never claim each other's rows.
+    async fn insert_message(pool: &sqlx::PgPool) -> OutboxMessage {
+        let topic = format!("test.{}", uuid::Uuid::new_v4());
+        let message = OutboxMessage::new(&topic, serde_json::json!({ "key": "value" }));
+        let mut conn = pool.acquire().await.expect("should acquire connection");
+        insert_outbox_message(&mut conn, &message)
+            .await
+            .expect("should insert outbox message");
+        message
+    }
+
+    async fn status_of(pool: &sqlx::PgPool, message: &OutboxMessage) -> (String, i32) {
+        let row = sqlx::query!(
+            r#"SELECT status, attempts FROM outbox WHERE id = $1"#,
+            message.id
+        )
+        .fetch_one(pool)
+        .await
+        .expect("should fetch outbox message");
+        (row.status, row.attempts)
+    }
+
+    #[tokio::test]
+    async fn test_claim_due_leases_messages() {
+        let pool = TestDatabase::create().await.expect("database should connect");
+        let repository = OutboxRepositoryWithPg::new(pool.clone());
+        let message = insert_message(&pool).await;
+        let topics = vec![message.topic.clone()];
+
+        let claimed = repository
+            .claim_due(&topics, 10, Duration::from_secs(60))
+            .await
+            .expect("should claim messages");
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, message.id);
+        assert_eq!(claimed[0].attempts, 1);
+        assert_eq!(claimed[0].payload, message.payload);
+
+        let claimed_again = repository
+            .claim_due(&topics, 10, Duration::from_secs(60))
+            .await
+            .expect("should claim messages");
+        assert!(
+            claimed_again.is_empty(),
+            "leased message should not be claimed twice"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mark_delivered_successfully() {
+        let pool = TestDatabase::create().await.expect("database should connect");
+        let repository = OutboxRepositoryWithPg::new(pool.clone());
+        let message = insert_message(&pool).await;
+
+        repository
+            .mark_delivered(&message.id)
+            .await
+            .expect("should mark delivered");
+
+        assert_eq!(
+            status_of(&pool, &message).await.0,
+            OutboxStatus::Delivered.as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mark_failed_retries_then_dead_letters() {
+        let pool = TestDatabase::create().await.expect("database should connect");
+        let repository = OutboxRepositoryWithPg::new(pool.clone());
+        let message = insert_message(&pool).await;
+        let topics = vec![message.topic.clone()];
+
+        repository
+            .mark_failed(&message.id, "temporary failure", Some(Utc::now()))
+            .await
+            .expect("should mark failed");
+        assert_eq!(
+            status_of(&pool, &message).await.0,
+            OutboxStatus::Pending.as_str()
+        );
+        let claimed = repository
+            .claim_due(&topics, 10, Duration::from_secs(60))
+            .await
+            .expect("should claim messages");
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].last_error.as_deref(), Some("temporary failure"));
+
+        repository
+            .mark_failed(&message.id, "permanent failure", None)
+            .await
+            .expect("should mark failed");
+        assert_eq!(
+            status_of(&pool, &message).await,
+            (OutboxStatus::Dead.as_str().to_owned(), 1)
+        );
+    }
+}
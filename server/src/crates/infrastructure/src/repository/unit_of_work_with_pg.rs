@@ -0,0 +1,374 @@
+use std::sync::Arc;
+
+use domain::{
+    entity::{
+        audit_event::{AuditEvent, AuditEventFilter, AuditEventPage},
+        user::User,
+        value_object::user_id::UserId,
+    },
+    error::user_error::UserEmailDuplicateValidationError,
+    interface::{
+        audit_log_repository_interface::AuditLogRepositoryInterface,
+        unit_of_work_interface::{TransactionInterface, UnitOfWorkInterface},
+        user_email_duplicate_validator_interface::UserEmailDuplicateValidatorInterface,
+        user_repository_interface::UserRepositoryInterface,
+    },
+};
+use futures_util::{StreamExt, TryStreamExt, stream::BoxStream};
+use tokio::sync::Mutex;
+
+use crate::repository::{
+    audit_log_repository_with_pg::{insert_audit_event, select_audit_events},
+    user_email_duplicate_validator_with_pg::check_user_email_duplicate,
+    user_repository_with_pg::{
+        delete_user, insert_user, select_all_users, select_user_by_id, update_user,
+    },
+};
+
+/// Shared by the repositories of one [`TransactionWithPg`]; each call holds the lock only for
+/// the duration of its statements.
+type SharedTransaction = Arc<Mutex<sqlx::Transaction<'static, sqlx::Postgres>>>;
+
+#[derive(Debug, Clone)]
+pub struct UnitOfWorkWithPg {
+    db: sqlx::PgPool,
+}
+
+impl UnitOfWorkWithPg {
+    pub fn new(db: sqlx::PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait::async_trait]
+impl UnitOfWorkInterface for UnitOfWorkWithPg {
+    #[tracing::instrument(
+        name = "UnitOfWorkWithPg::begin",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "BEGIN")
+    )]
+    async fn begin(&self) -> Result<Box<dyn TransactionInterface + Send + Sync>, anyhow::Error> {
+        let tx = Arc::new(Mutex::new(self.db.begin().await?));
+
+        Ok(Box::new(TransactionWithPg {
+            user_repository: Arc::new(TransactionalUserRepositoryWithPg { tx: tx.clone() }),
+            user_email_duplicate_validator: Arc::new(
+                TransactionalUserEmailDuplicateValidatorWithPg { tx: tx.clone() },
+            ),
+            audit_log_repository: Arc::new(TransactionalAuditLogRepositoryWithPg {
+                tx: tx.clone(),
+            }),
+            tx,
+        }))
+    }
+}
+
+pub struct TransactionWithPg {
+    tx: SharedTransaction,
+    user_repository: Arc<TransactionalUserRepositoryWithPg>,
+    user_email_duplicate_validator: Arc<TransactionalUserEmailDuplicateValidatorWithPg>,
+    audit_log_repository: Arc<TransactionalAuditLogRepositoryWithPg>,
+}
+
+impl TransactionWithPg {
+    /// Takes the transaction back from the repositories, failing if a caller still holds one.
+    fn into_inner(self) -> Result<sqlx::Transaction<'static, sqlx::Postgres>, anyhow::Error> {
+        let TransactionWithPg {
+            tx,
+            user_repository,
+            user_email_duplicate_validator,
+            audit_log_repository,
+        } = self;
+        drop((
+            user_repository,
+            user_email_duplicate_validator,
+            audit_log_repository,
+        ));
+        Arc::try_unwrap(tx)
+            .map(Mutex::into_inner)
+            .map_err(|_| anyhow::Error::msg("Transaction is still in use"))
+    }
+}
+
+#[async_trait::async_trait]
+impl TransactionInterface for TransactionWithPg {
+    fn user_repository(&self) -> Arc<dyn UserRepositoryInterface + Send + Sync> {
+        self.user_repository.clone()
+    }
+
+    fn user_email_duplicate_validator(
+        &self,
+    ) -> Arc<dyn UserEmailDuplicateValidatorInterface + Send + Sync> {
+        self.user_email_duplicate_validator.clone()
+    }
+
+    fn audit_log_repository(&self) -> Arc<dyn AuditLogRepositoryInterface + Send + Sync> {
+        self.audit_log_repository.clone()
+    }
+
+    #[tracing::instrument(
+        name = "TransactionWithPg::commit",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "COMMIT")
+    )]
+    async fn commit(self: Box<Self>) -> Result<(), anyhow::Error> {
+        self.into_inner()?.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "TransactionWithPg::rollback",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "ROLLBACK")
+    )]
+    async fn rollback(self: Box<Self>) -> Result<(), anyhow::Error> {
+        self.into_inner()?.rollback().await?;
+        Ok(())
+    }
+}
+
+pub struct TransactionalUserRepositoryWithPg {
+    tx: SharedTransaction,
+}
+
+#[async_trait::async_trait]
+impl UserRepositoryInterface for TransactionalUserRepositoryWithPg {
+    #[tracing::instrument(
+        name = "TransactionalUserRepositoryWithPg::create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "INSERT", user_id = %user.id)
+    )]
+    async fn create(&self, user: &User) -> Result<User, anyhow::Error> {
+        let mut tx = self.tx.lock().await;
+        insert_user(&mut tx, user).await
+    }
+
+    #[tracing::instrument(
+        name = "TransactionalUserRepositoryWithPg::find_all",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn find_all(&self) -> Result<Vec<User>, anyhow::Error> {
+        let mut tx = self.tx.lock().await;
+        select_all_users(&mut tx).await
+    }
+
+    /// Reads every user up front: the rows could not outlive the lock on the transaction.
+    fn stream_all(&self) -> BoxStream<'static, Result<User, anyhow::Error>> {
+        let tx = self.tx.clone();
+        futures_util::stream::once(async move {
+            let mut tx = tx.lock().await;
+            select_all_users(&mut tx).await
+        })
+        .map_ok(|users| futures_util::stream::iter(users.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+
+    #[tracing::instrument(
+        name = "TransactionalUserRepositoryWithPg::find_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "SELECT", user_id = %user_id)
+    )]
+    async fn find_by_id(&self, user_id: &UserId) -> Result<User, anyhow::Error> {
+        let mut tx = self.tx.lock().await;
+        select_user_by_id(&mut tx, user_id).await
+    }
+
+    #[tracing::instrument(
+        name = "TransactionalUserRepositoryWithPg::update",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "UPDATE", user_id = %user.id)
+    )]
+    async fn update(&self, user: &User) -> Result<User, anyhow::Error> {
+        let mut tx = self.tx.lock().await;
+        update_user(&mut tx, user).await
+    }
+
+    #[tracing::instrument(
+        name = "TransactionalUserRepositoryWithPg::delete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "DELETE", user_id = %user.id)
+    )]
+    async fn delete(&self, user: &User) -> Result<(), anyhow::Error> {
+        let mut tx = self.tx.lock().await;
+        delete_user(&mut tx, user).await
+    }
+}
+
+pub struct TransactionalUserEmailDuplicateValidatorWithPg {
+    tx: SharedTransaction,
+}
+
+#[async_trait::async_trait]
+impl UserEmailDuplicateValidatorInterface for TransactionalUserEmailDuplicateValidatorWithPg {
+    #[tracing::instrument(
+        name = "TransactionalUserEmailDuplicateValidatorWithPg::validate_user_email_duplicate",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn validate_user_email_duplicate(
+        &self,
+        email: &str,
+    ) -> Result<(), UserEmailDuplicateValidationError> {
+        let mut tx = self.tx.lock().await;
+        check_user_email_duplicate(&mut tx, email).await
+    }
+}
+
+pub struct TransactionalAuditLogRepositoryWithPg {
+    tx: SharedTransaction,
+}
+
+#[async_trait::async_trait]
+impl AuditLogRepositoryInterface for TransactionalAuditLogRepositoryWithPg {
+    #[tracing::instrument(
+        name = "TransactionalAuditLogRepositoryWithPg::append",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "INSERT", action = %event.action)
+    )]
+    async fn append(&self, event: &AuditEvent) -> Result<(), anyhow::Error> {
+        let mut tx = self.tx.lock().await;
+        insert_audit_event(&mut tx, event)
+            .await
+            .map_err(|_| anyhow::Error::msg("Failed to append audit event"))
+    }
+
+    #[tracing::instrument(
+        name = "TransactionalAuditLogRepositoryWithPg::find",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn find(&self, filter: &AuditEventFilter) -> Result<AuditEventPage, anyhow::Error> {
+        let mut tx = self.tx.lock().await;
+        select_audit_events(&mut tx, filter).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::test_database::TestDatabase;
+    use domain::{
+        entity::{
+            audit_event::{AuditAction, AuditEvent},
+            request_context::RequestContext,
+            user::User,
+        },
+        interface::{
+            audit_log_repository_interface::AuditLogRepositoryInterface,
+            unit_of_work_interface::UnitOfWorkInterface,
+            user_email_duplicate_validator_interface::UserEmailDuplicateValidatorInterface,
+            user_repository_interface::UserRepositoryInterface,
+        },
+    };
+
+    use super::UnitOfWorkWithPg;
+    use crate::repository::user_repository_with_pg::UserRepositoryWithPg;
+
+    fn audit_event(user: &User) -> AuditEvent {
+        AuditEvent::new(
+            AuditAction::UserCreated,
+            Some(user.id.clone()),
+            &RequestContext::default(),
+            chrono::Utc::now(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_committed_writes_are_visible() {
+        let pool = TestDatabase::create()
+            .await
+            .expect("database should connect");
+        let unit_of_work = UnitOfWorkWithPg::new(pool.clone());
+        let user = User::new(
+            "Test User".into(),
+            format!("test+{}@example.com", uuid::Uuid::new_v4()),
+        );
+
+        let tx = unit_of_work.begin().await.expect("should begin");
+        tx.user_email_duplicate_validator()
+            .validate_user_email_duplicate(&user.email)
+            .await
+            .expect("email should be unused");
+        tx.user_repository()
+            .create(&user)
+            .await
+            .expect("should create user");
+        // Reads inside the transaction see its own writes.
+        tx.user_email_duplicate_validator()
+            .validate_user_email_duplicate(&user.email)
+            .await
+            .expect_err("email should now be taken");
+        tx.commit().await.expect("should commit");
+
+        let found = UserRepositoryWithPg::new(pool.clone())
+            .find_by_id(&user.id)
+            .await
+            .expect("committed user should be visible");
+        assert_eq!(found.email, user.email);
+    }
+
+    #[tokio::test]
+    async fn test_rolled_back_and_dropped_writes_are_discarded() {
+        let pool = TestDatabase::create()
+            .await
+            .expect("database should connect");
+        let unit_of_work = UnitOfWorkWithPg::new(pool.clone());
+        let user_repository = UserRepositoryWithPg::new(pool.clone());
+
+        let rolled_back = User::new(
+            "Test User".into(),
+            format!("test+{}@example.com", uuid::Uuid::new_v4()),
+        );
+        let tx = unit_of_work.begin().await.expect("should begin");
+        tx.user_repository()
+            .create(&rolled_back)
+            .await
+            .expect("should create user");
+        tx.audit_log_repository()
+            .append(&audit_event(&rolled_back))
+            .await
+            .expect("should append audit event");
+        tx.rollback().await.expect("should roll back");
+
+        let dropped = User::new(
+            "Test User".into(),
+            format!("test+{}@example.com", uuid::Uuid::new_v4()),
+        );
+        {
+            let tx = unit_of_work.begin().await.expect("should begin");
+            tx.user_repository()
+                .create(&dropped)
+                .await
+                .expect("should create user");
+            tx.audit_log_repository()
+                .append(&audit_event(&dropped))
+                .await
+                .expect("should append audit event");
+        }
+
+        for user in [&rolled_back, &dropped] {
+            let result = user_repository.find_by_id(&user.id).await;
+            assert!(matches!(
+                result.unwrap_err().downcast_ref::<sqlx::Error>(),
+                Some(sqlx::Error::RowNotFound)
+            ));
+            let outbox_messages = sqlx::query_scalar!(
+                r#"SELECT count(*) AS "count!" FROM outbox WHERE payload->>'user_id' = $1"#,
+                user.id.to_string()
+            )
+            .fetch_one(&*pool)
+            .await
+            .expect("should count outbox messages");
+            assert_eq!(outbox_messages, 0);
+            let audit_events = sqlx::query_scalar!(
+                r#"SELECT count(*) AS "count!" FROM audit_log WHERE subject_id = $1"#,
+                user.id.0
+            )
+            .fetch_one(&*pool)
+            .await
+            .expect("should count audit events");
+            assert_eq!(audit_events, 0);
+        }
+    }
+}
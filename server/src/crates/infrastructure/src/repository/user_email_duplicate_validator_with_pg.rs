@@ -10,51 +10,55 @@ pub struct UserEmailDuplicateValidatorWithPg {
 
 impl UserEmailDuplicateValidatorWithPg {
     pub fn new(db: sqlx::PgPool) -> Self {
-        Self { db: db }
+        Self { db }
     }
 }
 
 #[async_trait::async_trait]
 impl UserEmailDuplicateValidatorInterface for UserEmailDuplicateValidatorWithPg {
+    #[tracing::instrument(
+        name = "UserEmailDuplicateValidatorWithPg::validate_user_email_duplicate",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "SELECT")
+    )]
     async fn validate_user_email_duplicate(
         &self,
         email: &str,
     ) -> Result<(), UserEmailDuplicateValidationError> {
-        let is_exist: bool =
-            sqlx::query_scalar(r#"SELECT EXISTS(SELECT 1 FROM "user" WHERE email = $1)"#)
-                .bind(email)
-                .fetch_one(&self.db)
-                .await?;
+        let mut conn = self.db.acquire().await?;
+        check_user_email_duplicate(&mut conn, email).await
+    }
+}
 
-        if is_exist {
-            return Err(UserEmailDuplicateValidationError::AlreadyExists);
-        }
+pub(crate) async fn check_user_email_duplicate(
+    conn: &mut sqlx::PgConnection,
+    email: &str,
+) -> Result<(), UserEmailDuplicateValidationError> {
+    let is_exist: bool =
+        sqlx::query_scalar(r#"SELECT EXISTS(SELECT 1 FROM "user" WHERE email = $1)"#)
+            .bind(email)
+            .fetch_one(conn)
+            .await?;
 
-        Ok(())
+    if is_exist {
+        return Err(UserEmailDuplicateValidationError::AlreadyExists);
     }
+
+    Ok(())
 }
+
 #[cfg(test)]
 mod tests {
+    use crate::testing::test_database::TestDatabase;
     use super::*;
     use crate::repository::user_repository_with_pg::UserRepositoryWithPg;
     use domain::entity::user::User;
     use domain::interface::user_repository_interface::UserRepositoryInterface;
 
-    async fn connect() -> Result<sqlx::PgPool, sqlx::Error> {
-        dotenv::dotenv().ok();
-
-        let database_url =
-            std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
-        sqlx::postgres::PgPoolOptions::new()
-            .max_connections(5)
-            .connect(&database_url)
-            .await
-    }
-
     #[tokio::test]
     async fn test_validate_returns_ok_for_new_email() {
-        let pool = connect().await.unwrap();
-        let validator = UserEmailDuplicateValidatorWithPg::new(pool);
+        let pool = TestDatabase::create().await.unwrap();
+        let validator = UserEmailDuplicateValidatorWithPg::new(pool.clone());
         let new_email = format!("unique+{}@example.com", uuid::Uuid::new_v4());
         let result = validator.validate_user_email_duplicate(&new_email).await;
 
@@ -63,12 +67,12 @@ mod tests {
 
     #[tokio::test]
     async fn test_validate_returns_err_for_existing_email() {
-        let pool = connect().await.unwrap();
+        let pool = TestDatabase::create().await.unwrap();
         let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
         let user = User::new("Test User".into(), email.clone());
         let user_repo = UserRepositoryWithPg::new(pool.clone());
         user_repo.create(&user).await.expect("should insert user");
-        let validator = UserEmailDuplicateValidatorWithPg::new(pool);
+        let validator = UserEmailDuplicateValidatorWithPg::new(pool.clone());
         let result = validator.validate_user_email_duplicate(&email).await;
 
         assert!(result.is_err());
@@ -1,7 +1,16 @@
 use crate::model::user_model::UserModel;
+use crate::repository::outbox_repository_with_pg::insert_outbox_message;
+use domain::entity::outbox_message::OutboxMessage;
 use domain::entity::user::User;
 use domain::entity::value_object::user_id::UserId;
+use domain::error::user_error::UserConcurrencyError;
+use domain::event::domain_event::DomainEvent;
 use domain::interface::user_repository_interface::UserRepositoryInterface;
+use futures_util::{StreamExt, stream::BoxStream};
+use tracing::Instrument;
+
+/// How many users [`UserRepositoryWithPg::stream_all`] reads ahead of a slow consumer.
+const STREAM_BUFFER: usize = 64;
 
 #[derive(Debug, Clone)]
 pub struct UserRepositoryWithPg {
@@ -16,104 +25,302 @@ impl UserRepositoryWithPg {
 
 #[async_trait::async_trait]
 impl UserRepositoryInterface for UserRepositoryWithPg {
+    #[tracing::instrument(
+        name = "UserRepositoryWithPg::create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "INSERT", user_id = %user.id)
+    )]
     async fn create(&self, user: &User) -> Result<User, anyhow::Error> {
-        tracing::info!("create_user: {:?}", user);
-        let user_model = UserModel::try_from(user.clone())?;
-        let row = sqlx::query_as!(
-            UserModel,
-            r#"
-            INSERT INTO "user" (id, name, email)
-            VALUES ($1, $2, $3)
-            RETURNING id, name, email
-            "#,
-            user_model.id,
-            user_model.name,
-            user_model.email
-        )
-        .fetch_one(&self.db)
-        .await
-        .map_err(|e| {
-            eprintln!("Failed to insert user: {:?}", e);
-            anyhow::Error::msg("Failed to insert user")
-        })?;
+        let mut tx = self.db.begin().await?;
+        let user = insert_user(&mut tx, user).await?;
+        tx.commit().await?;
 
-        Ok(User::try_from(row)?)
+        Ok(user)
     }
 
+    #[tracing::instrument(
+        name = "UserRepositoryWithPg::find_all",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "SELECT")
+    )]
     async fn find_all(&self) -> Result<Vec<User>, anyhow::Error> {
-        let rows = sqlx::query_as!(
-            UserModel,
-            r#"
-            SELECT id, name, email FROM "user"
-            ORDER BY name ASC
-            "#
-        )
-        .fetch_all(&self.db)
-        .await
-        .map_err(|e| {
-            eprintln!("Failed to fetch users: {:?}", e);
-            anyhow::Error::msg("Failed to fetch users")
-        })?;
+        let mut conn = self.db.acquire().await?;
+        select_all_users(&mut conn).await
+    }
 
-        rows.into_iter()
-            .map(User::try_from)
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| {
-                eprintln!("Failed to convert UserModel to User: {:?}", e);
-                anyhow::Error::msg("Data conversion failed")
-            })
+    #[tracing::instrument(
+        name = "UserRepositoryWithPg::stream_all",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "SELECT")
+    )]
+    fn stream_all(&self) -> BoxStream<'static, Result<User, anyhow::Error>> {
+        // The rows borrow the pool they are fetched from, so a task owning a handle to it reads
+        // them into a bounded channel; it stops once the receiver is dropped.
+        let db = self.db.clone();
+        let (sender, receiver) = tokio::sync::mpsc::channel(STREAM_BUFFER);
+        tokio::spawn(
+            async move {
+                let mut rows = sqlx::query_as!(
+                    UserModel,
+                    r#"
+                    SELECT id, name, email, email_verified_at, updated_at, version FROM "user"
+                    ORDER BY name ASC
+                    "#
+                )
+                .fetch(&db);
+                while let Some(row) = rows.next().await {
+                    let user = row
+                        .map_err(|e| {
+                            tracing::error!(error = %e, "failed to stream users");
+                            anyhow::Error::msg("Failed to fetch users")
+                        })
+                        .and_then(|row| {
+                            User::try_from(row).map_err(|e| {
+                                tracing::error!(error = %e, "failed to convert UserModel to User");
+                                anyhow::Error::msg("Data conversion failed")
+                            })
+                        });
+                    let failed = user.is_err();
+                    if sender.send(user).await.is_err() || failed {
+                        break;
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+
+        futures_util::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|user| (user, receiver))
+        })
+        .boxed()
     }
 
+    #[tracing::instrument(
+        name = "UserRepositoryWithPg::find_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "SELECT", user_id = %user_id)
+    )]
     async fn find_by_id(&self, user_id: &UserId) -> Result<User, anyhow::Error> {
-        let row = sqlx::query_as!(
-            UserModel,
-            r#"
-            SELECT id, name, email FROM "user" WHERE id = $1
-            "#,
-            user_id.0
-        )
-        .fetch_one(&self.db)
+        let mut conn = self.db.acquire().await?;
+        select_user_by_id(&mut conn, user_id).await
+    }
+
+    #[tracing::instrument(
+        name = "UserRepositoryWithPg::update",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "UPDATE", user_id = %user.id)
+    )]
+    async fn update(&self, user: &User) -> Result<User, anyhow::Error> {
+        let mut conn = self.db.acquire().await?;
+        update_user(&mut conn, user).await
+    }
+
+    #[tracing::instrument(
+        name = "UserRepositoryWithPg::delete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.operation = "DELETE", user_id = %user.id)
+    )]
+    async fn delete(&self, user: &User) -> Result<(), anyhow::Error> {
+        let mut conn = self.db.acquire().await?;
+        delete_user(&mut conn, user).await
+    }
+}
+
+/// Inserts the user together with its `UserCreated` outbox message; `conn` must be inside a
+/// transaction for the two to commit atomically.
+pub(crate) async fn insert_user(
+    conn: &mut sqlx::PgConnection,
+    user: &User,
+) -> Result<User, anyhow::Error> {
+    tracing::info!(user_id = %user.id, "creating user");
+    let user_model = UserModel::from(user.clone());
+    let row = sqlx::query_as!(
+        UserModel,
+        r#"
+        INSERT INTO "user" (id, name, email, email_verified_at, updated_at, version)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, name, email, email_verified_at, updated_at, version
+        "#,
+        user_model.id,
+        user_model.name,
+        user_model.email,
+        user_model.email_verified_at,
+        user_model.updated_at,
+        user_model.version
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| {
+        // `Display` only: the `Debug` output of a database error carries the offending row.
+        tracing::error!(error = %e, "failed to insert user");
+        anyhow::Error::msg("Failed to insert user")
+    })?;
+
+    // Recorded with the row so subscribers that must not miss it (such as the verification
+    // mail) are only notified of committed users.
+    let event = DomainEvent::user_created(user.id.clone());
+    insert_outbox_message(conn, &OutboxMessage::from_event(&event))
         .await
         .map_err(|e| {
-            if matches!(e, sqlx::Error::RowNotFound) {
-                anyhow::Error::new(e)
-            } else {
-                eprintln!("Failed to fetch user by ID: {:?}", e);
-                anyhow::Error::msg("Failed to fetch user by ID")
-            }
+            tracing::error!(error = %e, "failed to insert outbox message");
+            anyhow::Error::msg("Failed to insert user")
         })?;
 
-        Ok(User::try_from(row)?)
+    User::try_from(row)
+}
+
+pub(crate) async fn select_all_users(
+    conn: &mut sqlx::PgConnection,
+) -> Result<Vec<User>, anyhow::Error> {
+    let rows = sqlx::query_as!(
+        UserModel,
+        r#"
+        SELECT id, name, email, email_verified_at, updated_at, version FROM "user"
+        ORDER BY name ASC
+        "#
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "failed to fetch users");
+        anyhow::Error::msg("Failed to fetch users")
+    })?;
+
+    rows.into_iter()
+        .map(User::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to convert UserModel to User");
+            anyhow::Error::msg("Data conversion failed")
+        })
+}
+
+pub(crate) async fn select_user_by_id(
+    conn: &mut sqlx::PgConnection,
+    user_id: &UserId,
+) -> Result<User, anyhow::Error> {
+    let row = sqlx::query_as!(
+        UserModel,
+        r#"
+        SELECT id, name, email, email_verified_at, updated_at, version FROM "user" WHERE id = $1
+        "#,
+        user_id.0
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(|e| {
+        if matches!(e, sqlx::Error::RowNotFound) {
+            anyhow::Error::new(e)
+        } else {
+            tracing::error!(error = %e, user_id = %user_id, "failed to fetch user by ID");
+            anyhow::Error::msg("Failed to fetch user by ID")
+        }
+    })?;
+
+    User::try_from(row)
+}
+
+/// Writes the user if its stored version is still `user.version`, incrementing it.
+pub(crate) async fn update_user(
+    conn: &mut sqlx::PgConnection,
+    user: &User,
+) -> Result<User, anyhow::Error> {
+    tracing::info!(user_id = %user.id, "updating user");
+    let user_model = UserModel::from(user.clone());
+    let row = sqlx::query_as!(
+        UserModel,
+        r#"
+        UPDATE "user"
+        SET name = $2, email = $3, email_verified_at = $4, updated_at = $5, version = version + 1
+        WHERE id = $1 AND version = $6
+        RETURNING id, name, email, email_verified_at, updated_at, version
+        "#,
+        user_model.id,
+        user_model.name,
+        user_model.email,
+        user_model.email_verified_at,
+        user_model.updated_at,
+        user_model.version
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, user_id = %user.id, "failed to update user");
+        anyhow::Error::msg("Failed to update user")
+    })?;
+
+    match row {
+        Some(row) => User::try_from(row),
+        None => Err(unmatched_write_error(conn, &user.id).await),
+    }
+}
+
+/// Deletes the user if its stored version is still `user.version`.
+pub(crate) async fn delete_user(
+    conn: &mut sqlx::PgConnection,
+    user: &User,
+) -> Result<(), anyhow::Error> {
+    tracing::info!(user_id = %user.id, "deleting user");
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM "user" WHERE id = $1 AND version = $2
+        "#,
+        user.id.0,
+        user.version
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, user_id = %user.id, "failed to delete user");
+        anyhow::Error::msg("Failed to delete user")
+    })?;
+    if deleted.rows_affected() == 0 {
+        return Err(unmatched_write_error(conn, &user.id).await);
+    }
+
+    Ok(())
+}
+
+/// Tells apart why a versioned write matched no row: the user is gone, or another write
+/// changed its version.
+async fn unmatched_write_error(conn: &mut sqlx::PgConnection, user_id: &UserId) -> anyhow::Error {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM "user" WHERE id = $1) AS "exists!""#,
+        user_id.0
+    )
+    .fetch_one(conn)
+    .await;
+
+    match exists {
+        Ok(true) => {
+            tracing::warn!(user_id = %user_id, "rejected write with a stale user version");
+            anyhow::Error::new(UserConcurrencyError::Conflict)
+        }
+        Ok(false) => anyhow::Error::new(sqlx::Error::RowNotFound),
+        Err(e) => {
+            tracing::error!(error = %e, user_id = %user_id, "failed to check user existence");
+            anyhow::Error::msg("Failed to write user")
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::testing::test_database::TestDatabase;
     use domain::{
         entity::user::User, interface::user_repository_interface::UserRepositoryInterface,
     };
 
     use super::UserRepositoryWithPg;
 
-    async fn connect() -> Result<sqlx::PgPool, sqlx::Error> {
-        dotenv::dotenv().ok();
-
-        let database_url =
-            std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
-        let pool = sqlx::postgres::PgPoolOptions::new()
-            .max_connections(5)
-            .connect(&database_url)
-            .await?;
-
-        Ok(pool)
-    }
-
     #[tokio::test]
     async fn test_create_user_successfully() {
         let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
-        let pool = connect().await.expect("database should connect");
+        let pool = TestDatabase::create()
+            .await
+            .expect("database should connect");
         let user_repository = UserRepositoryWithPg::new(pool.clone());
-        let user = User::new("Test User".into(), email.into());
+        let user = User::new("Test User".into(), email);
         let created_user = user_repository
             .create(&user)
             .await
@@ -121,11 +328,22 @@ mod tests {
 
         assert_eq!(created_user.name, user.name);
         assert_eq!(created_user.email, user.email);
+
+        let topics = sqlx::query_scalar!(
+            r#"SELECT topic FROM outbox WHERE payload->>'user_id' = $1"#,
+            user.id.to_string()
+        )
+        .fetch_all(&*pool)
+        .await
+        .expect("should fetch outbox messages");
+        assert_eq!(topics, vec!["user.created".to_owned()]);
     }
 
     #[tokio::test]
     async fn test_find_all_users_successfully() {
-        let pool = connect().await.expect("database should connect");
+        let pool = TestDatabase::create()
+            .await
+            .expect("database should connect");
         let user_repository = UserRepositoryWithPg::new(pool.clone());
 
         let email1 = format!("user1+{}@example.com", uuid::Uuid::new_v4());
@@ -147,22 +365,18 @@ mod tests {
             .await
             .expect("should fetch all users");
 
-        assert!(
-            users.iter().any(|u| u.email == email1),
-            "User 1 should exist"
-        );
-        assert!(
-            users.iter().any(|u| u.email == email2),
-            "User 2 should exist"
-        );
+        let emails: Vec<String> = users.into_iter().map(|u| u.email).collect();
+        assert_eq!(emails, vec![email1, email2]);
     }
 
     #[tokio::test]
     async fn test_find_user_by_id_successfully() {
         let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
-        let pool = connect().await.expect("database should connect");
+        let pool = TestDatabase::create()
+            .await
+            .expect("database should connect");
         let user_repository = UserRepositoryWithPg::new(pool.clone());
-        let user = User::new("Test User".into(), email.into());
+        let user = User::new("Test User".into(), email);
         user_repository
             .create(&user)
             .await
@@ -175,4 +389,61 @@ mod tests {
         assert_eq!(find_user.email, user.email);
         assert_eq!(find_user.id, user.id);
     }
+
+    mod contract {
+        use domain::{
+            entity::{user::User, value_object::user_id::UserId},
+            interface::user_repository_interface::UserRepositoryInterface,
+        };
+        use futures_util::stream::BoxStream;
+
+        use super::{TestDatabase, UserRepositoryWithPg};
+
+        /// Keeps the schema alive for as long as the repository under test uses it.
+        struct SchemaScopedRepository {
+            repository: UserRepositoryWithPg,
+            _database: TestDatabase,
+        }
+
+        impl SchemaScopedRepository {
+            async fn create() -> Self {
+                let database = TestDatabase::create()
+                    .await
+                    .expect("database should connect");
+                Self {
+                    repository: UserRepositoryWithPg::new(database.pool().clone()),
+                    _database: database,
+                }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl UserRepositoryInterface for SchemaScopedRepository {
+            async fn create(&self, user: &User) -> Result<User, anyhow::Error> {
+                self.repository.create(user).await
+            }
+
+            async fn find_all(&self) -> Result<Vec<User>, anyhow::Error> {
+                self.repository.find_all().await
+            }
+
+            fn stream_all(&self) -> BoxStream<'static, Result<User, anyhow::Error>> {
+                self.repository.stream_all()
+            }
+
+            async fn find_by_id(&self, user_id: &UserId) -> Result<User, anyhow::Error> {
+                self.repository.find_by_id(user_id).await
+            }
+
+            async fn update(&self, user: &User) -> Result<User, anyhow::Error> {
+                self.repository.update(user).await
+            }
+
+            async fn delete(&self, user: &User) -> Result<(), anyhow::Error> {
+                self.repository.delete(user).await
+            }
+        }
+
+        domain::user_repository_contract_tests!(SchemaScopedRepository::create());
+    }
 }
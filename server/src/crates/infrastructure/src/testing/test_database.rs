@@ -0,0 +1,153 @@
+//! A throwaway Postgres schema per test, so tests can assert on everything a table holds and still
+//! run in parallel against the same `TEST_DATABASE_URL`.
+
+use std::ops::Deref;
+
+use sqlx::{
+    Connection, Executor, PgPool,
+    postgres::{PgConnectOptions, PgPoolOptions},
+};
+
+/// A freshly migrated schema that is dropped again when this value goes out of scope.
+///
+/// Derefs to a [`PgPool`] whose connections only see that schema.
+pub struct TestDatabase {
+    pool: PgPool,
+    schema: String,
+    database_url: String,
+}
+
+impl TestDatabase {
+    pub async fn create() -> Result<Self, anyhow::Error> {
+        dotenv::dotenv().ok();
+
+        let database_url =
+            std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let schema = format!("test_{}", uuid::Uuid::new_v4().simple());
+
+        let mut admin = sqlx::PgConnection::connect_with(&database_url.parse()?).await?;
+        admin
+            .execute(format!(r#"CREATE SCHEMA "{}""#, schema).as_str())
+            .await?;
+
+        let options: PgConnectOptions = database_url.parse()?;
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect_with(
+                options
+                    .options([("search_path", schema.as_str())])
+                    .application_name(&schema),
+            )
+            .await?;
+
+        let database = Self {
+            pool,
+            schema,
+            database_url,
+        };
+        // Every schema keeps its own `_sqlx_migrations`; the advisory lock is database-wide and
+        // would only serialize tests that never touch each other's tables.
+        let mut migrator = sqlx::migrate!("../../../migrations");
+        migrator.set_locking(false);
+        migrator.run(&database.pool).await?;
+
+        Ok(database)
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+impl Deref for TestDatabase {
+    type Target = PgPool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pool
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        let database_url = std::mem::take(&mut self.database_url);
+        let schema = std::mem::take(&mut self.schema);
+
+        // `Drop` cannot await and the test's runtime may already be shutting down, so the schema
+        // is dropped from a separate thread with a runtime of its own.
+        let dropped = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            runtime.block_on(async {
+                let mut connection =
+                    sqlx::PgConnection::connect_with(&database_url.parse()?).await?;
+                // The test's runtime is blocked on this very `drop`, so transactions its pool still
+                // holds would never roll back and keep their locks on the schema.
+                sqlx::query(
+                    "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE application_name = $1",
+                )
+                .bind(&schema)
+                .execute(&mut connection)
+                .await?;
+                connection
+                    .execute(format!(r#"DROP SCHEMA IF EXISTS "{}" CASCADE"#, schema).as_str())
+                    .await?;
+                Ok::<_, anyhow::Error>(())
+            })
+        })
+        .join();
+
+        match dropped {
+            Ok(Err(e)) => tracing::warn!(error = %e, "failed to drop test schema"),
+            Err(_) => tracing::warn!("failed to drop test schema: cleanup thread panicked"),
+            Ok(Ok(())) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TestDatabase;
+
+    async fn schema_exists(pool: &sqlx::PgPool, schema: &str) -> bool {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM information_schema.schemata WHERE schema_name = $1)",
+        )
+        .bind(schema)
+        .fetch_one(pool)
+        .await
+        .expect("should query schemata")
+    }
+
+    async fn count_users(pool: &sqlx::PgPool) -> i64 {
+        sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM "user""#)
+            .fetch_one(pool)
+            .await
+            .expect("should count users")
+    }
+
+    #[tokio::test]
+    async fn test_each_database_starts_empty_and_is_dropped() {
+        let first = TestDatabase::create()
+            .await
+            .expect("database should be created");
+        let second = TestDatabase::create()
+            .await
+            .expect("database should be created");
+        let schema = first.schema.clone();
+
+        sqlx::query(
+            r#"INSERT INTO "user" (id, name, email) VALUES ($1, 'Only Here', 'only@example.com')"#,
+        )
+        .bind(uuid::Uuid::new_v4())
+        .execute(&*first)
+        .await
+        .expect("should insert user");
+
+        assert_eq!(count_users(&first).await, 1);
+        assert_eq!(count_users(&second).await, 0);
+
+        drop(first);
+        assert!(!schema_exists(&second, &schema).await);
+    }
+}
@@ -0,0 +1,94 @@
+use axum::{
+    Router,
+    routing::{get, post, put},
+};
+
+use crate::{
+    app::AppState,
+    handler::{
+        handle_create_user, handle_delete_user, handle_export_users, handle_find_all_user,
+        handle_find_audit_events, handle_find_user_by_id, handle_import_users,
+        handle_issue_csrf_token, handle_issue_email_verification, handle_update_user,
+        handle_verify_email,
+    },
+    middleware::require_admin::require_admin,
+};
+
+/// A mounted major version of the API. Each one is nested under its own prefix, so a breaking
+/// change ships as a new variant whose routes reuse the handlers that did not change, while
+/// the previous version keeps serving until it is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ApiVersion {
+    V1,
+}
+
+impl ApiVersion {
+    pub(crate) const ALL: &[ApiVersion] = &[ApiVersion::V1];
+
+    pub(crate) fn prefix(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/v1",
+        }
+    }
+
+    /// The routes of this version, relative to [`ApiVersion::prefix`].
+    fn routes(self, state: &AppState) -> Router<AppState> {
+        match self {
+            ApiVersion::V1 => v1(state),
+        }
+    }
+}
+
+/// Every version nested under its prefix, ready to be merged into the root router.
+pub(crate) fn routes(state: &AppState) -> Router<AppState> {
+    ApiVersion::ALL
+        .iter()
+        .fold(Router::new(), |router, version| {
+            router.nest(version.prefix(), version.routes(state))
+        })
+}
+
+fn v1(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/users", get(handle_find_all_user).post(handle_create_user))
+        .route(
+            "/users/{id}",
+            get(handle_find_user_by_id).merge(
+                put(handle_update_user)
+                    .delete(handle_delete_user)
+                    .route_layer(axum::middleware::from_fn_with_state(
+                        state.clone(),
+                        require_admin,
+                    )),
+            ),
+        )
+        .route(
+            "/users/export",
+            get(handle_export_users).route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                require_admin,
+            )),
+        )
+        .route(
+            "/users:import",
+            post(handle_import_users).route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                require_admin,
+            )),
+        )
+        .route(
+            "/users/{id}/verification",
+            post(handle_issue_email_verification).route_layer(
+                axum::middleware::from_fn_with_state(state.clone(), require_admin),
+            ),
+        )
+        .route("/auth/csrf", get(handle_issue_csrf_token))
+        .route("/auth/verify-email", get(handle_verify_email))
+        .route(
+            "/audit-events",
+            get(handle_find_audit_events).route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                require_admin,
+            )),
+        )
+}
@@ -1,43 +1,199 @@
 use crate::{
+    api_version,
     config::{
-        connect,
+        app_config::AppConfig,
+        connect, logging,
+        openapi::OpenApiConfig,
         problem_type::{
             BAD_REQUEST, CONFLICT, FORBIDDEN, INTERNAL_SERVER_ERROR, INVALID_JSON,
             METHOD_NOT_ALLOWED, UNAUTHORIZED, UNSUPPORTED_MEDIA_TYPE,
         },
+        storage::StorageConfig,
     },
-    handler::{handle_create_user, handle_find_all_user, handle_find_user_by_id, handle_not_found},
+    handler::{handle_metrics, handle_not_found, handle_openapi},
+    idempotency_purger,
+    metrics::Metrics,
+    middleware::{
+        csrf::protect_csrf, deprecation::announce_deprecation, idempotency::idempotency,
+        rate_limit::rate_limit, request_context::capture_request_context,
+        request_id::propagate_request_id, require_admin::require_admin,
+        track_metrics::track_metrics,
+    },
+    openapi::api_doc,
+    outbox_dispatcher,
 };
 use axum::{
     Router,
+    extract::Request,
     http::StatusCode,
-    response::{IntoResponse, Response},
+    middleware::Next,
+    response::IntoResponse,
     routing::get,
 };
+use application::{
+    event_publisher::InProcessEventPublisher,
+    event_subscriber::mail_event_subscriber::MailEventSubscriber,
+    usecase::issue_email_verification::IssueEmailVerificationUsecase,
+};
+use domain::interface::{
+    audit_log_repository_interface::AuditLogRepositoryInterface,
+    email_verification_token_repository_interface::EmailVerificationTokenRepositoryInterface,
+    event_publisher_interface::EventPublisherInterface,
+    event_subscriber_interface::EventSubscriberInterface,
+    idempotency_store_interface::IdempotencyStoreInterface, mailer_interface::MailerInterface,
+    rate_limit_store_interface::RateLimitStoreInterface,
+    unit_of_work_interface::UnitOfWorkInterface,
+    user_repository_interface::UserRepositoryInterface,
+};
 use infrastructure::repository::{
-    user_email_duplicate_validator_with_pg::UserEmailDuplicateValidatorWithPg,
+    audit_log_repository_with_pg::AuditLogRepositoryWithPg,
+    email_verification_token_repository_with_pg::EmailVerificationTokenRepositoryWithPg,
+    idempotency_store_with_pg::IdempotencyStoreWithPg,
+    in_memory_audit_log_repository::InMemoryAuditLogRepository,
+    in_memory_email_verification_token_repository::InMemoryEmailVerificationTokenRepository,
+    in_memory_idempotency_store::InMemoryIdempotencyStore,
+    in_memory_rate_limit_store::InMemoryRateLimitStore,
+    in_memory_unit_of_work::InMemoryUnitOfWork,
+    in_memory_user_repository::InMemoryUserRepository,
+    unit_of_work_with_pg::UnitOfWorkWithPg,
     user_repository_with_pg::UserRepositoryWithPg,
 };
+use std::{net::SocketAddr, sync::Arc};
 use tower::ServiceBuilder;
+use utoipa_scalar::{Scalar, Servable};
 
 #[derive(Clone)]
 pub(crate) struct AppState {
-    pub(crate) user_repository: UserRepositoryWithPg,
-    pub(crate) user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg,
+    pub(crate) user_repository: Arc<dyn UserRepositoryInterface + Send + Sync>,
+    /// Begins transactions for usecases that make several writes atomically.
+    pub(crate) unit_of_work: Arc<dyn UnitOfWorkInterface + Send + Sync>,
+    pub(crate) email_verification_token_repository:
+        Arc<dyn EmailVerificationTokenRepositoryInterface + Send + Sync>,
+    pub(crate) audit_log_repository: Arc<dyn AuditLogRepositoryInterface + Send + Sync>,
+    pub(crate) rate_limit_store: Arc<dyn RateLimitStoreInterface + Send + Sync>,
+    /// Responses to `POST` requests sent with an `Idempotency-Key`, kept for replay.
+    pub(crate) idempotency_store: Arc<dyn IdempotencyStoreInterface + Send + Sync>,
+    pub(crate) mailer: Arc<dyn MailerInterface + Send + Sync>,
+    /// Notifies in-process subscribers once a usecase's write has committed.
+    pub(crate) event_publisher: Arc<dyn EventPublisherInterface + Send + Sync>,
+    pub(crate) metrics: Metrics,
+    pub(crate) config: Arc<AppConfig>,
+}
+
+/// The storage backends an [`AppState`] is built from.
+struct Repositories {
+    user_repository: Arc<dyn UserRepositoryInterface + Send + Sync>,
+    unit_of_work: Arc<dyn UnitOfWorkInterface + Send + Sync>,
+    email_verification_token_repository:
+        Arc<dyn EmailVerificationTokenRepositoryInterface + Send + Sync>,
+    audit_log_repository: Arc<dyn AuditLogRepositoryInterface + Send + Sync>,
+    idempotency_store: Arc<dyn IdempotencyStoreInterface + Send + Sync>,
+}
+
+impl AppState {
+    pub(crate) fn new(pool: sqlx::PgPool, config: AppConfig) -> Self {
+        Self::with_repositories(
+            Repositories {
+                user_repository: Arc::new(UserRepositoryWithPg::new(pool.clone())),
+                unit_of_work: Arc::new(UnitOfWorkWithPg::new(pool.clone())),
+                email_verification_token_repository: Arc::new(
+                    EmailVerificationTokenRepositoryWithPg::new(pool.clone()),
+                ),
+                audit_log_repository: Arc::new(AuditLogRepositoryWithPg::new(pool.clone())),
+                idempotency_store: Arc::new(IdempotencyStoreWithPg::new(pool)),
+            },
+            config,
+        )
+    }
+
+    /// Keeps everything in process memory, so the full HTTP stack runs without Postgres. There
+    /// is no outbox, so the verification mail on sign-up is sent from the in-process publisher
+    /// instead, and is lost if sending it fails.
+    pub(crate) fn in_memory(config: AppConfig) -> Self {
+        let user_repository = InMemoryUserRepository::new();
+        let audit_log_repository = Arc::new(InMemoryAuditLogRepository::new());
+
+        let state = Self::with_repositories(
+            Repositories {
+                unit_of_work: Arc::new(InMemoryUnitOfWork::new(
+                    user_repository.clone(),
+                    audit_log_repository.clone(),
+                )),
+                email_verification_token_repository: Arc::new(
+                    InMemoryEmailVerificationTokenRepository::new(
+                        user_repository.clone(),
+                        audit_log_repository.clone(),
+                    ),
+                ),
+                audit_log_repository,
+                idempotency_store: Arc::new(InMemoryIdempotencyStore::new()),
+                user_repository: Arc::new(user_repository),
+            },
+            config,
+        );
+        let event_publisher = InProcessEventPublisher::new()
+            .subscribe(state.metrics.clone())
+            .subscribe(state.mail_event_subscriber());
+
+        AppState {
+            event_publisher: Arc::new(event_publisher),
+            ..state
+        }
+    }
+
+    fn with_repositories(repositories: Repositories, config: AppConfig) -> Self {
+        let metrics = Metrics::new();
+        let event_publisher = InProcessEventPublisher::new().subscribe(metrics.clone());
+
+        AppState {
+            user_repository: repositories.user_repository,
+            unit_of_work: repositories.unit_of_work,
+            email_verification_token_repository: repositories.email_verification_token_repository,
+            audit_log_repository: repositories.audit_log_repository,
+            rate_limit_store: Arc::new(InMemoryRateLimitStore::new()),
+            idempotency_store: repositories.idempotency_store,
+            mailer: config.mailer.mailer().expect("mailer should be configured"),
+            event_publisher: Arc::new(event_publisher),
+            metrics,
+            config: Arc::new(config),
+        }
+    }
+
+    /// Sends the verification mail when a user is created.
+    pub(crate) fn mail_event_subscriber(
+        &self,
+    ) -> impl EventSubscriberInterface + Send + Sync + use<> {
+        MailEventSubscriber::new(IssueEmailVerificationUsecase::new(
+            self.user_repository.clone(),
+            self.email_verification_token_repository.clone(),
+            self.mailer.clone(),
+            self.config.auth.email_verification_url.clone(),
+            self.config.auth.email_verification_ttl,
+        ))
+    }
 }
 
-fn router() -> Router<AppState> {
+pub(crate) fn router(state: AppState) -> Router {
     Router::new()
         .route("/", get(|| async { "Home" }))
-        .route("/users", get(handle_find_all_user).post(handle_create_user))
-        .route("/users/{id}", get(handle_find_user_by_id))
-        .layer(ServiceBuilder::new().layer(axum::middleware::map_response(
-            |response: Response| async move {
+        .merge(api_version::routes(&state))
+        .route(
+            "/metrics",
+            get(handle_metrics).route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                require_admin,
+            )),
+        )
+        .route("/openapi.json", get(handle_openapi))
+        .merge(api_reference(&state.config.openapi))
+        .layer(ServiceBuilder::new().layer(axum::middleware::from_fn(
+            |request: Request, next: Next| async move {
+                let instance = request.uri().path().to_owned();
+                let response = next.run(request).await;
                 if let Some(content_type) = response.headers().get(axum::http::header::CONTENT_TYPE)
+                    && content_type == "application/problem+json"
                 {
-                    if content_type == "application/problem+json" {
-                        return response;
-                    }
+                    return response;
                 }
 
 
@@ -46,7 +202,7 @@ fn router() -> Router<AppState> {
                         .with_title("Invalid JSON")
                         .with_type(INVALID_JSON)
                         .with_detail("Required fields are missing or invalid")
-                        .with_instance("/users")
+                        .with_instance(instance)
                         .into_response(),
                     StatusCode::METHOD_NOT_ALLOWED =>  problemdetails::new(StatusCode::METHOD_NOT_ALLOWED)
                         .with_title("Method Not Allowed")
@@ -60,7 +216,7 @@ fn router() -> Router<AppState> {
                     StatusCode::UNSUPPORTED_MEDIA_TYPE =>  problemdetails::new(StatusCode::UNSUPPORTED_MEDIA_TYPE)
                         .with_title("Unsupported Media Type")
                         .with_type(UNSUPPORTED_MEDIA_TYPE)
-                        .with_detail("Content-Type must be application/json")
+                        .with_detail("Content-Type must be application/json, application/msgpack or application/cbor")
                         .into_response(),
                     StatusCode::CONFLICT => problemdetails::new(StatusCode::CONFLICT)
                         .with_title("Conflict")
@@ -77,6 +233,7 @@ fn router() -> Router<AppState> {
                         .with_type(FORBIDDEN)
                         .with_detail("You don't have permission to access this resource")
                         .into_response(),
+                    StatusCode::NOT_MODIFIED => response,
                     status if !status.is_success() => problemdetails::new(status)
                         .with_title("Internal Server Error")
                         .with_type(INTERNAL_SERVER_ERROR)
@@ -95,24 +252,88 @@ fn router() -> Router<AppState> {
                 .build(),
         )
         .fallback(handle_not_found)
+        // Inside CSRF protection and rate limiting, so a replay is checked like any request.
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            idempotency,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.config.clone(),
+            announce_deprecation,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.config.clone(),
+            protect_csrf,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            capture_request_context,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.metrics.clone(),
+            track_metrics,
+        ))
+        .layer(axum::middleware::from_fn(propagate_request_id))
+        // Outermost so preflight requests are answered before any route or problem mapping runs.
+        .layer(state.config.cors.layer())
+        .with_state(state)
 }
 
-pub async fn run() -> Result<(), ()> {
-    tracing_subscriber::fmt().init();
+/// Serves the interactive API reference at `/docs` when `OPENAPI_UI` enables it.
+fn api_reference(config: &OpenApiConfig) -> Router<AppState> {
+    if !config.ui_enabled {
+        return Router::new();
+    }
+
+    Router::new().merge(Scalar::with_url("/docs", api_doc().clone()))
+}
 
-    let pool = connect::connect().await.expect("database should connect");
-    let state = AppState {
-        user_repository: UserRepositoryWithPg::new(pool.clone()),
-        user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
+pub async fn run() -> Result<(), ()> {
+    let tracer_provider = logging::init();
+
+    let config = AppConfig::from_env();
+    let (state, outbox_dispatcher) = match config.storage {
+        StorageConfig::Postgres => {
+            let pool = connect::connect().await.expect("database should connect");
+            let state = AppState::new(pool.clone(), config);
+            state
+                .metrics
+                .register_pool(pool.clone())
+                .expect("pool metrics should register");
+            let outbox_dispatcher = outbox_dispatcher::spawn(state.clone(), pool);
+            (state, Some(outbox_dispatcher))
+        }
+        StorageConfig::InMemory => {
+            tracing::warn!("using in-memory storage; all data is lost on shutdown");
+            (AppState::in_memory(config), None)
+        }
     };
 
-    let app = router().with_state(state);
+    let idempotency_purger = idempotency_purger::spawn(state.clone());
+    let app = router(state);
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:8080")
         .await
         .unwrap();
-    println!("Listening on: {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
+    tracing::info!("Listening on: {}", listener.local_addr().unwrap());
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
+
+    if let Some(outbox_dispatcher) = outbox_dispatcher {
+        outbox_dispatcher.abort();
+    }
+    idempotency_purger.abort();
+    if let Some(tracer_provider) = tracer_provider {
+        tracer_provider.shutdown().ok();
+    }
     Ok(())
 }
 
@@ -124,39 +345,26 @@ mod tests {
     use axum::http::{StatusCode, header::CONTENT_TYPE};
     use domain::entity::user::User;
     use tower::ServiceExt;
-    use crate::config::problem_type::{DUPLICATE, NOT_FOUND, VALIDATE};
+    use crate::config::problem_type::{
+        CSRF, DUPLICATE, EMAIL_ALREADY_VERIFIED, INVALID_VERIFICATION_TOKEN, NOT_FOUND,
+        TOO_MANY_REQUESTS, VALIDATE,
+    };
 
     use super::*;
-
-    async fn connect() -> anyhow::Result<sqlx::PgPool, anyhow::Error> {
-        dotenv::dotenv().ok();
-
-        let database_url =
-            std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
-        let pool = sqlx::postgres::PgPoolOptions::new()
-            .max_connections(5)
-            .connect(&database_url)
-            .await?;
-
-        Ok(pool)
-    }
+    use crate::test_app::TestApp;
 
     #[tokio::test]
     async fn test_create_user() -> anyhow::Result<()> {
         let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
-        let pool = connect().await.expect("database should connect");
-        let state = AppState {
-            user_repository: UserRepositoryWithPg::new(pool.clone()),
-            user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
-        };
+        let test_app = TestApp::spawn().await;
 
-        let app = router().with_state(state);
+        let app = test_app.router();
 
         let response = app
             .oneshot(
                 axum::http::Request::builder()
                     .method("POST")
-                    .uri("/users")
+                    .uri("/v1/users")
                     .header(CONTENT_TYPE, "application/json")
                     .body(axum::body::Body::new(serde_json::to_string(
                         &CreateUserRequestBody {
@@ -179,14 +387,79 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_find_all_users() -> anyhow::Result<()> {
-        let pool = connect().await?;
-        let state = AppState {
-            user_repository: UserRepositoryWithPg::new(pool.clone()),
-            user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
+    async fn test_create_user_in_msgpack_and_find_it_in_cbor() -> anyhow::Result<()> {
+        use crate::config::problem_type::NOT_ACCEPTABLE;
+        use application::request_response::find_user_by_id_response::FindUserByIdResponseBody;
+        use axum::http::header::{ACCEPT, VARY};
+
+        let test_app = TestApp::spawn().await;
+        let app = test_app.router();
+        let create = |email: &str, accept: &str| -> anyhow::Result<_> {
+            let body = rmp_serde::to_vec_named(&CreateUserRequestBody {
+                name: "Test User".to_owned(),
+                email: email.to_owned(),
+            })?;
+            Ok(axum::http::Request::builder()
+                .method("POST")
+                .uri("/v1/users")
+                .header(CONTENT_TYPE, "application/msgpack")
+                .header(ACCEPT, accept)
+                .body(axum::body::Body::from(body))?)
         };
 
-        let app = router().with_state(state);
+        let created = app
+            .clone()
+            .oneshot(create("msgpack@example.com", "application/msgpack")?)
+            .await?;
+        assert_eq!(created.status(), StatusCode::CREATED);
+        assert_eq!(created.headers()[CONTENT_TYPE], "application/msgpack");
+        let created = rmp_serde::from_slice::<CreateUserResponseBody>(
+            &axum::body::to_bytes(created.into_body(), usize::MAX).await?,
+        )?;
+        let found = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri(format!("/v1/users/{}", created.id))
+                    .header(ACCEPT, "application/json;q=0.5, application/cbor")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+        assert_eq!(found.headers()[CONTENT_TYPE], "application/cbor");
+        assert_eq!(found.headers()[VARY], "accept");
+        let found = ciborium::from_reader::<FindUserByIdResponseBody, _>(
+            &axum::body::to_bytes(found.into_body(), usize::MAX).await?[..],
+        )?;
+        let not_acceptable = app
+            .clone()
+            .oneshot(create("xml@example.com", "application/xml")?)
+            .await?;
+
+        assert_eq!(found.email, "msgpack@example.com");
+        assert_eq!(not_acceptable.status(), StatusCode::NOT_ACCEPTABLE);
+        let problem: serde_json::Value = serde_json::from_slice(
+            &axum::body::to_bytes(not_acceptable.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(problem["type"], NOT_ACCEPTABLE);
+        assert_eq!(problem["instance"], "/v1/users");
+        // Rejected before the handler ran, so nothing was written.
+        let emails: Vec<String> = test_app
+            .state
+            .user_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|user| user.email)
+            .collect();
+        assert_eq!(emails, ["msgpack@example.com"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_all_users() -> anyhow::Result<()> {
+        let test_app = TestApp::spawn().await;
+
+        let app = test_app.router();
 
         let users_to_create = vec![
             (
@@ -213,7 +486,7 @@ mod tests {
                 .oneshot(
                     axum::http::Request::builder()
                         .method("POST")
-                        .uri("/users")
+                        .uri("/v1/users")
                         .header(CONTENT_TYPE, "application/json")
                         .body(axum::body::Body::from(serde_json::to_string(
                             &request_body,
@@ -233,7 +506,7 @@ mod tests {
             .oneshot(
                 axum::http::Request::builder()
                     .method("GET")
-                    .uri("/users")
+                    .uri("/v1/users")
                     .body(axum::body::Body::empty())?,
             )
             .await?;
@@ -243,29 +516,22 @@ mod tests {
         let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
         let users: Vec<CreateUserResponseBody> = serde_json::from_slice(&body)?;
 
-        for (expected_name, expected_email) in &users_to_create {
-            assert!(
-                users
-                    .iter()
-                    .any(|u| &u.name == expected_name && &u.email == expected_email),
-                "Expected user {} <{}> not found in response",
-                expected_name,
-                expected_email
-            );
-        }
+        assert_eq!(
+            users
+                .iter()
+                .map(|u| (u.name.as_str(), u.email.clone()))
+                .collect::<Vec<_>>(),
+            users_to_create
+        );
 
         Ok(())
     }
 
     #[tokio::test]
     async fn test_find_user_by_id() -> anyhow::Result<()> {
-        let pool = connect().await.expect("database should connect");
-        let state = AppState {
-            user_repository: UserRepositoryWithPg::new(pool.clone()),
-            user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
-        };
+        let test_app = TestApp::spawn().await;
 
-        let app = router().with_state(state);
+        let app = test_app.router();
 
         let name = "Test User";
         let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
@@ -279,7 +545,7 @@ mod tests {
             .oneshot(
                 axum::http::Request::builder()
                     .method("POST")
-                    .uri("/users")
+                    .uri("/v1/users")
                     .header(CONTENT_TYPE, "application/json")
                     .body(axum::body::Body::new(serde_json::to_string(&request_body)?))?,
             )
@@ -293,7 +559,7 @@ mod tests {
             .oneshot(
                 axum::http::Request::builder()
                     .method("GET")
-                    .uri(&format!("/users/{}", created_user.id))
+                    .uri(format!("/v1/users/{}", created_user.id))
                     .body(axum::body::Body::empty())?,
             )
             .await?;
@@ -311,18 +577,14 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_user_422() {
-        let pool = connect().await.unwrap();
-        let state = AppState {
-            user_repository: UserRepositoryWithPg::new(pool.clone()),
-            user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
-        };
-        let app = router().with_state(state);
+        let test_app = TestApp::spawn().await;
+        let app = test_app.router();
 
         let response = app
             .oneshot(
                 axum::http::Request::builder()
                     .method("POST")
-                    .uri("/users")
+                    .uri("/v1/users")
                     .header(CONTENT_TYPE, "application/json")
                     .body(axum::body::Body::from(r#"{}"#))
                     .unwrap(),
@@ -338,22 +600,55 @@ mod tests {
 
         assert_eq!(problem["title"], "Invalid JSON");
         assert_eq!(problem["type"], INVALID_JSON);
+        assert_eq!(problem["instance"], "/v1/users");
+    }
+
+    #[tokio::test]
+    async fn test_update_user_422_names_the_request_path() -> anyhow::Result<()> {
+        use crate::config::auth::AuthConfig;
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+        let app = test_app.router();
+        let uri = format!("/v1/users/{}", uuid::Uuid::new_v4());
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("PUT")
+                    .uri(&uri)
+                    .header(CONTENT_TYPE, "application/json")
+                    .header(axum::http::header::AUTHORIZATION, "Bearer admin-secret")
+                    .body(axum::body::Body::from(r#"{}"#))?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let problem = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(problem["type"], INVALID_JSON);
+        assert_eq!(problem["instance"], uri);
+
+        Ok(())
     }
 
     #[tokio::test]
     async fn test_create_user_405() {
-        let pool = connect().await.unwrap();
-        let state = AppState {
-            user_repository: UserRepositoryWithPg::new(pool.clone()),
-            user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
-        };
-        let app = router().with_state(state);
+        let test_app = TestApp::spawn().await;
+        let app = test_app.router();
 
         let response = app
             .oneshot(
                 axum::http::Request::builder()
                     .method("PUT")
-                    .uri("/users")
+                    .uri("/v1/users")
                     .header(CONTENT_TYPE, "application/json")
                     .body(axum::body::Body::from(r#"{}"#))
                     .unwrap(),
@@ -373,18 +668,14 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_user_validation_failed() -> anyhow::Result<()> {
-        let pool = connect().await.expect("database should connect");
-        let state = AppState {
-            user_repository: UserRepositoryWithPg::new(pool.clone()),
-            user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
-        };
+        let test_app = TestApp::spawn().await;
 
-        let app = router().with_state(state);
+        let app = test_app.router();
         let response = app
             .oneshot(
                 axum::http::Request::builder()
                     .method("POST")
-                    .uri("/users")
+                    .uri("/v1/users")
                     .header(CONTENT_TYPE, "application/json")
                     .body(axum::body::Body::new(serde_json::to_string(
                         &CreateUserRequestBody {
@@ -407,20 +698,16 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_user_email_duplicate_failed() -> anyhow::Result<()> {
-        let pool = connect().await.expect("database should connect");
+        let test_app = TestApp::spawn().await;
         let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
         let user = User::new("Test User".into(), email.clone());
-        let state = AppState {
-            user_repository: UserRepositoryWithPg::new(pool.clone()),
-            user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
-        };
 
-        let app = router().with_state(state);
+        let app = test_app.router();
         app.clone()
             .oneshot(
                 axum::http::Request::builder()
                     .method("POST")
-                    .uri("/users")
+                    .uri("/v1/users")
                     .header(CONTENT_TYPE, "application/json")
                     .body(axum::body::Body::new(serde_json::to_string(
                         &CreateUserRequestBody {
@@ -435,7 +722,7 @@ mod tests {
             .oneshot(
                 axum::http::Request::builder()
                     .method("POST")
-                    .uri("/users")
+                    .uri("/v1/users")
                     .header(CONTENT_TYPE, "application/json")
                     .body(axum::body::Body::new(serde_json::to_string(
                         &CreateUserRequestBody {
@@ -458,18 +745,14 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_user_415() {
-        let pool = connect().await.unwrap();
-        let state = AppState {
-            user_repository: UserRepositoryWithPg::new(pool.clone()),
-            user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
-        };
-        let app = router().with_state(state);
+        let test_app = TestApp::spawn().await;
+        let app = test_app.router();
 
         let response = app
             .oneshot(
                 axum::http::Request::builder()
                     .method("POST")
-                    .uri("/users")
+                    .uri("/v1/users")
                     .header(CONTENT_TYPE, "text/plain")
                     .body(axum::body::Body::from(r#"{}"#))
                     .unwrap(),
@@ -489,19 +772,15 @@ mod tests {
 
     #[tokio::test]
     async fn test_find_user_by_id_400() -> anyhow::Result<()> {
-        let pool = connect().await.expect("database should connect");
-        let state = AppState {
-            user_repository: UserRepositoryWithPg::new(pool.clone()),
-            user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
-        };
+        let test_app = TestApp::spawn().await;
 
-        let app = router().with_state(state);
+        let app = test_app.router();
 
         let response = app
             .oneshot(
                 axum::http::Request::builder()
                     .method("GET")
-                    .uri("/users/not-a-uuid")
+                    .uri("/v1/users/not-a-uuid")
                     .body(axum::body::Body::empty())?,
             )
             .await?;
@@ -519,13 +798,9 @@ mod tests {
 
     #[tokio::test]
     async fn test_find_user_by_id_404() -> anyhow::Result<()> {
-        let pool = connect().await.expect("database should connect");
-        let state = AppState {
-            user_repository: UserRepositoryWithPg::new(pool.clone()),
-            user_email_duplicate_validator: UserEmailDuplicateValidatorWithPg::new(pool.clone()),
-        };
+        let test_app = TestApp::spawn().await;
 
-        let app = router().with_state(state);
+        let app = test_app.router();
 
         let non_existing_id = uuid::Uuid::new_v4();
 
@@ -533,7 +808,7 @@ mod tests {
             .oneshot(
                 axum::http::Request::builder()
                     .method("GET")
-                    .uri(&format!("/users/{}", non_existing_id))
+                    .uri(format!("/v1/users/{}", non_existing_id))
                     .body(axum::body::Body::empty())?,
             )
             .await?;
@@ -548,4 +823,1874 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_metrics_count_requests_and_problems() -> anyhow::Result<()> {
+        use crate::config::auth::AuthConfig;
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+
+        let app = test_app.router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/v1/users/not-a-uuid")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let problem: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(problem["type"], BAD_REQUEST);
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/metrics")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/metrics")
+                    .header(axum::http::header::AUTHORIZATION, "Bearer admin-secret")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let body = String::from_utf8(body.to_vec())?;
+
+        assert!(
+            body.contains(
+                r#"http_requests_total{method="GET",route="/v1/users/{id}",status="400"} 1"#
+            )
+        );
+        assert!(body.contains(&format!(
+            r#"http_problems_total{{status="400",type="{}"}} 1"#,
+            BAD_REQUEST
+        )));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_id_is_added_to_problem_response() -> anyhow::Result<()> {
+        let test_app = TestApp::spawn().await;
+
+        let app = test_app.router();
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/v1/users/not-a-uuid")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let request_id = response
+            .headers()
+            .get("x-request-id")
+            .expect("response should carry a request id")
+            .to_str()?
+            .to_owned();
+        assert!(uuid::Uuid::parse_str(&request_id).is_ok());
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let problem: serde_json::Value = serde_json::from_slice(&body)?;
+
+        assert_eq!(problem["type"], BAD_REQUEST);
+        assert_eq!(problem["request_id"], request_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_id_is_propagated_from_request() -> anyhow::Result<()> {
+        let test_app = TestApp::spawn().await;
+
+        let app = test_app.router();
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/v1/users")
+                    .header("x-request-id", "front-7f3a")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-request-id"], "front-7f3a");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_spans_continue_incoming_trace() -> anyhow::Result<()> {
+        use opentelemetry::trace::TracerProvider;
+        use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+        crate::config::telemetry::install_propagator();
+
+        let test_app = TestApp::spawn().await;
+
+        let app = test_app.router();
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri(format!("/v1/users/{}", uuid::Uuid::new_v4()))
+                    .header(
+                        "traceparent",
+                        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+                    )
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        provider.force_flush()?;
+        let spans = exporter.get_finished_spans()?;
+        for expected in [
+            "GET /v1/users/{id}",
+            "handle_find_user_by_id",
+            "FindUserByIdUsecase::execute",
+            "UserRepositoryWithPg::find_by_id",
+        ] {
+            let span = spans
+                .iter()
+                .find(|span| span.name == expected)
+                .unwrap_or_else(|| panic!("span {} should be exported", expected));
+            assert_eq!(
+                span.span_context.trace_id().to_string(),
+                "4bf92f3577b34da6a3ce929d0e0e4736"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight_from_allowed_origin() -> anyhow::Result<()> {
+        let test_app = TestApp::spawn().await;
+
+        let app = test_app.router();
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("OPTIONS")
+                    .uri("/v1/users")
+                    .header("origin", "http://localhost:3000")
+                    .header("access-control-request-method", "POST")
+                    .header("access-control-request-headers", "content-type")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(
+            headers["access-control-allow-origin"],
+            "http://localhost:3000"
+        );
+        assert_eq!(headers["access-control-allow-credentials"], "true");
+        assert!(
+            headers["access-control-allow-methods"]
+                .to_str()?
+                .contains("POST")
+        );
+        assert!(headers.get(CONTENT_TYPE).is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cors_exposes_headers_only_to_allowed_origin() -> anyhow::Result<()> {
+        let test_app = TestApp::spawn().await;
+
+        let app = test_app.router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/v1/users")
+                    .header("origin", "http://localhost:3000")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let exposed = response.headers()["access-control-expose-headers"].to_str()?;
+        for header in [
+            "content-disposition",
+            "deprecation",
+            "link",
+            "location",
+            "retry-after",
+            "sunset",
+            "x-request-id",
+        ] {
+            assert!(exposed.contains(header), "{} should be exposed", header);
+        }
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/v1/users")
+                    .header("origin", "https://evil.example.com")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert!(
+            response
+                .headers()
+                .get("access-control-allow-origin")
+                .is_none()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_user_with_cookie_requires_csrf_token() -> anyhow::Result<()> {
+        let test_app = TestApp::spawn().await;
+
+        let app = test_app.router();
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri("/v1/users")
+                    .header(CONTENT_TYPE, "application/json")
+                    .header("cookie", "session=abc")
+                    .body(axum::body::Body::new(serde_json::to_string(
+                        &CreateUserRequestBody {
+                            name: "Test User".to_string(),
+                            email: format!("test+{}@example.com", uuid::Uuid::new_v4()),
+                        },
+                    )?))?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let problem: serde_json::Value = serde_json::from_slice(&body)?;
+
+        assert_eq!(problem["title"], "CSRF Validation Failed");
+        assert_eq!(problem["type"], CSRF);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_user_from_untrusted_origin_fails() -> anyhow::Result<()> {
+        let test_app = TestApp::spawn().await;
+
+        let app = test_app.router();
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri("/v1/users")
+                    .header(CONTENT_TYPE, "application/json")
+                    .header("origin", "https://evil.example.com")
+                    .body(axum::body::Body::new(serde_json::to_string(
+                        &CreateUserRequestBody {
+                            name: "Test User".to_string(),
+                            email: format!("test+{}@example.com", uuid::Uuid::new_v4()),
+                        },
+                    )?))?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let problem: serde_json::Value = serde_json::from_slice(&body)?;
+
+        assert_eq!(problem["type"], CSRF);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_user_with_issued_csrf_token() -> anyhow::Result<()> {
+        use application::request_response::csrf_token_response::CsrfTokenResponseBody;
+
+        let test_app = TestApp::spawn().await;
+
+        let app = test_app.router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/v1/auth/csrf")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let set_cookie = response.headers()["set-cookie"].to_str()?.to_owned();
+        assert!(set_cookie.contains("HttpOnly"));
+        assert!(set_cookie.contains("SameSite=Lax"));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let CsrfTokenResponseBody { token } = serde_json::from_slice(&body)?;
+        assert!(set_cookie.starts_with(&format!("csrf_token={}", token)));
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri("/v1/users")
+                    .header(CONTENT_TYPE, "application/json")
+                    .header("origin", "http://localhost:3000")
+                    .header("cookie", format!("session=abc; csrf_token={}", token))
+                    .header("x-csrf-token", &token)
+                    .body(axum::body::Body::new(serde_json::to_string(
+                        &CreateUserRequestBody {
+                            name: "Test User".to_string(),
+                            email: format!("test+{}@example.com", uuid::Uuid::new_v4()),
+                        },
+                    )?))?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_user_is_rate_limited_by_email() -> anyhow::Result<()> {
+        use crate::config::rate_limit::{RateLimitConfig, parse_route_rate_limits};
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            rate_limit: RateLimitConfig {
+                routes: parse_route_rate_limits("POST /v1/users email=1/3600")
+                    .map_err(anyhow::Error::msg)?,
+                trust_forwarded_for: false,
+                trusted_proxies: Vec::new(),
+            },
+            ..AppConfig::default()
+        })
+        .await;
+
+        let app = test_app.router();
+        let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
+
+        let mut statuses = Vec::new();
+        for candidate in [email.clone(), email.to_uppercase()] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("POST")
+                        .uri("/v1/users")
+                        .header(CONTENT_TYPE, "application/json")
+                        .body(axum::body::Body::new(serde_json::to_string(
+                            &CreateUserRequestBody {
+                                name: "Test User".to_string(),
+                                email: candidate,
+                            },
+                        )?))?,
+                )
+                .await?;
+            statuses.push(response.status());
+
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                let retry_after: u64 = response.headers()["retry-after"].to_str()?.parse()?;
+                assert!(retry_after > 0 && retry_after <= 3600);
+
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+                let problem: serde_json::Value = serde_json::from_slice(&body)?;
+                assert_eq!(problem["title"], "Too Many Requests");
+                assert_eq!(problem["type"], TOO_MANY_REQUESTS);
+            }
+        }
+
+        assert_eq!(
+            statuses,
+            vec![StatusCode::CREATED, StatusCode::TOO_MANY_REQUESTS]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_user_in_msgpack_or_cbor_is_rate_limited_by_email() -> anyhow::Result<()> {
+        use crate::config::rate_limit::{RateLimitConfig, parse_route_rate_limits};
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            rate_limit: RateLimitConfig {
+                routes: parse_route_rate_limits("POST /v1/users email=3/3600")
+                    .map_err(anyhow::Error::msg)?,
+                trust_forwarded_for: false,
+                trusted_proxies: Vec::new(),
+            },
+            ..AppConfig::default()
+        })
+        .await;
+
+        let app = test_app.router();
+        let body = CreateUserRequestBody {
+            name: "Test User".to_string(),
+            email: format!("test+{}@example.com", uuid::Uuid::new_v4()),
+        };
+        let msgpack = rmp_serde::to_vec_named(&body)?;
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&body, &mut cbor)?;
+
+        let mut statuses = Vec::new();
+        for (content_type, body) in [
+            ("application/msgpack", &msgpack),
+            ("application/cbor", &cbor),
+            ("application/msgpack", &msgpack),
+            ("application/cbor", &cbor),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("POST")
+                        .uri("/v1/users")
+                        .header(CONTENT_TYPE, content_type)
+                        .body(axum::body::Body::from(body.clone()))?,
+                )
+                .await?;
+            statuses.push(response.status());
+        }
+
+        assert_eq!(
+            statuses,
+            vec![
+                StatusCode::CREATED,
+                StatusCode::CONFLICT,
+                StatusCode::CONFLICT,
+                StatusCode::TOO_MANY_REQUESTS
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_user_is_rate_limited_by_forwarded_ip() -> anyhow::Result<()> {
+        use crate::config::rate_limit::{RateLimitConfig, parse_route_rate_limits};
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            rate_limit: RateLimitConfig {
+                routes: parse_route_rate_limits("POST /v1/users ip=1/60")
+                    .map_err(anyhow::Error::msg)?,
+                trust_forwarded_for: true,
+                trusted_proxies: Vec::new(),
+            },
+            ..AppConfig::default()
+        })
+        .await;
+
+        let app = test_app.router();
+
+        let mut statuses = Vec::new();
+        for ip in ["203.0.113.7", "203.0.113.7", "198.51.100.2"] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("POST")
+                        .uri("/v1/users")
+                        .header(CONTENT_TYPE, "application/json")
+                        .header("x-forwarded-for", ip)
+                        .body(axum::body::Body::new(serde_json::to_string(
+                            &CreateUserRequestBody {
+                                name: "Test User".to_string(),
+                                email: format!("test+{}@example.com", uuid::Uuid::new_v4()),
+                            },
+                        )?))?,
+                )
+                .await?;
+            statuses.push(response.status());
+        }
+
+        assert_eq!(
+            statuses,
+            vec![
+                StatusCode::CREATED,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::CREATED
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_user_without_client_ip_skips_the_ip_limit() -> anyhow::Result<()> {
+        use crate::config::rate_limit::{RateLimitConfig, parse_route_rate_limits};
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            rate_limit: RateLimitConfig {
+                routes: parse_route_rate_limits("POST /v1/users ip=1/60")
+                    .map_err(anyhow::Error::msg)?,
+                trust_forwarded_for: false,
+                trusted_proxies: Vec::new(),
+            },
+            ..AppConfig::default()
+        })
+        .await;
+
+        let app = test_app.router();
+
+        let mut statuses = Vec::new();
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("POST")
+                        .uri("/v1/users")
+                        .header(CONTENT_TYPE, "application/json")
+                        .body(axum::body::Body::new(serde_json::to_string(
+                            &CreateUserRequestBody {
+                                name: "Test User".to_string(),
+                                email: format!("test+{}@example.com", uuid::Uuid::new_v4()),
+                            },
+                        )?))?,
+                )
+                .await?;
+            statuses.push(response.status());
+        }
+
+        assert_eq!(statuses, vec![StatusCode::CREATED, StatusCode::CREATED]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_email() -> anyhow::Result<()> {
+        use crate::config::auth::AuthConfig;
+        use application::request_response::{
+            find_user_by_id_response::FindUserByIdResponseBody,
+            verify_email_response::VerifyEmailResponseBody,
+        };
+        use domain::{
+            entity::email_verification_token::EmailVerificationToken,
+            interface::{
+                email_verification_token_repository_interface::EmailVerificationTokenRepositoryInterface,
+                user_repository_interface::UserRepositoryInterface,
+            },
+        };
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+        let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
+        let user = test_app
+            .state
+            .user_repository
+            .create(&User::new("Test User".into(), email))
+            .await?;
+        let (token, raw_token) = EmailVerificationToken::issue(
+            user.id.clone(),
+            chrono::Utc::now(),
+            chrono::Duration::hours(1),
+        );
+        test_app
+            .state
+            .email_verification_token_repository
+            .create(&token)
+            .await?;
+
+        let app = test_app.router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri(format!("/v1/auth/verify-email?token={}", raw_token))
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["cache-control"], "no-store");
+        let response_body = serde_json::from_slice::<VerifyEmailResponseBody>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(response_body.id, user.id.to_string());
+        assert!(response_body.email_verified_at.is_some());
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri(format!("/v1/users/{}", user.id))
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+        let found_user = serde_json::from_slice::<FindUserByIdResponseBody>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(found_user.email_verified_at, response_body.email_verified_at);
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri(format!("/v1/auth/verify-email?token={}", raw_token))
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let problem = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(problem["type"], INVALID_VERIFICATION_TOKEN);
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/users/{}/verification", user.id))
+                    .header(axum::http::header::AUTHORIZATION, "Bearer admin-secret")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let problem = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(problem["type"], EMAIL_ALREADY_VERIFIED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_issue_email_verification() -> anyhow::Result<()> {
+        use crate::config::auth::AuthConfig;
+        use domain::interface::user_repository_interface::UserRepositoryInterface;
+        use infrastructure::mailer::in_memory_mailer::InMemoryMailer;
+
+        let mailer = InMemoryMailer::new();
+        let mut test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+        test_app.state.mailer = Arc::new(mailer.clone());
+        let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
+        let user = test_app
+            .state
+            .user_repository
+            .create(&User::new("Test User".into(), email.clone()))
+            .await?;
+
+        let app = test_app.router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/users/{}/verification", user.id))
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(mailer.sent().is_empty());
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/users/{}/verification", user.id))
+                    .header(axum::http::header::AUTHORIZATION, "Bearer admin-secret")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let sent = mailer.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, email);
+        let link = sent[0]
+            .text_body
+            .lines()
+            .find(|line| line.starts_with("http://localhost:8080/v1/auth/verify-email?token="))
+            .expect("mail should contain the verification link");
+        let path = link.trim_start_matches("http://localhost:8080");
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri(path)
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/users/{}/verification", uuid::Uuid::new_v4()))
+                    .header(axum::http::header::AUTHORIZATION, "Bearer admin-secret")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_email_with_unknown_token() -> anyhow::Result<()> {
+        let test_app = TestApp::spawn().await;
+
+        let app = test_app.router();
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/v1/auth/verify-email?token=unknown")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let problem = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(problem["type"], INVALID_VERIFICATION_TOKEN);
+        assert!(problem.get("token").is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_audit_events_requires_admin_token() -> anyhow::Result<()> {
+        use crate::config::{auth::AuthConfig, problem_type::{FORBIDDEN, UNAUTHORIZED}};
+
+        let disabled_app = TestApp::spawn().await;
+        let enabled_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+        let disabled = disabled_app.router();
+        let enabled = enabled_app.router();
+
+        let mut results = Vec::new();
+        for (app, authorization) in [
+            (disabled, Some("Bearer admin-secret")),
+            (enabled.clone(), None),
+            (enabled, Some("Bearer wrong-secret")),
+        ] {
+            let mut request = axum::http::Request::builder()
+                .method("GET")
+                .uri("/v1/audit-events");
+            if let Some(authorization) = authorization {
+                request = request.header(axum::http::header::AUTHORIZATION, authorization);
+            }
+            let response = app.oneshot(request.body(axum::body::Body::empty())?).await?;
+            let status = response.status();
+            let problem = serde_json::from_slice::<serde_json::Value>(
+                &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+            )?;
+            results.push((status, problem["type"].as_str().unwrap_or_default().to_owned()));
+        }
+
+        assert_eq!(
+            results,
+            vec![
+                (StatusCode::FORBIDDEN, FORBIDDEN.to_owned()),
+                (StatusCode::UNAUTHORIZED, UNAUTHORIZED.to_owned()),
+                (StatusCode::UNAUTHORIZED, UNAUTHORIZED.to_owned()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_user_is_recorded_in_audit_log() -> anyhow::Result<()> {
+        use crate::config::auth::AuthConfig;
+        use axum::extract::ConnectInfo;
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+
+        let app = test_app.router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri("/v1/users")
+                    .header(CONTENT_TYPE, "application/json")
+                    .header(axum::http::header::USER_AGENT, "audit-test/1.0")
+                    .extension(ConnectInfo(SocketAddr::from(([203, 0, 113, 7], 4000))))
+                    .body(axum::body::Body::new(serde_json::to_string(
+                        &CreateUserRequestBody {
+                            name: "Test User".to_string(),
+                            email: format!("test+{}@example.com", uuid::Uuid::new_v4()),
+                        },
+                    )?))?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let created = serde_json::from_slice::<CreateUserResponseBody>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/v1/audit-events")
+                    .header(axum::http::header::AUTHORIZATION, "Bearer admin-secret")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        let items = body["items"].as_array().expect("items should be an array");
+        assert_eq!(
+            items
+                .iter()
+                .map(|item| (
+                    item["action"].as_str().unwrap(),
+                    item["subject_id"].as_str().unwrap(),
+                    item["actor_id"].clone(),
+                    item["ip"].as_str().unwrap(),
+                    item["user_agent"].as_str().unwrap(),
+                ))
+                .collect::<Vec<_>>(),
+            vec![(
+                "user.created",
+                created.id.as_str(),
+                serde_json::Value::Null,
+                "203.0.113.7",
+                "audit-test/1.0",
+            )]
+        );
+        assert_eq!(body["next_cursor"], serde_json::Value::Null);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_audit_events_with_unknown_action_fails() -> anyhow::Result<()> {
+        use crate::config::auth::AuthConfig;
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+
+        let app = test_app.router();
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/v1/audit-events?action=user.renamed")
+                    .header(axum::http::header::AUTHORIZATION, "Bearer admin-secret")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let problem = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(problem["type"], VALIDATE);
+        assert_eq!(problem["action"], serde_json::json!(["Unknown audit action"]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_state_sends_the_verification_mail() -> anyhow::Result<()> {
+        use crate::config::mailer::MailerConfig;
+
+        let dir = std::env::temp_dir().join(format!("in-memory-mail-{}", uuid::Uuid::new_v4()));
+        let app = router(AppState::in_memory(AppConfig {
+            mailer: MailerConfig::File {
+                dir: dir.clone(),
+                from: "no-reply@example.com".to_owned(),
+            },
+            ..AppConfig::default()
+        }));
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri("/v1/users")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(axum::body::Body::new(serde_json::to_string(
+                        &CreateUserRequestBody {
+                            name: "Mailed User".to_string(),
+                            email: "mailed@example.com".to_string(),
+                        },
+                    )?))?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let mails = std::fs::read_dir(&dir)?.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(mails.len(), 1);
+        let mail = std::fs::read_to_string(mails[0].path())?;
+        assert!(mail.contains("To: mailed@example.com"));
+        std::fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_state_serves_users() -> anyhow::Result<()> {
+        let app = router(AppState::in_memory(AppConfig::default()));
+
+        let mut statuses = Vec::new();
+        for (name, email) in [
+            ("Bob", "bob@example.com"),
+            ("Alice", "alice@example.com"),
+            ("Alice Again", "alice@example.com"),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("POST")
+                        .uri("/v1/users")
+                        .header(CONTENT_TYPE, "application/json")
+                        .body(axum::body::Body::new(serde_json::to_string(
+                            &CreateUserRequestBody {
+                                name: name.to_string(),
+                                email: email.to_string(),
+                            },
+                        )?))?,
+                )
+                .await?;
+            statuses.push(response.status());
+        }
+        assert_eq!(
+            statuses,
+            vec![StatusCode::CREATED, StatusCode::CREATED, StatusCode::CONFLICT]
+        );
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/v1/users")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        let users: Vec<(String, String)> = body
+            .as_array()
+            .expect("body should be an array")
+            .iter()
+            .map(|user| {
+                (
+                    user["name"].as_str().unwrap().to_owned(),
+                    user["email"].as_str().unwrap().to_owned(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            users,
+            vec![
+                ("Alice".to_owned(), "alice@example.com".to_owned()),
+                ("Bob".to_owned(), "bob@example.com".to_owned()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_state_find_user_by_id_404() -> anyhow::Result<()> {
+        let app = router(AppState::in_memory(AppConfig::default()));
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri(format!("/v1/users/{}", uuid::Uuid::new_v4()))
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let problem = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(problem["type"], NOT_FOUND);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_openapi_document_lists_every_route() -> anyhow::Result<()> {
+        let app = router(AppState::in_memory(AppConfig::default()));
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri("/openapi.json")
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let doc = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+        )?;
+        let mut operations = Vec::new();
+        for (path, item) in doc["paths"].as_object().expect("paths should be an object") {
+            for method in item.as_object().expect("path item should be an object").keys() {
+                operations.push(format!("{} {}", method, path));
+            }
+        }
+        operations.sort();
+        assert_eq!(
+            operations,
+            vec![
+                "delete /v1/users/{id}",
+                "get /metrics",
+                "get /v1/audit-events",
+                "get /v1/auth/csrf",
+                "get /v1/auth/verify-email",
+                "get /v1/users",
+                "get /v1/users/export",
+                "get /v1/users/{id}",
+                "post /v1/users",
+                "post /v1/users/{id}/verification",
+                "post /v1/users:import",
+                "put /v1/users/{id}",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_api_reference_is_served_only_when_enabled() -> anyhow::Result<()> {
+        use crate::config::openapi::OpenApiConfig;
+
+        let mut statuses = Vec::new();
+        for ui_enabled in [false, true] {
+            let app = router(AppState::in_memory(AppConfig {
+                openapi: OpenApiConfig { ui_enabled },
+                ..AppConfig::default()
+            }));
+            let response = app
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("GET")
+                        .uri("/docs")
+                        .body(axum::body::Body::empty())?,
+                )
+                .await?;
+            statuses.push(response.status());
+        }
+
+        assert_eq!(statuses, vec![StatusCode::NOT_FOUND, StatusCode::OK]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_round_trips_against_router() -> anyhow::Result<()> {
+        use client::{client::Client, error::ProblemKind};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let app = router(AppState::in_memory(AppConfig::default()));
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+        let client = Client::new(format!("http://{}", address))?;
+
+        let created = client
+            .create_user(&CreateUserRequestBody {
+                name: "Client User".to_owned(),
+                email: "client@example.com".to_owned(),
+            })
+            .await?;
+        let duplicate = client
+            .create_user(&CreateUserRequestBody {
+                name: "Client Duplicate".to_owned(),
+                email: "client@example.com".to_owned(),
+            })
+            .await
+            .expect_err("a duplicate email should be rejected");
+        let missing = client
+            .find_user_by_id(uuid::Uuid::new_v4())
+            .await
+            .expect_err("an unknown user should not be found");
+        let found = client.find_user_by_id(created.id.parse()?).await?;
+        let all = client.find_all_users().await?;
+
+        assert_eq!(found.body.email, created.email);
+        assert!(found.etag.is_some());
+        assert_eq!(
+            all.0.into_iter().map(|user| user.id).collect::<Vec<_>>(),
+            vec![created.id]
+        );
+        assert_eq!(
+            (duplicate.problem_kind(), missing.problem_kind()),
+            (Some(ProblemKind::Duplicate), Some(ProblemKind::NotFound))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_sends_the_admin_token_to_guarded_routes() -> anyhow::Result<()> {
+        use application::request_response::{
+            export_users_request::ExportUsersFormat,
+            find_audit_events_request::FindAuditEventsRequestQuery,
+            import_users_request::ImportUsersMode, update_user_request::UpdateUserRequestBody,
+        };
+        use client::{client::Client, error::ProblemKind};
+        use domain::entity::request_context::ADMIN_TOKEN_ACTOR_ID;
+
+        use crate::config::auth::AuthConfig;
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+        let anonymous = Client::new(format!("http://{}", test_app.serve().await))?;
+        let admin = anonymous.clone().with_admin_token("admin-secret");
+        let update = UpdateUserRequestBody {
+            name: "Renamed User".to_owned(),
+            email: "renamed@example.com".to_owned(),
+        };
+
+        let imported = admin
+            .import_users(
+                &[CreateUserRequestBody {
+                    name: "Imported User".to_owned(),
+                    email: "imported@example.com".to_owned(),
+                }],
+                ImportUsersMode::Transaction,
+            )
+            .await?;
+        let id: uuid::Uuid = imported.rows[0]
+            .id
+            .as_deref()
+            .expect("the row should be created")
+            .parse()?;
+        let rejected = [
+            anonymous.update_user(id, &update, None).await.err(),
+            anonymous.delete_user(id, None).await.err(),
+            anonymous
+                .import_users(&[], ImportUsersMode::Transaction)
+                .await
+                .err(),
+            anonymous.export_users(ExportUsersFormat::Csv).await.err(),
+            anonymous.issue_email_verification(id).await.err(),
+            anonymous
+                .find_audit_events(&FindAuditEventsRequestQuery::default())
+                .await
+                .err(),
+            anonymous.metrics().await.err(),
+        ]
+        .map(|error| error.and_then(|error| error.problem_kind()));
+
+        let found = anonymous.find_user_by_id(id).await?;
+        let updated = admin
+            .update_user(id, &update, found.etag.as_deref())
+            .await?;
+        admin.issue_email_verification(id).await?;
+        let csv = admin.export_users(ExportUsersFormat::Csv).await?;
+        admin.delete_user(id, updated.etag.as_deref()).await?;
+        let events = admin
+            .find_audit_events(&FindAuditEventsRequestQuery::default())
+            .await?;
+        let metrics = admin.metrics().await?;
+
+        assert_eq!(rejected, [Some(ProblemKind::Unauthorized); 7]);
+        assert!(metrics.contains("http_requests_total"));
+        assert_eq!(updated.body.email, update.email);
+        assert!(csv.contains("renamed@example.com"));
+        let admin_id = ADMIN_TOKEN_ACTOR_ID.to_string();
+        assert_eq!(
+            events
+                .items
+                .iter()
+                .map(|event| (event.action.as_str(), event.actor_id.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("user.deleted", Some(admin_id.as_str())),
+                ("user.updated", Some(admin_id.as_str())),
+                ("user.created", Some(admin_id.as_str())),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_routes_are_served_under_their_version_prefix() -> anyhow::Result<()> {
+        let app = router(AppState::in_memory(AppConfig::default()));
+
+        let mut statuses = Vec::new();
+        for uri in ["/v1/users", "/users"] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("GET")
+                        .uri(uri)
+                        .body(axum::body::Body::empty())?,
+                )
+                .await?;
+            statuses.push(response.status());
+        }
+
+        assert_eq!(statuses, vec![StatusCode::OK, StatusCode::NOT_FOUND]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_deprecated_route_announces_deprecation_and_sunset() -> anyhow::Result<()> {
+        use crate::config::deprecation::{DeprecationConfig, parse_route_deprecations};
+
+        let app = router(AppState::in_memory(AppConfig {
+            deprecation: DeprecationConfig {
+                routes: parse_route_deprecations(
+                    "GET /v1/users/{id} deprecated=2026-01-01T00:00:00Z \
+                     sunset=2026-07-01T00:00:00Z link=https://example.com/v2",
+                )
+                .expect("deprecations should parse"),
+            },
+            ..AppConfig::default()
+        }));
+
+        let mut headers = Vec::new();
+        for uri in [format!("/v1/users/{}", uuid::Uuid::new_v4()), "/v1/users".to_owned()] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("GET")
+                        .uri(uri)
+                        .body(axum::body::Body::empty())?,
+                )
+                .await?;
+            headers.push(
+                ["deprecation", "sunset", "link"].map(|name| {
+                    response
+                        .headers()
+                        .get(name)
+                        .map(|value| value.to_str().unwrap_or_default().to_owned())
+                }),
+            );
+        }
+
+        assert_eq!(
+            headers,
+            vec![
+                [
+                    Some("@1767225600".to_owned()),
+                    Some("Wed, 01 Jul 2026 00:00:00 GMT".to_owned()),
+                    Some("<https://example.com/v2>; rel=\"deprecation\"".to_owned()),
+                ],
+                [None, None, None],
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_user_by_id_answers_not_modified_for_current_etag() -> anyhow::Result<()> {
+        use axum::http::header::{ETAG, IF_NONE_MATCH};
+
+        let test_app = TestApp::spawn().await;
+        let app = test_app.router();
+        let user = User::new("ETag User".to_owned(), "etag@example.com".to_owned());
+        test_app.state.user_repository.create(&user).await?;
+        let uri = format!("/v1/users/{}", user.id);
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("GET")
+                    .uri(&uri)
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[ETAG].clone();
+
+        let mut statuses = Vec::new();
+        for if_none_match in [etag.to_str()?.to_owned(), r#""stale""#.to_owned()] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("GET")
+                        .uri(&uri)
+                        .header(IF_NONE_MATCH, if_none_match)
+                        .body(axum::body::Body::empty())?,
+                )
+                .await?;
+            let status = response.status();
+            let returned_etag = response.headers().get(ETAG).cloned();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+            statuses.push((status, returned_etag, body.is_empty()));
+        }
+
+        assert_eq!(
+            statuses,
+            vec![
+                (StatusCode::NOT_MODIFIED, Some(etag.clone()), true),
+                (StatusCode::OK, Some(etag), false),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_user_etag_is_per_format_and_accepted_by_if_match() -> anyhow::Result<()> {
+        use crate::config::auth::AuthConfig;
+        use axum::http::header::{ACCEPT, AUTHORIZATION, ETAG, IF_MATCH, IF_NONE_MATCH, VARY};
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+        let app = test_app.router();
+        let user = User::new("ETag User".to_owned(), "etag@example.com".to_owned());
+        test_app.state.user_repository.create(&user).await?;
+        let uri = format!("/v1/users/{}", user.id);
+        let get = |accept: &str, if_none_match: Option<&str>| {
+            let mut request = axum::http::Request::builder()
+                .method("GET")
+                .uri(&uri)
+                .header(ACCEPT, accept);
+            if let Some(if_none_match) = if_none_match {
+                request = request.header(IF_NONE_MATCH, if_none_match);
+            }
+            request.body(axum::body::Body::empty())
+        };
+
+        let json = app.clone().oneshot(get("application/json", None)?).await?;
+        let cbor = app.clone().oneshot(get("application/cbor", None)?).await?;
+        let cbor_etag = cbor.headers()[ETAG].to_str()?.to_owned();
+        assert_ne!(json.headers()[ETAG].to_str()?, cbor_etag);
+
+        let not_modified = app
+            .clone()
+            .oneshot(get("application/cbor", Some(&cbor_etag))?)
+            .await?;
+        let other_format = app
+            .clone()
+            .oneshot(get("application/json", Some(&cbor_etag))?)
+            .await?;
+        let deleted = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("DELETE")
+                    .uri(&uri)
+                    .header(AUTHORIZATION, "Bearer admin-secret")
+                    .header(IF_MATCH, &cbor_etag)
+                    .body(axum::body::Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(
+            (
+                not_modified.status(),
+                other_format.status(),
+                deleted.status()
+            ),
+            (
+                StatusCode::NOT_MODIFIED,
+                StatusCode::OK,
+                StatusCode::NO_CONTENT
+            )
+        );
+        assert_eq!(not_modified.headers()[ETAG], cbor_etag.as_str());
+        assert_eq!(not_modified.headers()[VARY], "accept");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_user_enforce_if_match() -> anyhow::Result<()> {
+        use crate::config::{auth::AuthConfig, problem_type::PRECONDITION_FAILED};
+        use application::{
+            entity_tag::user_entity_tag,
+            request_response::update_user_response::UpdateUserResponseBody,
+        };
+        use axum::http::header::{AUTHORIZATION, ETAG, IF_MATCH};
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+        let app = test_app.router();
+        let user = User::new("Admin Edited".to_owned(), "edited@example.com".to_owned());
+        test_app.state.user_repository.create(&user).await?;
+        let uri = format!("/v1/users/{}", user.id);
+        let request = |method: &str, authorization: Option<&str>, if_match: &str, body: &str| {
+            let mut request = axum::http::Request::builder()
+                .method(method)
+                .uri(&uri)
+                .header(CONTENT_TYPE, "application/json")
+                .header(IF_MATCH, if_match);
+            if let Some(authorization) = authorization {
+                request = request.header(AUTHORIZATION, authorization);
+            }
+            request.body(axum::body::Body::from(body.to_owned()))
+        };
+        let original_etag = user_entity_tag(&user);
+        let update = r#"{"name":"Renamed By Admin","email":"edited@example.com"}"#;
+
+        let unauthorized = app
+            .clone()
+            .oneshot(request("PUT", None, &original_etag, update)?)
+            .await?;
+        let updated = app
+            .clone()
+            .oneshot(request("PUT", Some("Bearer admin-secret"), &original_etag, update)?)
+            .await?;
+        assert_eq!(updated.status(), StatusCode::OK);
+        let new_etag = updated.headers()[ETAG].to_str()?.to_owned();
+        let updated = serde_json::from_slice::<UpdateUserResponseBody>(
+            &axum::body::to_bytes(updated.into_body(), usize::MAX).await?,
+        )?;
+        let stale_update = app
+            .clone()
+            .oneshot(request("PUT", Some("Bearer admin-secret"), &original_etag, update)?)
+            .await?;
+        let stale_delete = app
+            .clone()
+            .oneshot(request("DELETE", Some("Bearer admin-secret"), &original_etag, "")?)
+            .await?;
+        let stale_delete_status = stale_delete.status();
+        let stale_problem = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(stale_delete.into_body(), usize::MAX).await?,
+        )?;
+        let deleted = app
+            .clone()
+            .oneshot(request("DELETE", Some("Bearer admin-secret"), &new_etag, "")?)
+            .await?;
+
+        assert_eq!(
+            (unauthorized.status(), stale_update.status(), stale_delete_status, deleted.status()),
+            (
+                StatusCode::UNAUTHORIZED,
+                StatusCode::PRECONDITION_FAILED,
+                StatusCode::PRECONDITION_FAILED,
+                StatusCode::NO_CONTENT
+            )
+        );
+        assert_ne!(new_etag, original_etag);
+        assert_eq!(updated.name, "Renamed By Admin");
+        assert_eq!(stale_problem["type"], PRECONDITION_FAILED);
+        let missing = test_app.state.user_repository.find_by_id(&user.id).await;
+        assert!(matches!(
+            missing.unwrap_err().downcast_ref::<sqlx::Error>(),
+            Some(sqlx::Error::RowNotFound)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_user_with_idempotency_key_replays_the_first_response() -> anyhow::Result<()>
+    {
+        use crate::{
+            config::problem_type::IDEMPOTENCY_KEY_MISMATCH,
+            middleware::idempotency::{IDEMPOTENCY_KEY, IDEMPOTENT_REPLAYED},
+        };
+
+        let test_app = TestApp::spawn().await;
+        let app = test_app.router();
+        let key = uuid::Uuid::new_v4().to_string();
+        let request = |email: &str| {
+            axum::http::Request::builder()
+                .method("POST")
+                .uri("/v1/users")
+                .header(CONTENT_TYPE, "application/json")
+                .header(IDEMPOTENCY_KEY, &key)
+                .body(axum::body::Body::new(serde_json::to_string(
+                    &CreateUserRequestBody {
+                        name: "Retrying User".to_string(),
+                        email: email.to_owned(),
+                    },
+                )?))
+                .map_err(anyhow::Error::from)
+        };
+        let email = format!("test+{}@example.com", uuid::Uuid::new_v4());
+
+        let first = app.clone().oneshot(request(&email)?).await?;
+        let retry = app.clone().oneshot(request(&email)?).await?;
+        let reused = app
+            .clone()
+            .oneshot(request("someone-else@example.com")?)
+            .await?;
+
+        assert_eq!(
+            (first.status(), retry.status(), reused.status()),
+            (
+                StatusCode::CREATED,
+                StatusCode::CREATED,
+                StatusCode::UNPROCESSABLE_ENTITY
+            )
+        );
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED).is_none());
+        assert_eq!(retry.headers()[IDEMPOTENT_REPLAYED], "true");
+        let first = axum::body::to_bytes(first.into_body(), usize::MAX).await?;
+        let retry = axum::body::to_bytes(retry.into_body(), usize::MAX).await?;
+        assert_eq!(first, retry);
+        let problem = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(reused.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(problem["type"], IDEMPOTENCY_KEY_MISMATCH);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_is_scoped_to_the_client_ip() -> anyhow::Result<()> {
+        use crate::middleware::idempotency::{IDEMPOTENCY_KEY, IDEMPOTENT_REPLAYED};
+        use axum::extract::ConnectInfo;
+
+        let test_app = TestApp::spawn().await;
+        let app = test_app.router();
+        let key = uuid::Uuid::new_v4().to_string();
+        let request = |ip: [u8; 4], email: &str| {
+            axum::http::Request::builder()
+                .method("POST")
+                .uri("/v1/users")
+                .header(CONTENT_TYPE, "application/json")
+                .header(IDEMPOTENCY_KEY, &key)
+                .extension(ConnectInfo(SocketAddr::from((ip, 4000))))
+                .body(axum::body::Body::new(serde_json::to_string(
+                    &CreateUserRequestBody {
+                        name: "Retrying User".to_string(),
+                        email: email.to_owned(),
+                    },
+                )?))
+                .map_err(anyhow::Error::from)
+        };
+
+        let first = app
+            .clone()
+            .oneshot(request(
+                [203, 0, 113, 7],
+                &format!("test+{}@example.com", uuid::Uuid::new_v4()),
+            )?)
+            .await?;
+        let other = app
+            .clone()
+            .oneshot(request(
+                [198, 51, 100, 2],
+                &format!("test+{}@example.com", uuid::Uuid::new_v4()),
+            )?)
+            .await?;
+
+        assert_eq!(
+            (first.status(), other.status()),
+            (StatusCode::CREATED, StatusCode::CREATED)
+        );
+        assert!(other.headers().get(IDEMPOTENT_REPLAYED).is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_reused_with_another_format_is_rejected() -> anyhow::Result<()> {
+        use crate::{
+            config::problem_type::IDEMPOTENCY_KEY_MISMATCH,
+            middleware::idempotency::IDEMPOTENCY_KEY,
+        };
+
+        let test_app = TestApp::spawn().await;
+        let app = test_app.router();
+        let key = uuid::Uuid::new_v4().to_string();
+        let body = serde_json::to_string(&CreateUserRequestBody {
+            name: "Retrying User".to_string(),
+            email: format!("test+{}@example.com", uuid::Uuid::new_v4()),
+        })?;
+        let request = |accept: &str| {
+            axum::http::Request::builder()
+                .method("POST")
+                .uri("/v1/users")
+                .header(CONTENT_TYPE, "application/json")
+                .header(axum::http::header::ACCEPT, accept)
+                .header(IDEMPOTENCY_KEY, &key)
+                .body(axum::body::Body::new(body.clone()))
+        };
+
+        let first = app.clone().oneshot(request("application/json")?).await?;
+        let retry = app.oneshot(request("application/cbor")?).await?;
+
+        assert_eq!(
+            (first.status(), retry.status()),
+            (StatusCode::CREATED, StatusCode::UNPROCESSABLE_ENTITY)
+        );
+        let problem = serde_json::from_slice::<serde_json::Value>(
+            &axum::body::to_bytes(retry.into_body(), usize::MAX).await?,
+        )?;
+        assert_eq!(problem["type"], IDEMPOTENCY_KEY_MISMATCH);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_users_reports_each_row() -> anyhow::Result<()> {
+        use crate::config::auth::AuthConfig;
+        use application::request_response::import_users_response::{
+            ImportUserRowStatus, ImportUsersResponseBody,
+        };
+        use axum::http::header::AUTHORIZATION;
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+        let app = test_app.router();
+        let taken = User::new("Existing User".to_owned(), "taken@example.com".to_owned());
+        test_app.state.user_repository.create(&taken).await?;
+        let import = |mode: &str, content_type: &str, body: &str| {
+            axum::http::Request::builder()
+                .method("POST")
+                .uri(format!("/v1/users:import?mode={}", mode))
+                .header(AUTHORIZATION, "Bearer admin-secret")
+                .header(CONTENT_TYPE, content_type)
+                .body(axum::body::Body::from(body.to_owned()))
+        };
+        let csv = "name,email\nAda Lovelace,ada@example.com\nA,bad\nTaken,taken@example.com\n";
+        let json = r#"[{"name":"Grace Hopper","email":"grace@example.com"},{"name":"Taken","email":"taken@example.com"}]"#;
+
+        let per_row = app
+            .clone()
+            .oneshot(import("per_row", "text/csv; charset=utf-8", csv)?)
+            .await?;
+        assert_eq!(per_row.status(), StatusCode::OK);
+        let per_row = serde_json::from_slice::<ImportUsersResponseBody>(
+            &axum::body::to_bytes(per_row.into_body(), usize::MAX).await?,
+        )?;
+        let transaction = app
+            .clone()
+            .oneshot(import("transaction", "application/json", json)?)
+            .await?;
+        assert_eq!(transaction.status(), StatusCode::OK);
+        let transaction = serde_json::from_slice::<ImportUsersResponseBody>(
+            &axum::body::to_bytes(transaction.into_body(), usize::MAX).await?,
+        )?;
+        let unsupported = app
+            .clone()
+            .oneshot(import("per_row", "text/plain", csv)?)
+            .await?;
+
+        assert_eq!(
+            per_row.rows.iter().map(|row| row.status).collect::<Vec<_>>(),
+            [
+                ImportUserRowStatus::Created,
+                ImportUserRowStatus::Invalid,
+                ImportUserRowStatus::Duplicate
+            ]
+        );
+        assert_eq!((per_row.created, per_row.failed), (1, 2));
+        assert_eq!(
+            transaction
+                .rows
+                .iter()
+                .map(|row| row.status)
+                .collect::<Vec<_>>(),
+            [
+                ImportUserRowStatus::NotImported,
+                ImportUserRowStatus::Duplicate
+            ]
+        );
+        assert_eq!(unsupported.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let emails: Vec<String> = test_app
+            .state
+            .user_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|user| user.email)
+            .collect();
+        assert!(emails.contains(&"ada@example.com".to_owned()));
+        assert!(!emails.contains(&"grace@example.com".to_owned()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_users_streams_csv_and_ndjson() -> anyhow::Result<()> {
+        use crate::config::auth::AuthConfig;
+        use application::request_response::find_all_user_response::FindAllUserResponseBodyItem;
+        use axum::http::header::{AUTHORIZATION, CONTENT_DISPOSITION};
+
+        let test_app = TestApp::spawn_with(AppConfig {
+            auth: AuthConfig {
+                admin_api_token: Some("admin-secret".to_owned()),
+                ..AuthConfig::default()
+            },
+            ..AppConfig::default()
+        })
+        .await;
+        let app = test_app.router();
+        for (name, email) in [
+            ("Grace Hopper", "grace@example.com"),
+            ("Ada Lovelace", "ada@example.com"),
+        ] {
+            let user = User::new(name.to_owned(), email.to_owned());
+            test_app.state.user_repository.create(&user).await?;
+        }
+        let export = |format: &str| {
+            axum::http::Request::builder()
+                .uri(format!("/v1/users/export?format={}", format))
+                .header(AUTHORIZATION, "Bearer admin-secret")
+                .body(axum::body::Body::empty())
+        };
+
+        let csv = app.clone().oneshot(export("csv")?).await?;
+        assert_eq!(csv.status(), StatusCode::OK);
+        assert_eq!(csv.headers()[CONTENT_TYPE], "text/csv; charset=utf-8");
+        assert_eq!(
+            csv.headers()[CONTENT_DISPOSITION],
+            "attachment; filename=\"users.csv\""
+        );
+        let csv = String::from_utf8(
+            axum::body::to_bytes(csv.into_body(), usize::MAX)
+                .await?
+                .to_vec(),
+        )?;
+        let ndjson = app.clone().oneshot(export("ndjson")?).await?;
+        assert_eq!(ndjson.headers()[CONTENT_TYPE], "application/x-ndjson");
+        let ndjson = axum::body::to_bytes(ndjson.into_body(), usize::MAX).await?;
+        let unknown = app.clone().oneshot(export("xml")?).await?;
+
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("id,name,email,email_verified_at"));
+        let names: Vec<&str> = lines
+            .map(|line| line.split(',').nth(1).unwrap_or_default())
+            .collect();
+        assert_eq!(names, ["Ada Lovelace", "Grace Hopper"]);
+        let emails = ndjson
+            .split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                serde_json::from_slice::<FindAllUserResponseBodyItem>(line).map(|item| item.email)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(emails, ["ada@example.com", "grace@example.com"]);
+        assert_eq!(unknown.status(), StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
 }
@@ -1,2 +1,22 @@
+pub mod app_config;
+pub mod auth;
 pub mod connect;
-pub mod problem_type;
\ No newline at end of file
+pub mod cors;
+pub mod csrf;
+pub mod deprecation;
+pub mod idempotency;
+pub mod logging;
+pub mod mailer;
+pub mod openapi;
+pub mod outbox;
+pub mod problem_type;
+pub mod rate_limit;
+pub mod storage;
+pub mod telemetry;
+
+/// The environment variable `name` parsed as a `T`; `None` if it is unset or does not parse.
+pub(crate) fn env_parse<T: std::str::FromStr>(name: &str) -> Option<T> {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+}
@@ -0,0 +1,39 @@
+use super::{
+    auth::AuthConfig, cors::CorsConfig, csrf::CsrfConfig, deprecation::DeprecationConfig,
+    idempotency::IdempotencyConfig, mailer::MailerConfig, openapi::OpenApiConfig,
+    outbox::OutboxConfig, rate_limit::RateLimitConfig, storage::StorageConfig,
+};
+
+/// Settings read once at startup and shared with the router's layers and handlers.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AppConfig {
+    pub(crate) auth: AuthConfig,
+    pub(crate) cors: CorsConfig,
+    pub(crate) csrf: CsrfConfig,
+    pub(crate) deprecation: DeprecationConfig,
+    pub(crate) idempotency: IdempotencyConfig,
+    pub(crate) mailer: MailerConfig,
+    pub(crate) openapi: OpenApiConfig,
+    pub(crate) outbox: OutboxConfig,
+    pub(crate) rate_limit: RateLimitConfig,
+    pub(crate) storage: StorageConfig,
+}
+
+impl AppConfig {
+    pub(crate) fn from_env() -> Self {
+        dotenv::dotenv().ok();
+
+        AppConfig {
+            auth: AuthConfig::from_env(),
+            cors: CorsConfig::from_env(),
+            csrf: CsrfConfig::from_env(),
+            deprecation: DeprecationConfig::from_env(),
+            idempotency: IdempotencyConfig::from_env(),
+            mailer: MailerConfig::from_env(),
+            openapi: OpenApiConfig::from_env(),
+            outbox: OutboxConfig::from_env(),
+            rate_limit: RateLimitConfig::from_env(),
+            storage: StorageConfig::from_env(),
+        }
+    }
+}
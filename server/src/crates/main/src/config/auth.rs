@@ -0,0 +1,60 @@
+use std::fmt;
+
+use chrono::Duration;
+use domain::redact::Redacted;
+
+use super::env_parse;
+
+const DEFAULT_EMAIL_VERIFICATION_URL: &str = "http://localhost:8080/v1/auth/verify-email";
+const DEFAULT_EMAIL_VERIFICATION_TTL_SECS: i64 = 24 * 60 * 60;
+
+#[derive(Clone)]
+pub(crate) struct AuthConfig {
+    pub(crate) email_verification_url: String,
+    pub(crate) email_verification_ttl: Duration,
+    /// Bearer token for the admin-only endpoints; they are disabled while it is unset.
+    pub(crate) admin_api_token: Option<String>,
+}
+
+impl fmt::Debug for AuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthConfig")
+            .field("email_verification_url", &self.email_verification_url)
+            .field("email_verification_ttl", &self.email_verification_ttl)
+            .field(
+                "admin_api_token",
+                &self.admin_api_token.as_ref().map(|_| Redacted),
+            )
+            .finish()
+    }
+}
+
+impl AuthConfig {
+    /// Reads `EMAIL_VERIFICATION_URL`, the page verification links point at,
+    /// `EMAIL_VERIFICATION_TTL_SECS`, the lifetime of a verification link, and `ADMIN_API_TOKEN`.
+    pub(crate) fn from_env() -> Self {
+        let email_verification_url = std::env::var("EMAIL_VERIFICATION_URL")
+            .unwrap_or_else(|_| DEFAULT_EMAIL_VERIFICATION_URL.to_owned());
+        let email_verification_ttl =
+            env_parse("EMAIL_VERIFICATION_TTL_SECS").unwrap_or(DEFAULT_EMAIL_VERIFICATION_TTL_SECS);
+        let admin_api_token = std::env::var("ADMIN_API_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty());
+
+        AuthConfig {
+            email_verification_url,
+            email_verification_ttl: Duration::seconds(email_verification_ttl),
+            admin_api_token,
+        }
+    }
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        AuthConfig {
+            email_verification_url: DEFAULT_EMAIL_VERIFICATION_URL.to_owned(),
+            email_verification_ttl: Duration::seconds(DEFAULT_EMAIL_VERIFICATION_TTL_SECS),
+            admin_api_token: None,
+        }
+    }
+}
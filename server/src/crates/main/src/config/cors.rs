@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use axum::http::{
+    HeaderName, HeaderValue, Method,
+    header::{
+        ACCEPT, AUTHORIZATION, CONTENT_DISPOSITION, CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH,
+        LINK, LOCATION, RETRY_AFTER,
+    },
+};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use super::env_parse;
+use crate::middleware::{
+    csrf::X_CSRF_TOKEN,
+    deprecation::{DEPRECATION, SUNSET},
+    idempotency::{IDEMPOTENCY_KEY, IDEMPOTENT_REPLAYED},
+    request_id::X_REQUEST_ID,
+};
+
+const DEFAULT_ALLOWED_ORIGINS: &str = "http://localhost:3000";
+const DEFAULT_MAX_AGE_SECS: u64 = 600;
+
+#[derive(Debug, Clone)]
+pub(crate) struct CorsConfig {
+    pub(crate) allowed_origins: Vec<HeaderValue>,
+    pub(crate) allow_credentials: bool,
+    pub(crate) max_age: Duration,
+}
+
+impl CorsConfig {
+    /// Reads `CORS_ALLOWED_ORIGINS` (comma separated), `CORS_ALLOW_CREDENTIALS` and
+    /// `CORS_MAX_AGE_SECS`, falling back to the Next.js dev server origin.
+    pub(crate) fn from_env() -> Self {
+        let allowed_origins = std::env::var("CORS_ALLOWED_ORIGINS")
+            .unwrap_or_else(|_| DEFAULT_ALLOWED_ORIGINS.to_owned());
+        let allow_credentials = std::env::var("CORS_ALLOW_CREDENTIALS")
+            .map(|value| value.eq_ignore_ascii_case("true"))
+            .unwrap_or(true);
+        let max_age = env_parse("CORS_MAX_AGE_SECS").unwrap_or(DEFAULT_MAX_AGE_SECS);
+
+        CorsConfig {
+            allowed_origins: parse_origins(&allowed_origins),
+            allow_credentials,
+            max_age: Duration::from_secs(max_age),
+        }
+    }
+
+    pub(crate) fn layer(&self) -> CorsLayer {
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(self.allowed_origins.clone()))
+            .allow_methods([
+                Method::GET,
+                Method::POST,
+                Method::PUT,
+                Method::PATCH,
+                Method::DELETE,
+            ])
+            .allow_headers([
+                ACCEPT,
+                AUTHORIZATION,
+                CONTENT_TYPE,
+                IF_MATCH,
+                IF_NONE_MATCH,
+                IDEMPOTENCY_KEY,
+                X_CSRF_TOKEN,
+                X_REQUEST_ID,
+                HeaderName::from_static("traceparent"),
+                HeaderName::from_static("tracestate"),
+            ])
+            .expose_headers([
+                CONTENT_DISPOSITION,
+                DEPRECATION,
+                ETAG,
+                IDEMPOTENT_REPLAYED,
+                LINK,
+                LOCATION,
+                RETRY_AFTER,
+                SUNSET,
+                X_REQUEST_ID,
+            ])
+            .allow_credentials(self.allow_credentials)
+            .max_age(self.max_age)
+    }
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        CorsConfig {
+            allowed_origins: parse_origins(DEFAULT_ALLOWED_ORIGINS),
+            allow_credentials: true,
+            max_age: Duration::from_secs(DEFAULT_MAX_AGE_SECS),
+        }
+    }
+}
+
+fn parse_origins(origins: &str) -> Vec<HeaderValue> {
+    origins
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .map(|origin| {
+            HeaderValue::from_str(origin.trim_end_matches('/'))
+                .unwrap_or_else(|_| panic!("invalid CORS origin: {}", origin))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_origins() {
+        let origins = parse_origins("http://localhost:3000, https://app.example.com/ ,");
+
+        assert_eq!(
+            origins,
+            vec![
+                HeaderValue::from_static("http://localhost:3000"),
+                HeaderValue::from_static("https://app.example.com"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+
+use domain::redact::Redacted;
+use rand::RngCore;
+
+const SECRET_BYTES: usize = 32;
+
+#[derive(Clone)]
+pub(crate) struct CsrfConfig {
+    pub(crate) cookie_secure: bool,
+    /// Signs the issued tokens, so a cookie planted by another site is refused.
+    pub(crate) secret: Vec<u8>,
+}
+
+impl fmt::Debug for CsrfConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CsrfConfig")
+            .field("cookie_secure", &self.cookie_secure)
+            .field("secret", &Redacted)
+            .finish()
+    }
+}
+
+impl CsrfConfig {
+    /// Reads `CSRF_COOKIE_SECURE`; browsers accept secure cookies on `http://localhost`,
+    /// so it only needs to be disabled for plain-HTTP hosts other than localhost.
+    ///
+    /// Also reads `CSRF_SECRET`, the key tokens are signed with. It must be the same on every
+    /// instance. Only debug builds fall back to a random key per process, which invalidates the
+    /// issued tokens on every restart.
+    pub(crate) fn from_env() -> Self {
+        let cookie_secure = std::env::var("CSRF_COOKIE_SECURE")
+            .map(|value| !value.eq_ignore_ascii_case("false"))
+            .unwrap_or(true);
+        let secret = match std::env::var("CSRF_SECRET") {
+            Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+            _ if cfg!(debug_assertions) => random_secret(),
+            _ => panic!("CSRF_SECRET must be set in release builds"),
+        };
+
+        CsrfConfig {
+            cookie_secure,
+            secret,
+        }
+    }
+}
+
+impl Default for CsrfConfig {
+    fn default() -> Self {
+        CsrfConfig {
+            cookie_secure: true,
+            secret: random_secret(),
+        }
+    }
+}
+
+fn random_secret() -> Vec<u8> {
+    let mut secret = vec![0u8; SECRET_BYTES];
+    rand::rng().fill_bytes(&mut secret);
+    secret
+}
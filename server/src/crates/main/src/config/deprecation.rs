@@ -0,0 +1,148 @@
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RouteDeprecation {
+    pub(crate) method: Method,
+    pub(crate) route: String,
+    /// When the route was (or will be) deprecated, sent as the `Deprecation` header.
+    pub(crate) deprecated_at: DateTime<Utc>,
+    /// When the route stops being served, sent as the `Sunset` header.
+    pub(crate) sunset: Option<DateTime<Utc>>,
+    /// A page describing the deprecation, sent as a `Link` with `rel="deprecation"`.
+    pub(crate) link: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DeprecationConfig {
+    pub(crate) routes: Vec<RouteDeprecation>,
+}
+
+impl DeprecationConfig {
+    /// Reads `DEPRECATED_ROUTES`, a `;` separated list of
+    /// `METHOD /route deprecated=RFC3339 sunset=RFC3339 link=URL` entries keyed by the matched
+    /// route including its version prefix (e.g. `/v1/users/{id}`); `sunset` and `link` are
+    /// optional.
+    pub(crate) fn from_env() -> Self {
+        let routes = std::env::var("DEPRECATED_ROUTES").unwrap_or_default();
+
+        DeprecationConfig {
+            routes: parse_route_deprecations(&routes)
+                .unwrap_or_else(|e| panic!("invalid DEPRECATED_ROUTES: {}", e)),
+        }
+    }
+
+    pub(crate) fn find(&self, method: &Method, route: &str) -> Option<&RouteDeprecation> {
+        self.routes
+            .iter()
+            .find(|deprecation| deprecation.method == method && deprecation.route == route)
+    }
+}
+
+pub(crate) fn parse_route_deprecations(value: &str) -> Result<Vec<RouteDeprecation>, String> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let mut parts = entry.split_whitespace();
+            let method = parts
+                .next()
+                .and_then(|method| method.parse::<Method>().ok())
+                .ok_or_else(|| format!("missing or invalid method in `{}`", entry))?;
+            let route = parts
+                .next()
+                .filter(|route| route.starts_with('/'))
+                .ok_or_else(|| format!("missing or invalid route in `{}`", entry))?
+                .to_owned();
+
+            let mut deprecated_at = None;
+            let mut sunset = None;
+            let mut link = None;
+            for part in parts {
+                let (key, value) = part
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected key=value, got `{}`", part))?;
+                match key {
+                    "deprecated" => deprecated_at = Some(parse_timestamp(value)?),
+                    "sunset" => sunset = Some(parse_timestamp(value)?),
+                    "link" => link = Some(value.to_owned()),
+                    _ => return Err(format!("unknown deprecation key `{}`", key)),
+                }
+            }
+            let deprecated_at =
+                deprecated_at.ok_or_else(|| format!("missing deprecated=... in `{}`", entry))?;
+            if sunset.is_some_and(|sunset| sunset < deprecated_at) {
+                return Err(format!("sunset precedes deprecation in `{}`", entry));
+            }
+
+            Ok(RouteDeprecation {
+                method,
+                route,
+                deprecated_at,
+                sunset,
+                link,
+            })
+        })
+        .collect()
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|_| format!("invalid RFC 3339 timestamp `{}`", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_route_deprecations() {
+        let deprecations = parse_route_deprecations(
+            "GET /v1/users deprecated=2026-01-01T00:00:00Z sunset=2026-07-01T00:00:00Z \
+             link=https://example.com/v2; POST /v1/users/{id}/verification \
+             deprecated=2026-03-01T09:00:00+09:00",
+        )
+        .unwrap();
+
+        assert_eq!(
+            deprecations,
+            vec![
+                RouteDeprecation {
+                    method: Method::GET,
+                    route: "/v1/users".to_owned(),
+                    deprecated_at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+                    sunset: Some(Utc.with_ymd_and_hms(2026, 7, 1, 0, 0, 0).unwrap()),
+                    link: Some("https://example.com/v2".to_owned()),
+                },
+                RouteDeprecation {
+                    method: Method::POST,
+                    route: "/v1/users/{id}/verification".to_owned(),
+                    deprecated_at: Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap(),
+                    sunset: None,
+                    link: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_route_deprecations_rejects_invalid_entries() {
+        assert!(parse_route_deprecations("GET v1/users deprecated=2026-01-01T00:00:00Z").is_err());
+        assert!(parse_route_deprecations("GET /v1/users").is_err());
+        assert!(parse_route_deprecations("GET /v1/users deprecated=2026-01-01").is_err());
+        assert!(
+            parse_route_deprecations(
+                "GET /v1/users deprecated=2026-07-01T00:00:00Z sunset=2026-01-01T00:00:00Z"
+            )
+            .is_err()
+        );
+        assert!(
+            parse_route_deprecations("GET /v1/users deprecated=2026-01-01T00:00:00Z after=1")
+                .is_err()
+        );
+    }
+}
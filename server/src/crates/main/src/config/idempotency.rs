@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use super::env_parse;
+
+#[derive(Debug, Clone)]
+pub(crate) struct IdempotencyConfig {
+    /// How long a completed response is replayed for retries with the same key.
+    pub(crate) window: Duration,
+    /// How long a key stays locked while its first request is processed. A retry within this
+    /// time is rejected as in progress; after it, the key can be claimed again in case the first
+    /// request was abandoned mid-way.
+    pub(crate) lock: Duration,
+    /// How often keys past their window or lock are deleted from the store.
+    pub(crate) purge_interval: Duration,
+}
+
+impl IdempotencyConfig {
+    /// Reads `IDEMPOTENCY_WINDOW_SECS`, `IDEMPOTENCY_LOCK_SECS` and
+    /// `IDEMPOTENCY_PURGE_INTERVAL_SECS`.
+    pub(crate) fn from_env() -> Self {
+        let default = IdempotencyConfig::default();
+
+        IdempotencyConfig {
+            window: env_parse("IDEMPOTENCY_WINDOW_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.window),
+            lock: env_parse("IDEMPOTENCY_LOCK_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.lock),
+            purge_interval: env_parse("IDEMPOTENCY_PURGE_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.purge_interval),
+        }
+    }
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        IdempotencyConfig {
+            window: Duration::from_secs(24 * 60 * 60),
+            lock: Duration::from_secs(60),
+            purge_interval: Duration::from_secs(10 * 60),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::{path::PathBuf, sync::Arc};
+
+use domain::interface::mailer_interface::MailerInterface;
+use infrastructure::mailer::{
+    console_mailer::ConsoleMailer,
+    file_mailer::FileMailer,
+    smtp_mailer::{SmtpMailer, SmtpMailerSettings, SmtpTls},
+};
+
+use super::env_parse;
+
+const DEFAULT_MAIL_FROM: &str = "no-reply@localhost";
+const DEFAULT_MAIL_DIR: &str = "./mail";
+
+#[derive(Debug, Clone, Default)]
+pub(crate) enum MailerConfig {
+    #[default]
+    Console,
+    File {
+        dir: PathBuf,
+        from: String,
+    },
+    Smtp(SmtpMailerSettings),
+}
+
+impl MailerConfig {
+    /// Reads `MAILER` (`console`, `file` or `smtp`) and `MAIL_FROM`, plus `MAIL_DIR` for the file
+    /// backend and `SMTP_HOST`, `SMTP_PORT`, `SMTP_TLS` (`none`, `starttls` or `tls`),
+    /// `SMTP_USERNAME` and `SMTP_PASSWORD` for SMTP. Only debug builds fall back to the console
+    /// without `MAILER`, so a release build never drops mails because it was left unset.
+    pub(crate) fn from_env() -> Self {
+        let from = std::env::var("MAIL_FROM").unwrap_or_else(|_| DEFAULT_MAIL_FROM.to_owned());
+
+        match std::env::var("MAILER").as_deref() {
+            Ok("file") => MailerConfig::File {
+                dir: std::env::var("MAIL_DIR")
+                    .unwrap_or_else(|_| DEFAULT_MAIL_DIR.to_owned())
+                    .into(),
+                from,
+            },
+            Ok("smtp") => {
+                let tls = match std::env::var("SMTP_TLS").as_deref() {
+                    Ok("none") => SmtpTls::None,
+                    Ok("tls") => SmtpTls::Tls,
+                    Ok("starttls") | Err(_) => SmtpTls::StartTls,
+                    Ok(other) => panic!("invalid SMTP_TLS: {}", other),
+                };
+                let default_port = match tls {
+                    SmtpTls::None => 25,
+                    SmtpTls::StartTls => 587,
+                    SmtpTls::Tls => 465,
+                };
+
+                MailerConfig::Smtp(SmtpMailerSettings {
+                    host: std::env::var("SMTP_HOST").unwrap_or_else(|_| "localhost".to_owned()),
+                    port: env_parse("SMTP_PORT").unwrap_or(default_port),
+                    tls,
+                    username: std::env::var("SMTP_USERNAME").ok(),
+                    password: std::env::var("SMTP_PASSWORD").ok(),
+                    from,
+                })
+            }
+            Ok("console") => MailerConfig::Console,
+            Err(_) if cfg!(debug_assertions) => MailerConfig::Console,
+            Err(_) => panic!("MAILER must be set in release builds"),
+            Ok(other) => panic!("invalid MAILER: {}", other),
+        }
+    }
+
+    pub(crate) fn mailer(&self) -> Result<Arc<dyn MailerInterface + Send + Sync>, anyhow::Error> {
+        Ok(match self {
+            MailerConfig::Console => Arc::new(ConsoleMailer::new()),
+            MailerConfig::File { dir, from } => Arc::new(FileMailer::new(dir.clone(), from)?),
+            MailerConfig::Smtp(settings) => Arc::new(SmtpMailer::new(settings.clone())?),
+        })
+    }
+}
@@ -0,0 +1,16 @@
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OpenApiConfig {
+    pub(crate) ui_enabled: bool,
+}
+
+impl OpenApiConfig {
+    /// Reads `OPENAPI_UI`; when `true`, an interactive API reference is served at `/docs`.
+    /// `/openapi.json` itself is always available.
+    pub(crate) fn from_env() -> Self {
+        let ui_enabled = std::env::var("OPENAPI_UI")
+            .map(|value| value.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        OpenApiConfig { ui_enabled }
+    }
+}
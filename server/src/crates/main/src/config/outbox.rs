@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use domain::entity::outbox_message::OutboxRetryPolicy;
+
+use super::env_parse;
+
+#[derive(Debug, Clone)]
+pub(crate) struct OutboxConfig {
+    pub(crate) poll_interval: Duration,
+    pub(crate) batch_size: i64,
+    pub(crate) lease: Duration,
+    pub(crate) retry_policy: OutboxRetryPolicy,
+}
+
+impl OutboxConfig {
+    /// Reads `OUTBOX_POLL_INTERVAL_MS`, `OUTBOX_BATCH_SIZE`, `OUTBOX_LEASE_SECS`,
+    /// `OUTBOX_MAX_ATTEMPTS`, `OUTBOX_RETRY_BASE_DELAY_SECS` and `OUTBOX_RETRY_MAX_DELAY_SECS`.
+    pub(crate) fn from_env() -> Self {
+        let default = OutboxConfig::default();
+
+        OutboxConfig {
+            poll_interval: env_parse("OUTBOX_POLL_INTERVAL_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.poll_interval),
+            batch_size: env_parse("OUTBOX_BATCH_SIZE").unwrap_or(default.batch_size),
+            lease: env_parse("OUTBOX_LEASE_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.lease),
+            retry_policy: OutboxRetryPolicy {
+                max_attempts: env_parse("OUTBOX_MAX_ATTEMPTS")
+                    .unwrap_or(default.retry_policy.max_attempts),
+                base_delay: env_parse("OUTBOX_RETRY_BASE_DELAY_SECS")
+                    .map(Duration::from_secs)
+                    .unwrap_or(default.retry_policy.base_delay),
+                max_delay: env_parse("OUTBOX_RETRY_MAX_DELAY_SECS")
+                    .map(Duration::from_secs)
+                    .unwrap_or(default.retry_policy.max_delay),
+            },
+        }
+    }
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        OutboxConfig {
+            poll_interval: Duration::from_secs(1),
+            batch_size: 20,
+            lease: Duration::from_secs(60),
+            retry_policy: OutboxRetryPolicy {
+                max_attempts: 8,
+                base_delay: Duration::from_secs(10),
+                max_delay: Duration::from_secs(60 * 60),
+            },
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use std::{net::IpAddr, time::Duration};
+
+use axum::http::Method;
+use domain::entity::rate_limit::RateLimitPolicy;
+use ipnet::IpNet;
+
+const DEFAULT_RATE_LIMITS: &str =
+    "POST /v1/users ip=10/60 email=3/3600; POST /v1/users/{id}/verification ip=5/3600";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RouteRateLimit {
+    pub(crate) method: Method,
+    pub(crate) route: String,
+    pub(crate) per_ip: Option<RateLimitPolicy>,
+    pub(crate) per_email: Option<RateLimitPolicy>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimitConfig {
+    pub(crate) routes: Vec<RouteRateLimit>,
+    pub(crate) trust_forwarded_for: bool,
+    /// Proxies whose `X-Forwarded-For` hops are skipped to find the client.
+    pub(crate) trusted_proxies: Vec<IpNet>,
+}
+
+impl RateLimitConfig {
+    /// Reads `RATE_LIMITS`, a `;` separated list of `METHOD /route ip=N/SECS email=N/SECS`
+    /// entries keyed by the matched route (e.g. `/v1/users/{id}`), `RATE_LIMIT_TRUST_FORWARDED_FOR`,
+    /// which should only be enabled behind a proxy that appends to `X-Forwarded-For`, and
+    /// `RATE_LIMIT_TRUSTED_PROXIES`, a `,` separated list of the addresses or CIDR blocks of any
+    /// further proxies between that one and the client.
+    pub(crate) fn from_env() -> Self {
+        let routes =
+            std::env::var("RATE_LIMITS").unwrap_or_else(|_| DEFAULT_RATE_LIMITS.to_owned());
+        let trust_forwarded_for = std::env::var("RATE_LIMIT_TRUST_FORWARDED_FOR")
+            .map(|value| value.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        let trusted_proxies = std::env::var("RATE_LIMIT_TRUSTED_PROXIES").unwrap_or_default();
+
+        RateLimitConfig {
+            routes: parse_route_rate_limits(&routes)
+                .unwrap_or_else(|e| panic!("invalid RATE_LIMITS: {}", e)),
+            trust_forwarded_for,
+            trusted_proxies: parse_trusted_proxies(&trusted_proxies)
+                .unwrap_or_else(|e| panic!("invalid RATE_LIMIT_TRUSTED_PROXIES: {}", e)),
+        }
+    }
+
+    pub(crate) fn find(&self, method: &Method, route: &str) -> Option<&RouteRateLimit> {
+        self.routes
+            .iter()
+            .find(|limit| limit.method == method && limit.route == route)
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        RateLimitConfig {
+            routes: parse_route_rate_limits(DEFAULT_RATE_LIMITS)
+                .expect("default rate limits should parse"),
+            trust_forwarded_for: false,
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+/// Parses a `,` separated list of IP addresses and CIDR blocks; an address is a block of one.
+pub(crate) fn parse_trusted_proxies(value: &str) -> Result<Vec<IpNet>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|proxy| !proxy.is_empty())
+        .map(|proxy| {
+            proxy
+                .parse::<IpNet>()
+                .or_else(|_| proxy.parse::<IpAddr>().map(IpNet::from))
+                .map_err(|_| format!("invalid address or CIDR block `{}`", proxy))
+        })
+        .collect()
+}
+
+pub(crate) fn parse_route_rate_limits(value: &str) -> Result<Vec<RouteRateLimit>, String> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let mut parts = entry.split_whitespace();
+            let method = parts
+                .next()
+                .and_then(|method| method.parse::<Method>().ok())
+                .ok_or_else(|| format!("missing or invalid method in `{}`", entry))?;
+            let route = parts
+                .next()
+                .filter(|route| route.starts_with('/'))
+                .ok_or_else(|| format!("missing or invalid route in `{}`", entry))?
+                .to_owned();
+
+            let mut limit = RouteRateLimit {
+                method,
+                route,
+                per_ip: None,
+                per_email: None,
+            };
+            for part in parts {
+                let (key, policy) = part
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected key=N/SECS, got `{}`", part))?;
+                let policy = parse_policy(policy)?;
+                match key {
+                    "ip" => limit.per_ip = Some(policy),
+                    "email" => limit.per_email = Some(policy),
+                    _ => return Err(format!("unknown rate limit key `{}`", key)),
+                }
+            }
+
+            Ok(limit)
+        })
+        .collect()
+}
+
+fn parse_policy(value: &str) -> Result<RateLimitPolicy, String> {
+    let (capacity, period) = value
+        .split_once('/')
+        .ok_or_else(|| format!("expected N/SECS, got `{}`", value))?;
+    let capacity = capacity
+        .parse::<u32>()
+        .ok()
+        .filter(|capacity| *capacity > 0)
+        .ok_or_else(|| format!("invalid capacity `{}`", capacity))?;
+    let period = period
+        .parse::<u64>()
+        .ok()
+        .filter(|period| *period > 0)
+        .ok_or_else(|| format!("invalid period `{}`", period))?;
+
+    Ok(RateLimitPolicy::new(capacity, Duration::from_secs(period)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_route_rate_limits() {
+        let limits =
+            parse_route_rate_limits("POST /users ip=10/60 email=3/3600; GET /users/{id} ip=100/1")
+                .unwrap();
+
+        assert_eq!(
+            limits,
+            vec![
+                RouteRateLimit {
+                    method: Method::POST,
+                    route: "/users".to_owned(),
+                    per_ip: Some(RateLimitPolicy::new(10, Duration::from_secs(60))),
+                    per_email: Some(RateLimitPolicy::new(3, Duration::from_secs(3600))),
+                },
+                RouteRateLimit {
+                    method: Method::GET,
+                    route: "/users/{id}".to_owned(),
+                    per_ip: Some(RateLimitPolicy::new(100, Duration::from_secs(1))),
+                    per_email: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_route_rate_limits_rejects_invalid_entries() {
+        assert!(parse_route_rate_limits("POST users ip=1/1").is_err());
+        assert!(parse_route_rate_limits("POST /users ip=0/1").is_err());
+        assert!(parse_route_rate_limits("POST /users ip=1").is_err());
+        assert!(parse_route_rate_limits("POST /users token=1/1").is_err());
+    }
+
+    #[test]
+    fn test_parse_trusted_proxies() {
+        assert_eq!(
+            parse_trusted_proxies("10.0.0.0/8, 192.0.2.1,2001:db8::/32").unwrap(),
+            vec![
+                "10.0.0.0/8".parse::<IpNet>().unwrap(),
+                "192.0.2.1/32".parse::<IpNet>().unwrap(),
+                "2001:db8::/32".parse::<IpNet>().unwrap(),
+            ]
+        );
+        assert_eq!(parse_trusted_proxies("").unwrap(), vec![]);
+        assert!(parse_trusted_proxies("proxy.internal").is_err());
+    }
+}
@@ -0,0 +1,18 @@
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum StorageConfig {
+    #[default]
+    Postgres,
+    /// Keeps data in process memory for demos without a database; nothing survives a restart.
+    InMemory,
+}
+
+impl StorageConfig {
+    /// Reads `STORAGE` (`postgres` or `memory`).
+    pub(crate) fn from_env() -> Self {
+        match std::env::var("STORAGE").as_deref() {
+            Ok("postgres") | Err(_) => StorageConfig::Postgres,
+            Ok("memory") => StorageConfig::InMemory,
+            Ok(other) => panic!("invalid STORAGE: {}", other),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use opentelemetry::{global, propagation::TextMapCompositePropagator};
+use opentelemetry_otlp::{ExporterBuildError, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource,
+    propagation::{BaggagePropagator, TraceContextPropagator},
+    trace::SdkTracerProvider,
+};
+
+const DEFAULT_SERVICE_NAME: &str = "server";
+
+/// Builds an OTLP/HTTP trace pipeline when `OTEL_EXPORTER_OTLP_ENDPOINT` is set
+/// (e.g. `http://localhost:4318`). Without it spans only reach the log output.
+pub fn tracer_provider_from_env() -> Option<SdkTracerProvider> {
+    let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok()?;
+    let service_name =
+        std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| DEFAULT_SERVICE_NAME.to_owned());
+
+    Some(tracer_provider(&endpoint, service_name).expect("OTLP exporter should build"))
+}
+
+pub(crate) fn tracer_provider(
+    endpoint: &str,
+    service_name: String,
+) -> Result<SdkTracerProvider, ExporterBuildError> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build())
+}
+
+/// Lets incoming `traceparent`/`tracestate` and `baggage` headers continue the caller's trace.
+pub fn install_propagator() {
+    global::set_text_map_propagator(TextMapCompositePropagator::new(vec![
+        Box::new(TraceContextPropagator::new()),
+        Box::new(BaggagePropagator::new()),
+    ]));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{Router, body::Bytes, http::HeaderMap, routing::post};
+    use opentelemetry::trace::{Tracer, TracerProvider};
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_spans_are_exported_to_otlp_endpoint() -> anyhow::Result<()> {
+        let received = Arc::new(Mutex::new(Vec::<(Option<String>, Bytes)>::new()));
+        let collector = Router::new().route(
+            "/v1/traces",
+            post({
+                let received = received.clone();
+                move |headers: HeaderMap, body: Bytes| async move {
+                    let content_type = headers
+                        .get("content-type")
+                        .and_then(|value| value.to_str().ok())
+                        .map(str::to_owned);
+                    received.lock().unwrap().push((content_type, body));
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let endpoint = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, collector).await });
+
+        let provider = tracer_provider(&endpoint, "test-service".to_owned())?;
+        provider.tracer("test").in_span("exported-span", |_| {});
+        tokio::task::spawn_blocking(move || provider.shutdown()).await??;
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].0.as_deref(), Some("application/x-protobuf"));
+        let body = String::from_utf8_lossy(&received[0].1);
+        assert!(body.contains("exported-span"));
+        assert!(body.contains("test-service"));
+
+        Ok(())
+    }
+}
@@ -1,61 +1,102 @@
 use crate::{
     app::AppState,
-    config::problem_type::{DUPLICATE, INTERNAL_SERVER_ERROR, NOT_FOUND, VALIDATE},
+    config::problem_type::{
+        BAD_REQUEST, CONFLICT, DUPLICATE, EMAIL_ALREADY_VERIFIED, INTERNAL_SERVER_ERROR,
+        INVALID_VERIFICATION_TOKEN, NOT_FOUND, PRECONDITION_FAILED, UNSUPPORTED_MEDIA_TYPE,
+        VALIDATE,
+    },
+    middleware::csrf::{CSRF_COOKIE, csrf_token},
+    negotiation::{Accept, Format, Payload},
+    openapi::api_doc,
 };
 use application::{
+    entity_tag::{EntityTagCondition, user_entity_tag},
     request_response::{
         create_user_request::CreateUserRequestBody, create_user_response::CreateUserResponseBody,
+        csrf_token_response::CsrfTokenResponseBody,
+        delete_user_request::DeleteUserRequestParam,
+        export_users_request::ExportUsersRequestQuery,
+        export_users_response::ExportUsersEncoder,
+        find_audit_events_request::FindAuditEventsRequestQuery,
+        find_audit_events_response::FindAuditEventsResponseBody,
         find_all_user_response::FindAllUserResponseBody,
         find_user_by_id_request::FindUserByIdRequestParam,
         find_user_by_id_response::FindUserByIdResponseBody,
+        import_users_request::{ImportUsersRequestBody, ImportUsersRequestQuery},
+        import_users_response::ImportUsersResponseBody,
+        issue_email_verification_request::IssueEmailVerificationRequestParam,
+        problem_response::ProblemResponseBody,
+        update_user_request::{UpdateUserRequestBody, UpdateUserRequestParam},
+        update_user_response::UpdateUserResponseBody,
+        verify_email_request::VerifyEmailRequestQuery,
+        verify_email_response::VerifyEmailResponseBody,
     },
     usecase::{
         create_user::{CreateUserInput, CreateUserUsecase},
+        delete_user::{DeleteUserInput, DeleteUserUsecase},
+        export_users::ExportUsersUsecase,
         find_all_user::FindAllUserUsecase,
+        find_audit_events::{FindAuditEventsInput, FindAuditEventsUsecase},
         find_user_by_id::FindUserByIdUsecase,
+        import_users::ImportUsersUsecase,
+        issue_email_verification::IssueEmailVerificationUsecase,
+        update_user::UpdateUserUsecase,
+        verify_email::VerifyEmailUsecase,
     },
 };
 use axum::{
-    extract::{Json, Path, State},
-    http::{self, StatusCode},
-    response::IntoResponse,
+    body::{Body, Bytes},
+    extract::{Json, OriginalUri, Path, Query, State},
+    http::{self, HeaderMap, HeaderName, StatusCode},
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::{
+    CookieJar,
+    cookie::{Cookie, SameSite},
+};
+use domain::error::{
+    email_verification_error::EmailVerificationError,
+    user_error::{UserConcurrencyError, UserEmailDuplicateValidationError},
 };
-use domain::error::user_error::UserEmailDuplicateValidationError;
-use validator::Validate;
+use futures_util::{StreamExt, future, stream};
+use validator::{Validate, ValidationErrors};
 
+#[utoipa::path(
+    post,
+    path = "/v1/users",
+    tag = "users",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Replay the first response to a retried request with this key"),
+    ),
+    request_body = CreateUserRequestBody,
+    responses(
+        (status = CREATED, description = "The created user", body = CreateUserResponseBody),
+        (status = BAD_REQUEST, description = "`validate`: one member per invalid field", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = FORBIDDEN, description = "`csrf`: the request failed the CSRF checks", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "`duplicate`: the email address is already in use; `idempotency-key-in-use`: a request with the same `Idempotency-Key` is still being processed", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = UNSUPPORTED_MEDIA_TYPE, description = "`unsupported-media-type`: the body is not JSON, MessagePack or CBOR", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = UNPROCESSABLE_ENTITY, description = "`invalid-json`: required fields are missing or mistyped; `idempotency-key-mismatch`: the `Idempotency-Key` was used with a different body", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = TOO_MANY_REQUESTS, description = "`too-many-requests`: retry after `Retry-After` seconds", body = ProblemResponseBody, content_type = "application/problem+json"),
+    )
+)]
+#[tracing::instrument(skip_all)]
 pub(crate) async fn handle_create_user(
     State(state): State<AppState>,
-    axum::Json(body): Json<CreateUserRequestBody>,
+    OriginalUri(uri): OriginalUri,
+    accept: Accept,
+    Payload(body): Payload<CreateUserRequestBody>,
 ) -> Result<impl IntoResponse, problemdetails::Problem> {
     if let Err(validation_errors) = body.validate() {
-        let mut problem = problemdetails::new(StatusCode::BAD_REQUEST)
-            .with_title("Validation Error")
-            .with_type(VALIDATE)
-            .with_detail("One or more validation rules failed for the provided input")
-            .with_instance("/users");
-
-        for (field, errors) in validation_errors.field_errors() {
-            let messages: Vec<String> = errors
-                .iter()
-                .filter_map(|e| e.message.as_ref().map(|m| m.to_string()))
-                .collect();
-
-            if !messages.is_empty() {
-                problem = problem.with_value(&field, messages);
-            }
-        }
-
-        return Err(problem);
+        return Err(validation_problem(&validation_errors, uri.path()));
     }
 
     let create_user_input = CreateUserInput::from(body);
-    let mut usecase =
-        CreateUserUsecase::new(state.user_repository, state.user_email_duplicate_validator);
+    let mut usecase = CreateUserUsecase::new(state.unit_of_work, state.event_publisher);
 
     match usecase.execute(create_user_input).await {
         Ok(user) => {
             let response_body = CreateUserResponseBody::from(user);
-            Ok((StatusCode::CREATED, Json(response_body)))
+            Ok((StatusCode::CREATED, accept.respond(response_body)))
         }
         Err(e) => {
             if let Some(UserEmailDuplicateValidationError::AlreadyExists) =
@@ -65,14 +106,14 @@ pub(crate) async fn handle_create_user(
                     .with_title("Duplicate User Email")
                     .with_type(DUPLICATE)
                     .with_detail("This email address is already in use")
-                    .with_instance("/users");
+                    .with_instance(uri.path());
 
                 Err(problem)
             } else {
                 let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
                     .with_title("Internal Server Error")
                     .with_type(INTERNAL_SERVER_ERROR)
-                    .with_instance("/users");
+                    .with_instance(uri.path());
 
                 #[cfg(debug_assertions)]
                 let problem = problem.with_detail(e.to_string());
@@ -83,15 +124,26 @@ pub(crate) async fn handle_create_user(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/v1/users",
+    tag = "users",
+    responses(
+        (status = OK, description = "Every user, ordered by name", body = FindAllUserResponseBody),
+    )
+)]
+#[tracing::instrument(skip_all)]
 pub(crate) async fn handle_find_all_user(
     State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    accept: Accept,
 ) -> Result<impl IntoResponse, problemdetails::Problem> {
     let usecase = FindAllUserUsecase::new(state.user_repository);
 
     let output = usecase.execute().await.map_err(|e| {
         let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
             .with_title("Internal Server Error")
-            .with_instance("/users");
+            .with_instance(uri.path());
 
         #[cfg(debug_assertions)]
         let problem = problem.with_detail(e.to_string());
@@ -100,33 +152,505 @@ pub(crate) async fn handle_find_all_user(
     })?;
     let response_body = FindAllUserResponseBody::from(output);
 
-    Ok((StatusCode::OK, Json(response_body)))
+    Ok((StatusCode::OK, accept.respond(response_body)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/v1/users/{id}",
+    tag = "users",
+    params(
+        FindUserByIdRequestParam,
+        ("If-None-Match" = Option<String>, Header, description = "Answer `304` if the user still has one of these ETags"),
+    ),
+    responses(
+        (status = OK, description = "The user", body = FindUserByIdResponseBody, headers(("ETag" = String))),
+        (status = NOT_MODIFIED, description = "The user still has the `If-None-Match` ETag", headers(("ETag" = String))),
+        (status = BAD_REQUEST, description = "`bad-request`: `id` is not a UUID", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = NOT_FOUND, description = "`not-found`: no user has this ID", body = ProblemResponseBody, content_type = "application/problem+json"),
+    )
+)]
+#[tracing::instrument(skip_all)]
 pub(crate) async fn handle_find_user_by_id(
     State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
     Path(user_id): Path<FindUserByIdRequestParam>,
-) -> Result<impl IntoResponse, problemdetails::Problem> {
+    headers: HeaderMap,
+    accept: Accept,
+) -> Result<Response, problemdetails::Problem> {
     let usecase = FindUserByIdUsecase::new(state.user_repository);
     let user_id = user_id.id;
-    let instance_uri = format!("/users/{}", user_id);
+    let instance_uri = uri.path();
 
     match usecase.execute(user_id.clone()).await {
         Ok(user) => {
+            let etag = format_entity_tag(user_entity_tag(&user), accept.0);
+            if entity_tag_condition(&headers, http::header::IF_NONE_MATCH)
+                .is_some_and(|if_none_match| if_none_match.matches_weak(&etag))
+            {
+                return Ok((
+                    StatusCode::NOT_MODIFIED,
+                    [
+                        (http::header::ETAG, etag),
+                        (http::header::VARY, "accept".to_owned()),
+                    ],
+                )
+                    .into_response());
+            }
+
             let response_body = FindUserByIdResponseBody::from(user);
-            Ok((StatusCode::OK, Json(response_body)))
+            Ok((
+                StatusCode::OK,
+                [(http::header::ETAG, etag)],
+                accept.respond(response_body),
+            )
+                .into_response())
+        }
+        Err(e) => {
+            if let Some(sqlx_error) = e.downcast_ref::<sqlx::Error>()
+                && matches!(sqlx_error, sqlx::Error::RowNotFound)
+            {
+                let problem = problemdetails::new(StatusCode::NOT_FOUND)
+                    .with_title("User Not Found")
+                    .with_type(NOT_FOUND)
+                    .with_detail("The requested user was not found")
+                    .with_instance(instance_uri);
+
+                return Err(problem);
+            }
+
+            let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
+                .with_title("Internal Server Error")
+                .with_type(INTERNAL_SERVER_ERROR)
+                .with_instance(instance_uri);
+
+            #[cfg(debug_assertions)]
+            let problem = problem.with_detail(e.to_string());
+
+            Err(problem)
+        }
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/users/{id}",
+    tag = "admin",
+    params(
+        UpdateUserRequestParam,
+        ("If-Match" = Option<String>, Header, description = "Only update the user if it still has one of these ETags"),
+    ),
+    request_body = UpdateUserRequestBody,
+    security(("admin_token" = [])),
+    responses(
+        (status = OK, description = "The updated user", body = UpdateUserResponseBody, headers(("ETag" = String))),
+        (status = BAD_REQUEST, description = "`validate`: one member per invalid field", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = UNAUTHORIZED, description = "`unauthorized`: the admin token is missing or wrong", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = NOT_FOUND, description = "`not-found`: no user has this ID", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "`duplicate`: the email address is already in use; `conflict`: another request wrote the user concurrently", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = PRECONDITION_FAILED, description = "`precondition-failed`: the user no longer has the `If-Match` ETag", body = ProblemResponseBody, content_type = "application/problem+json"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub(crate) async fn handle_update_user(
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    Path(user_id): Path<UpdateUserRequestParam>,
+    headers: HeaderMap,
+    accept: Accept,
+    Payload(body): Payload<UpdateUserRequestBody>,
+) -> Result<impl IntoResponse, problemdetails::Problem> {
+    if let Err(validation_errors) = body.validate() {
+        return Err(validation_problem(&validation_errors, uri.path()));
+    }
+
+    let if_match = if_match(&headers);
+    let mut usecase = UpdateUserUsecase::new(state.unit_of_work, state.event_publisher);
+
+    match usecase.execute(body.into_input(user_id.id, if_match)).await {
+        Ok(user) => {
+            let etag = format_entity_tag(user_entity_tag(&user), accept.0);
+            let response_body = UpdateUserResponseBody::from(user);
+            Ok((
+                StatusCode::OK,
+                [(http::header::ETAG, etag)],
+                accept.respond(response_body),
+            ))
+        }
+        Err(e) => Err(user_write_problem(&e, uri.path())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/users/{id}",
+    tag = "admin",
+    params(
+        DeleteUserRequestParam,
+        ("If-Match" = Option<String>, Header, description = "Only delete the user if it still has one of these ETags"),
+    ),
+    security(("admin_token" = [])),
+    responses(
+        (status = NO_CONTENT, description = "The user was deleted"),
+        (status = UNAUTHORIZED, description = "`unauthorized`: the admin token is missing or wrong", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = NOT_FOUND, description = "`not-found`: no user has this ID", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = PRECONDITION_FAILED, description = "`precondition-failed`: the user no longer has the `If-Match` ETag", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "`conflict`: another request wrote the user concurrently", body = ProblemResponseBody, content_type = "application/problem+json"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub(crate) async fn handle_delete_user(
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    Path(user_id): Path<DeleteUserRequestParam>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, problemdetails::Problem> {
+    let mut usecase = DeleteUserUsecase::new(state.unit_of_work, state.event_publisher);
+    let input = DeleteUserInput {
+        id: user_id.id,
+        if_match: if_match(&headers),
+    };
+
+    match usecase.execute(input).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => Err(user_write_problem(&e, uri.path())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/users:import",
+    tag = "admin",
+    params(ImportUsersRequestQuery),
+    request_body(
+        description = "An array of `CreateUserRequestBody`, or CSV with a `name,email` header",
+        content(
+            (Vec<CreateUserRequestBody> = "application/json"),
+            (String = "text/csv"),
+        ),
+    ),
+    security(("admin_token" = [])),
+    responses(
+        (status = OK, description = "What happened to each row; in `transaction` mode nothing is created unless every row is", body = ImportUsersResponseBody),
+        (status = BAD_REQUEST, description = "`bad-request`: the file could not be read or has no rows or too many", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = UNAUTHORIZED, description = "`unauthorized`: the admin token is missing or wrong", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = FORBIDDEN, description = "`forbidden`: the admin API is disabled", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = UNSUPPORTED_MEDIA_TYPE, description = "`unsupported-media-type`: the body is neither CSV nor an array in JSON, MessagePack or CBOR", body = ProblemResponseBody, content_type = "application/problem+json"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub(crate) async fn handle_import_users(
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<ImportUsersRequestQuery>,
+    headers: HeaderMap,
+    accept: Accept,
+    body: Bytes,
+) -> Result<impl IntoResponse, problemdetails::Problem> {
+    let media_type = headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|media_type| media_type.trim().to_ascii_lowercase());
+    let encoding = media_type.as_deref().and_then(Format::from_media_type);
+    let parsed = match (media_type.as_deref(), encoding) {
+        (Some("text/csv"), _) => ImportUsersRequestBody::from_csv(&body),
+        (_, Some(Format::Json)) => ImportUsersRequestBody::from_json(&body),
+        (_, Some(encoding)) => encoding
+            .decode(&body)
+            .map_err(|e| format!("The body must be an array of users: {}", e))
+            .and_then(ImportUsersRequestBody::from_values),
+        (_, None) => {
+            return Err(problemdetails::new(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+                .with_title("Unsupported Media Type")
+                .with_type(UNSUPPORTED_MEDIA_TYPE)
+                .with_detail(
+                    "Content-Type must be text/csv, application/json, application/msgpack or \
+                     application/cbor",
+                )
+                .with_instance(uri.path()));
+        }
+    };
+    let request_body = parsed.map_err(|detail| {
+        problemdetails::new(StatusCode::BAD_REQUEST)
+            .with_title("Bad Request")
+            .with_type(BAD_REQUEST)
+            .with_detail(detail)
+            .with_instance(uri.path())
+    })?;
+
+    let mode = query.mode.unwrap_or_default();
+    let mut usecase = ImportUsersUsecase::new(state.unit_of_work, state.event_publisher);
+    let output = usecase
+        .execute(request_body.into_input(mode))
+        .await
+        .map_err(|e| {
+            let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
+                .with_title("Internal Server Error")
+                .with_type(INTERNAL_SERVER_ERROR)
+                .with_instance(uri.path());
+
+            #[cfg(debug_assertions)]
+            let problem = problem.with_detail(e.to_string());
+
+            problem
+        })?;
+
+    Ok((
+        StatusCode::OK,
+        accept.respond(ImportUsersResponseBody::new(mode, output)),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/users/export",
+    tag = "admin",
+    params(ExportUsersRequestQuery),
+    security(("admin_token" = [])),
+    responses(
+        (status = OK, description = "Every user, ordered by name, one `FindAllUserResponseBodyItem` per line. The body is streamed as the rows are read, so a failure part way through cuts it short rather than returning a problem. CSV cells starting with `=`, `+`, `-`, `@`, a tab or a carriage return are prefixed with `'` so spreadsheets show them as text", content(
+            (String = "text/csv"),
+            (String = "application/x-ndjson"),
+        )),
+        (status = BAD_REQUEST, description = "`bad-request`: `format` is missing or neither `csv` nor `ndjson`", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = UNAUTHORIZED, description = "`unauthorized`: the admin token is missing or wrong", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = FORBIDDEN, description = "`forbidden`: the admin API is disabled", body = ProblemResponseBody, content_type = "application/problem+json"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub(crate) async fn handle_export_users(
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<ExportUsersRequestQuery>,
+) -> Result<impl IntoResponse, problemdetails::Problem> {
+    let encoder = ExportUsersEncoder::new(query.format);
+    let header = encoder.header().map_err(|e| {
+        let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
+            .with_title("Internal Server Error")
+            .with_type(INTERNAL_SERVER_ERROR)
+            .with_instance(uri.path());
+
+        #[cfg(debug_assertions)]
+        let problem = problem.with_detail(e.to_string());
+
+        problem
+    })?;
+
+    let usecase = ExportUsersUsecase::new(state.user_repository);
+    let lines = usecase
+        .execute()
+        .map(move |user| user.and_then(|user| encoder.encode(user)));
+    let body = Body::from_stream(stream::once(future::ready(Ok(header))).chain(lines));
+
+    Ok((
+        StatusCode::OK,
+        [
+            (
+                http::header::CONTENT_TYPE,
+                encoder.content_type().to_owned(),
+            ),
+            (
+                http::header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", encoder.file_name()),
+            ),
+        ],
+        body,
+    ))
+}
+
+/// Maps the errors an update or delete of a user can fail with.
+fn user_write_problem(e: &anyhow::Error, instance: &str) -> problemdetails::Problem {
+    if let Some(sqlx_error) = e.downcast_ref::<sqlx::Error>()
+        && matches!(sqlx_error, sqlx::Error::RowNotFound)
+    {
+        return problemdetails::new(StatusCode::NOT_FOUND)
+            .with_title("User Not Found")
+            .with_type(NOT_FOUND)
+            .with_detail("The requested user was not found")
+            .with_instance(instance);
+    }
+
+    match e.downcast_ref::<UserConcurrencyError>() {
+        Some(UserConcurrencyError::PreconditionFailed) => {
+            return problemdetails::new(StatusCode::PRECONDITION_FAILED)
+                .with_title("Precondition Failed")
+                .with_type(PRECONDITION_FAILED)
+                .with_detail(
+                    "The user has been modified since it was read; fetch it again and retry",
+                )
+                .with_instance(instance);
+        }
+        Some(UserConcurrencyError::Conflict) => {
+            return problemdetails::new(StatusCode::CONFLICT)
+                .with_title("Conflict")
+                .with_type(CONFLICT)
+                .with_detail("The user was modified by another request while this one was applied")
+                .with_instance(instance);
+        }
+        None => {}
+    }
+
+    if let Some(UserEmailDuplicateValidationError::AlreadyExists) =
+        e.downcast_ref::<UserEmailDuplicateValidationError>()
+    {
+        return problemdetails::new(StatusCode::CONFLICT)
+            .with_title("Duplicate User Email")
+            .with_type(DUPLICATE)
+            .with_detail("This email address is already in use")
+            .with_instance(instance);
+    }
+
+    let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
+        .with_title("Internal Server Error")
+        .with_type(INTERNAL_SERVER_ERROR)
+        .with_instance(instance);
+
+    #[cfg(debug_assertions)]
+    let problem = problem.with_detail(e.to_string());
+
+    problem
+}
+
+/// A `validate` problem carrying the messages of every field that failed validation.
+fn validation_problem(
+    validation_errors: &ValidationErrors,
+    instance: &str,
+) -> problemdetails::Problem {
+    let mut problem = problemdetails::new(StatusCode::BAD_REQUEST)
+        .with_title("Validation Error")
+        .with_type(VALIDATE)
+        .with_detail("One or more validation rules failed for the provided input")
+        .with_instance(instance);
+
+    for (field, errors) in validation_errors.field_errors() {
+        let messages: Vec<String> = errors
+            .iter()
+            .filter_map(|e| e.message.as_ref().map(|m| m.to_string()))
+            .collect();
+
+        if !messages.is_empty() {
+            problem = problem.with_value(&field, messages);
+        }
+    }
+
+    problem
+}
+
+/// The tag of the user's representation in `format`. MessagePack and CBOR bodies differ from the
+/// JSON one byte for byte, so each gets its own tag, e.g. `"3+cbor"` next to JSON's `"3"`.
+fn format_entity_tag(etag: String, format: Format) -> String {
+    match format {
+        Format::Json => etag,
+        format => format!(
+            "{}+{}\"",
+            etag.trim_end_matches('"'),
+            format_subtype(format)
+        ),
+    }
+}
+
+/// `If-Match` with every tag brought back to the JSON one, since a write checks the user's
+/// version whichever format the client read it in.
+fn if_match(headers: &HeaderMap) -> Option<EntityTagCondition> {
+    entity_tag_condition(headers, http::header::IF_MATCH).map(|condition| match condition {
+        EntityTagCondition::Tags(tags) => {
+            EntityTagCondition::Tags(tags.into_iter().map(json_entity_tag).collect())
+        }
+        EntityTagCondition::Any => EntityTagCondition::Any,
+    })
+}
+
+fn json_entity_tag(tag: String) -> String {
+    for format in [Format::MessagePack, Format::Cbor] {
+        if let Some(version) = tag.strip_suffix(&format!("+{}\"", format_subtype(format))) {
+            return format!("{}\"", version);
+        }
+    }
+    tag
+}
+
+fn format_subtype(format: Format) -> &'static str {
+    format.media_type().trim_start_matches("application/")
+}
+
+/// Reads `If-Match` or `If-None-Match`, joining repeated header lines; `None` if absent.
+fn entity_tag_condition(headers: &HeaderMap, name: HeaderName) -> Option<EntityTagCondition> {
+    let values: Vec<&str> = headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(EntityTagCondition::parse(&values.join(",")))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/users/{id}/verification",
+    tag = "admin",
+    params(
+        IssueEmailVerificationRequestParam,
+        ("Idempotency-Key" = Option<String>, Header, description = "Replay the first response to a retried request with this key"),
+    ),
+    security(("admin_token" = [])),
+    responses(
+        (status = ACCEPTED, description = "A verification link was mailed to the user"),
+        (status = UNAUTHORIZED, description = "`unauthorized`: the admin token is missing or wrong", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = FORBIDDEN, description = "`forbidden`: the admin API is disabled; `csrf`: the request failed the CSRF checks", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = NOT_FOUND, description = "`not-found`: no user has this ID", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "`email-already-verified`: there is nothing left to verify; `idempotency-key-in-use`: a request with the same `Idempotency-Key` is still being processed", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = UNPROCESSABLE_ENTITY, description = "`idempotency-key-mismatch`: the `Idempotency-Key` was used with a different body", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = TOO_MANY_REQUESTS, description = "`too-many-requests`: retry after `Retry-After` seconds", body = ProblemResponseBody, content_type = "application/problem+json"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub(crate) async fn handle_issue_email_verification(
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    Path(user_id): Path<IssueEmailVerificationRequestParam>,
+) -> Result<impl IntoResponse, problemdetails::Problem> {
+    let usecase = IssueEmailVerificationUsecase::new(
+        state.user_repository,
+        state.email_verification_token_repository,
+        state.mailer,
+        state.config.auth.email_verification_url.clone(),
+        state.config.auth.email_verification_ttl,
+    );
+    let user_id = user_id.id;
+    let instance_uri = uri.path();
+
+    match usecase.execute(user_id).await {
+        Ok(user) => {
+            tracing::info!(user_id = %user.id, "email verification sent");
+            Ok(StatusCode::ACCEPTED)
         }
         Err(e) => {
-            if let Some(sqlx_error) = e.downcast_ref::<sqlx::Error>() {
-                if matches!(sqlx_error, sqlx::Error::RowNotFound) {
-                    let problem = problemdetails::new(StatusCode::NOT_FOUND)
-                        .with_title("User Not Found")
-                        .with_type(NOT_FOUND)
-                        .with_detail("The requested user was not found")
-                        .with_instance(instance_uri);
-
-                    return Err(problem);
-                }
+            if let Some(sqlx_error) = e.downcast_ref::<sqlx::Error>()
+                && matches!(sqlx_error, sqlx::Error::RowNotFound)
+            {
+                let problem = problemdetails::new(StatusCode::NOT_FOUND)
+                    .with_title("User Not Found")
+                    .with_type(NOT_FOUND)
+                    .with_detail("The requested user was not found")
+                    .with_instance(instance_uri);
+
+                return Err(problem);
+            }
+
+            if let Some(EmailVerificationError::AlreadyVerified) =
+                e.downcast_ref::<EmailVerificationError>()
+            {
+                let problem = problemdetails::new(StatusCode::CONFLICT)
+                    .with_title("Email Already Verified")
+                    .with_type(EMAIL_ALREADY_VERIFIED)
+                    .with_detail("This email address has already been verified")
+                    .with_instance(instance_uri);
+
+                return Err(problem);
             }
 
             let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
@@ -142,10 +666,193 @@ pub(crate) async fn handle_find_user_by_id(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/v1/auth/verify-email",
+    tag = "auth",
+    params(VerifyEmailRequestQuery),
+    responses(
+        (status = OK, description = "The email address is now verified", body = VerifyEmailResponseBody),
+        (status = BAD_REQUEST, description = "`invalid-verification-token`: the token is unknown, used or expired", body = ProblemResponseBody, content_type = "application/problem+json"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub(crate) async fn handle_verify_email(
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<VerifyEmailRequestQuery>,
+    accept: Accept,
+) -> Result<impl IntoResponse, problemdetails::Problem> {
+    let usecase = VerifyEmailUsecase::new(
+        state.email_verification_token_repository,
+        state.event_publisher,
+    );
+
+    match usecase.execute(query.token).await {
+        Ok(user) => {
+            let response_body = VerifyEmailResponseBody::from(user);
+            // The token travels in the URL, so keep it out of caches and `Referer` headers.
+            Ok((
+                StatusCode::OK,
+                [
+                    (http::header::CACHE_CONTROL, "no-store"),
+                    (http::header::REFERRER_POLICY, "no-referrer"),
+                ],
+                accept.respond(response_body),
+            ))
+        }
+        Err(e) => match e.downcast_ref::<EmailVerificationError>() {
+            Some(EmailVerificationError::InvalidToken) => {
+                let problem = problemdetails::new(StatusCode::BAD_REQUEST)
+                    .with_title("Invalid Verification Token")
+                    .with_type(INVALID_VERIFICATION_TOKEN)
+                    .with_detail("The verification link is invalid or has already been used")
+                    .with_instance(uri.path());
+
+                Err(problem)
+            }
+            Some(EmailVerificationError::Expired) => {
+                let problem = problemdetails::new(StatusCode::BAD_REQUEST)
+                    .with_title("Expired Verification Token")
+                    .with_type(INVALID_VERIFICATION_TOKEN)
+                    .with_detail("The verification link has expired; request a new one")
+                    .with_instance(uri.path());
+
+                Err(problem)
+            }
+            _ => {
+                let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
+                    .with_title("Internal Server Error")
+                    .with_type(INTERNAL_SERVER_ERROR)
+                    .with_instance(uri.path());
+
+                #[cfg(debug_assertions)]
+                let problem = problem.with_detail(e.to_string());
+
+                Err(problem)
+            }
+        },
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/audit-events",
+    tag = "admin",
+    params(FindAuditEventsRequestQuery),
+    security(("admin_token" = [])),
+    responses(
+        (status = OK, description = "Matching events, newest first", body = FindAuditEventsResponseBody),
+        (status = BAD_REQUEST, description = "`validate`: one member per invalid query parameter", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = UNAUTHORIZED, description = "`unauthorized`: the admin token is missing or wrong", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = FORBIDDEN, description = "`forbidden`: the admin API is disabled", body = ProblemResponseBody, content_type = "application/problem+json"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub(crate) async fn handle_find_audit_events(
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<FindAuditEventsRequestQuery>,
+    accept: Accept,
+) -> Result<impl IntoResponse, problemdetails::Problem> {
+    if let Err(validation_errors) = query.validate() {
+        return Err(validation_problem(&validation_errors, uri.path()));
+    }
+
+    let usecase = FindAuditEventsUsecase::new(state.audit_log_repository);
+
+    let output = usecase
+        .execute(FindAuditEventsInput::from(query))
+        .await
+        .map_err(|e| {
+            let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
+                .with_title("Internal Server Error")
+                .with_type(INTERNAL_SERVER_ERROR)
+                .with_instance(uri.path());
+
+            #[cfg(debug_assertions)]
+            let problem = problem.with_detail(e.to_string());
+
+            problem
+        })?;
+    let response_body = FindAuditEventsResponseBody::from(output);
+
+    Ok((
+        StatusCode::OK,
+        [(http::header::CACHE_CONTROL, "no-store")],
+        accept.respond(response_body),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/auth/csrf",
+    tag = "auth",
+    responses(
+        (status = OK, description = "The token, also set as the `csrf_token` cookie", body = CsrfTokenResponseBody),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub(crate) async fn handle_issue_csrf_token(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    accept: Accept,
+) -> impl IntoResponse {
+    let token = csrf_token(&jar, &state.config.csrf.secret);
+    let cookie = Cookie::build((CSRF_COOKIE, token.clone()))
+        .path("/")
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .secure(state.config.csrf.cookie_secure);
+
+    (
+        StatusCode::OK,
+        [(http::header::CACHE_CONTROL, "no-store")],
+        jar.add(cookie),
+        accept.respond(CsrfTokenResponseBody { token }),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "operations",
+    security(("admin_token" = [])),
+    responses(
+        (status = OK, description = "Prometheus text exposition format", body = String, content_type = "text/plain"),
+        (status = UNAUTHORIZED, description = "`unauthorized`: the admin token is missing or wrong", body = ProblemResponseBody, content_type = "application/problem+json"),
+        (status = FORBIDDEN, description = "`forbidden`: the admin API is disabled", body = ProblemResponseBody, content_type = "application/problem+json"),
+    )
+)]
+pub(crate) async fn handle_metrics(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, problemdetails::Problem> {
+    let body = state.metrics.encode().map_err(|e| {
+        let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
+            .with_title("Internal Server Error")
+            .with_type(INTERNAL_SERVER_ERROR)
+            .with_instance("/metrics");
+
+        #[cfg(debug_assertions)]
+        let problem = problem.with_detail(e.to_string());
+
+        problem
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        [(http::header::CONTENT_TYPE, prometheus::TEXT_FORMAT)],
+        body,
+    ))
+}
+
+pub(crate) async fn handle_openapi() -> impl IntoResponse {
+    Json(api_doc())
+}
+
 pub async fn handle_not_found(_req: http::Request<axum::body::Body>) -> impl IntoResponse {
-    let problem = problemdetails::new(StatusCode::NOT_FOUND)
+    problemdetails::new(StatusCode::NOT_FOUND)
         .with_title("Not Found")
         .with_type(NOT_FOUND)
-        .with_detail("The requested resource was not found.");
-    problem
+        .with_detail("The requested resource was not found.")
 }
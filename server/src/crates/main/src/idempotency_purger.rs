@@ -0,0 +1,25 @@
+use chrono::Utc;
+use tokio::task::JoinHandle;
+
+use crate::app::AppState;
+
+/// Deletes idempotency keys past their window or lock every `IdempotencyConfig::purge_interval`
+/// until the task is aborted. Runs for either storage backend, as both keep keys until purged.
+pub(crate) fn spawn(state: AppState) -> JoinHandle<()> {
+    let store = state.idempotency_store.clone();
+    let purge_interval = state.config.idempotency.purge_interval;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(purge_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            match store.purge_expired(Utc::now()).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::debug!(purged, "purged expired idempotency keys"),
+                Err(e) => tracing::error!(error = %e, "failed to purge expired idempotency keys"),
+            }
+        }
+    })
+}
@@ -1,3 +1,12 @@
 pub mod app;
+pub(crate) mod api_version;
 pub(crate) mod config;
 pub(crate) mod handler;
+pub(crate) mod idempotency_purger;
+pub(crate) mod metrics;
+pub(crate) mod middleware;
+pub(crate) mod negotiation;
+pub(crate) mod openapi;
+pub(crate) mod outbox_dispatcher;
+#[cfg(test)]
+pub(crate) mod test_app;
@@ -0,0 +1,232 @@
+use axum::http::{Method, StatusCode};
+use domain::{
+    event::domain_event::DomainEvent,
+    interface::event_subscriber_interface::EventSubscriberInterface,
+};
+use prometheus::{
+    HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder,
+    core::{Collector, Desc},
+    proto::MetricFamily,
+};
+
+#[derive(Clone)]
+pub(crate) struct Metrics {
+    registry: Registry,
+    http_requests_total: IntCounterVec,
+    http_request_duration_seconds: HistogramVec,
+    problems_total: IntCounterVec,
+    domain_events_total: IntCounterVec,
+}
+
+impl Metrics {
+    pub(crate) fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "Total number of HTTP requests"),
+            &["method", "route", "status"],
+        )
+        .expect("http_requests_total should be a valid metric");
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds",
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("http_request_duration_seconds should be a valid metric");
+        let problems_total = IntCounterVec::new(
+            Opts::new(
+                "http_problems_total",
+                "Total number of problem+json responses by problem type",
+            ),
+            &["type", "status"],
+        )
+        .expect("http_problems_total should be a valid metric");
+        let domain_events_total = IntCounterVec::new(
+            Opts::new(
+                "domain_events_total",
+                "Total number of domain events published in process",
+            ),
+            &["topic"],
+        )
+        .expect("domain_events_total should be a valid metric");
+
+        for collector in [
+            Box::new(http_requests_total.clone()) as Box<dyn Collector>,
+            Box::new(http_request_duration_seconds.clone()),
+            Box::new(problems_total.clone()),
+            Box::new(domain_events_total.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric should only be registered once");
+        }
+
+        Metrics {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            problems_total,
+            domain_events_total,
+        }
+    }
+
+    pub(crate) fn register_pool(&self, pool: sqlx::PgPool) -> Result<(), prometheus::Error> {
+        self.registry.register(Box::new(PgPoolCollector::new(pool)))
+    }
+
+    pub(crate) fn observe_request(
+        &self,
+        method: &Method,
+        route: &str,
+        status: StatusCode,
+        elapsed_seconds: f64,
+    ) {
+        let labels = [method.as_str(), route, status.as_str()];
+        self.http_requests_total.with_label_values(&labels).inc();
+        self.http_request_duration_seconds
+            .with_label_values(&labels)
+            .observe(elapsed_seconds);
+    }
+
+    pub(crate) fn observe_problem(&self, problem_type: &str, status: StatusCode) {
+        self.problems_total
+            .with_label_values(&[problem_type, status.as_str()])
+            .inc();
+    }
+
+    pub(crate) fn encode(&self) -> Result<String, prometheus::Error> {
+        TextEncoder::new().encode_to_string(&self.registry.gather())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSubscriberInterface for Metrics {
+    fn name(&self) -> &'static str {
+        "metrics"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), anyhow::Error> {
+        self.domain_events_total
+            .with_label_values(&[event.topic()])
+            .inc();
+
+        Ok(())
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads the pool statistics at scrape time instead of polling them in the background.
+struct PgPoolCollector {
+    pool: sqlx::PgPool,
+    connections: IntGaugeVec,
+    max_connections: IntGauge,
+}
+
+impl PgPoolCollector {
+    fn new(pool: sqlx::PgPool) -> Self {
+        let connections = IntGaugeVec::new(
+            Opts::new(
+                "db_pool_connections",
+                "Number of pooled database connections",
+            ),
+            &["state"],
+        )
+        .expect("db_pool_connections should be a valid metric");
+        let max_connections = IntGauge::new(
+            "db_pool_max_connections",
+            "Maximum number of pooled database connections",
+        )
+        .expect("db_pool_max_connections should be a valid metric");
+
+        PgPoolCollector {
+            pool,
+            connections,
+            max_connections,
+        }
+    }
+}
+
+impl Collector for PgPoolCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        self.connections
+            .desc()
+            .into_iter()
+            .chain(self.max_connections.desc())
+            .collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        let size = i64::from(self.pool.size());
+        let idle = self.pool.num_idle() as i64;
+        self.connections.with_label_values(&["idle"]).set(idle);
+        self.connections
+            .with_label_values(&["in_use"])
+            .set(size - idle);
+        self.max_connections
+            .set(i64::from(self.pool.options().get_max_connections()));
+
+        self.connections
+            .collect()
+            .into_iter()
+            .chain(self.max_connections.collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observe_request_is_encoded_with_labels() {
+        let metrics = Metrics::new();
+        metrics.observe_request(&Method::GET, "/users/{id}", StatusCode::OK, 0.01);
+
+        let encoded = metrics.encode().unwrap();
+        assert!(
+            encoded.contains(
+                r#"http_requests_total{method="GET",route="/users/{id}",status="200"} 1"#
+            )
+        );
+        assert!(encoded.contains("http_request_duration_seconds_bucket"));
+    }
+
+    #[test]
+    fn test_observe_problem_counts_by_type() {
+        let metrics = Metrics::new();
+        metrics.observe_problem(
+            "https://example.com/problems/duplicate",
+            StatusCode::CONFLICT,
+        );
+        metrics.observe_problem(
+            "https://example.com/problems/duplicate",
+            StatusCode::CONFLICT,
+        );
+
+        let encoded = metrics.encode().unwrap();
+        assert!(encoded.contains(
+            r#"http_problems_total{status="409",type="https://example.com/problems/duplicate"} 2"#
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_domain_events_are_counted_by_topic() {
+        let metrics = Metrics::new();
+        metrics
+            .handle(&DomainEvent::user_created(
+                domain::entity::value_object::user_id::UserId::new(),
+            ))
+            .await
+            .unwrap();
+
+        let encoded = metrics.encode().unwrap();
+        assert!(encoded.contains(r#"domain_events_total{topic="user.created"} 1"#));
+    }
+}
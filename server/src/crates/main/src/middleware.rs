@@ -0,0 +1,9 @@
+pub mod client_ip;
+pub mod csrf;
+pub mod deprecation;
+pub mod idempotency;
+pub mod rate_limit;
+pub mod request_context;
+pub mod request_id;
+pub mod require_admin;
+pub mod track_metrics;
@@ -0,0 +1,88 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::HeaderMap,
+};
+use ipnet::IpNet;
+
+use crate::config::rate_limit::RateLimitConfig;
+
+/// The address of the client, taken from `X-Forwarded-For` when the proxy in front is trusted
+/// and from the socket otherwise.
+pub(crate) fn client_ip(request: &Request, config: &RateLimitConfig) -> Option<String> {
+    if config.trust_forwarded_for
+        && let Some(ip) = forwarded_for(request.headers(), &config.trusted_proxies)
+    {
+        return Some(ip.to_string());
+    }
+
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip().to_string())
+}
+
+/// The right-most hop that is not one of `trusted_proxies`. Hops to the left of it were sent by
+/// the client and may be forged, so they are never looked at. `None` if that hop is not an IP
+/// address or every hop is a trusted proxy.
+fn forwarded_for(headers: &HeaderMap, trusted_proxies: &[IpNet]) -> Option<IpAddr> {
+    let hops: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect();
+
+    for hop in hops.into_iter().rev() {
+        let ip = hop.parse::<IpAddr>().ok()?;
+        if !trusted_proxies.iter().any(|proxy| proxy.contains(&ip)) {
+            return Some(ip);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_forwarded_for_uses_right_most_untrusted_hop() {
+        let trusted_proxies = ["10.0.0.0/8".parse::<IpNet>().unwrap()];
+        let client = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-forwarded-for", HeaderValue::from_static(value));
+            forwarded_for(&headers, &trusted_proxies)
+        };
+
+        assert_eq!(forwarded_for(&HeaderMap::new(), &trusted_proxies), None);
+        assert_eq!(
+            client("198.51.100.9, 203.0.113.7, 10.0.0.2"),
+            Some(IpAddr::from([203, 0, 113, 7]))
+        );
+        assert_eq!(
+            client("203.0.113.7, 198.51.100.9"),
+            Some(IpAddr::from([198, 51, 100, 9]))
+        );
+        assert_eq!(client("not-an-ip, 10.0.0.2"), None);
+        assert_eq!(client("10.0.0.3, 10.0.0.2"), None);
+    }
+
+    #[test]
+    fn test_client_ip_ignores_forwarded_for_unless_trusted() {
+        let request = Request::builder()
+            .header("x-forwarded-for", "203.0.113.7")
+            .extension(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 4000))))
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let mut config = RateLimitConfig::default();
+
+        assert_eq!(client_ip(&request, &config), Some("10.0.0.1".to_owned()));
+        config.trust_forwarded_for = true;
+        assert_eq!(client_ip(&request, &config), Some("203.0.113.7".to_owned()));
+    }
+}
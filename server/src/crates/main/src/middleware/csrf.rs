@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, Method, StatusCode, header::ORIGIN},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::CookieJar;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+
+use crate::config::{app_config::AppConfig, problem_type::CSRF};
+
+pub(crate) const CSRF_COOKIE: &str = "csrf_token";
+pub(crate) const X_CSRF_TOKEN: HeaderName = HeaderName::from_static("x-csrf-token");
+
+const SEC_FETCH_SITE: HeaderName = HeaderName::from_static("sec-fetch-site");
+const NONCE_BYTES: usize = 32;
+
+/// Rejects cross-site mutating requests. The `Origin` (or `Sec-Fetch-Site` when no origin is
+/// sent) must be one of `CORS_ALLOWED_ORIGINS`, and requests that carry cookies must echo the
+/// `csrf_token` cookie in the `X-CSRF-Token` header (double-submit). The token must also be
+/// signed with `CSRF_SECRET`, so a sibling subdomain that can set cookies cannot plant its own.
+pub(crate) async fn protect_csrf(
+    State(config): State<Arc<AppConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if is_safe_method(request.method()) {
+        return next.run(request).await;
+    }
+
+    let verdict = check_origin(request.headers(), &config)
+        .and_then(|()| check_double_submit(request.headers(), &config.csrf.secret));
+    if let Err(detail) = verdict {
+        tracing::warn!(reason = detail, "rejected request failing CSRF checks");
+        return problemdetails::new(StatusCode::FORBIDDEN)
+            .with_title("CSRF Validation Failed")
+            .with_type(CSRF)
+            .with_detail(detail)
+            .with_instance(request.uri().path().to_owned())
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Returns the token already held by the client if it carries a valid signature, or a fresh
+/// `<nonce>.<signature>` one.
+pub(crate) fn csrf_token(jar: &CookieJar, secret: &[u8]) -> String {
+    if let Some(cookie) = jar.get(CSRF_COOKIE)
+        && is_signed_token(cookie.value(), secret)
+    {
+        return cookie.value().to_owned();
+    }
+
+    let mut bytes = [0u8; NONCE_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    let nonce = hex(&bytes);
+    let signature = sign(&nonce, secret);
+    format!("{}.{}", nonce, signature)
+}
+
+fn is_safe_method(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+    )
+}
+
+fn check_origin(headers: &HeaderMap, config: &AppConfig) -> Result<(), &'static str> {
+    let Some(origin) = headers.get(ORIGIN) else {
+        return match headers.get(SEC_FETCH_SITE) {
+            Some(site) if site == "cross-site" => Err("Cross-site requests are not allowed"),
+            _ => Ok(()),
+        };
+    };
+
+    // Compared as a whole: a matching host alone would still let `http://` pages post to an
+    // `https://` deployment.
+    if config.cors.allowed_origins.contains(origin) {
+        Ok(())
+    } else {
+        Err("The request origin is not trusted")
+    }
+}
+
+fn check_double_submit(headers: &HeaderMap, secret: &[u8]) -> Result<(), &'static str> {
+    let jar = CookieJar::from_headers(headers);
+    // Without cookies the browser attaches no ambient credentials, so there is nothing to forge.
+    if jar.iter().next().is_none() {
+        return Ok(());
+    }
+
+    let cookie = jar.get(CSRF_COOKIE).ok_or("The CSRF cookie is missing")?;
+    let header = headers
+        .get(&X_CSRF_TOKEN)
+        .ok_or("The X-CSRF-Token header is missing")?;
+
+    if !constant_time_eq(cookie.value().as_bytes(), header.as_bytes()) {
+        return Err("The CSRF token does not match");
+    }
+    if !is_signed_token(cookie.value(), secret) {
+        return Err("The CSRF token was not issued by this server");
+    }
+
+    Ok(())
+}
+
+fn is_signed_token(token: &str, secret: &[u8]) -> bool {
+    let Some((nonce, signature)) = token.split_once('.') else {
+        return false;
+    };
+
+    nonce.len() == NONCE_BYTES * 2
+        && nonce.bytes().all(|byte| byte.is_ascii_hexdigit())
+        && constant_time_eq(sign(nonce, secret).as_bytes(), signature.as_bytes())
+}
+
+fn sign(nonce: &str, secret: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret).expect("HMAC should accept a key of any length");
+    mac.update(nonce.as_bytes());
+    hex(&mac.finalize().into_bytes())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    const SECRET: &[u8] = b"test-secret";
+
+    #[test]
+    fn test_check_origin() {
+        let config = AppConfig::default();
+
+        let mut headers = HeaderMap::new();
+        assert!(check_origin(&headers, &config).is_ok());
+
+        headers.insert(SEC_FETCH_SITE, HeaderValue::from_static("cross-site"));
+        assert!(check_origin(&headers, &config).is_err());
+
+        headers.insert(ORIGIN, HeaderValue::from_static("http://localhost:3000"));
+        assert!(check_origin(&headers, &config).is_ok());
+
+        headers.insert(ORIGIN, HeaderValue::from_static("https://evil.example.com"));
+        assert!(check_origin(&headers, &config).is_err());
+
+        headers.insert(ORIGIN, HeaderValue::from_static("https://localhost:3000"));
+        assert!(check_origin(&headers, &config).is_err());
+    }
+
+    #[test]
+    fn test_origin_matching_the_host_is_not_trusted_by_itself() {
+        let config = AppConfig::default();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ORIGIN, HeaderValue::from_static("http://127.0.0.1:8080"));
+        headers.insert("host", HeaderValue::from_static("127.0.0.1:8080"));
+
+        assert!(check_origin(&headers, &config).is_err());
+    }
+
+    #[test]
+    fn test_check_double_submit() {
+        let token = csrf_token(&CookieJar::new(), SECRET);
+
+        let mut headers = HeaderMap::new();
+        assert!(check_double_submit(&headers, SECRET).is_ok());
+
+        headers.insert("cookie", HeaderValue::from_static("session=abc"));
+        assert!(check_double_submit(&headers, SECRET).is_err());
+
+        headers.insert(
+            "cookie",
+            HeaderValue::from_str(&format!("session=abc; {}={}", CSRF_COOKIE, token)).unwrap(),
+        );
+        assert!(check_double_submit(&headers, SECRET).is_err());
+
+        headers.insert(X_CSRF_TOKEN, HeaderValue::from_static("other"));
+        assert!(check_double_submit(&headers, SECRET).is_err());
+
+        headers.insert(X_CSRF_TOKEN, HeaderValue::from_str(&token).unwrap());
+        assert!(check_double_submit(&headers, SECRET).is_ok());
+        assert!(check_double_submit(&headers, b"another-secret").is_err());
+    }
+
+    #[test]
+    fn test_double_submit_rejects_unsigned_token() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "cookie",
+            HeaderValue::from_static("session=abc; csrf_token=t0k3n"),
+        );
+        headers.insert(X_CSRF_TOKEN, HeaderValue::from_static("t0k3n"));
+
+        assert!(check_double_submit(&headers, SECRET).is_err());
+    }
+
+    #[test]
+    fn test_csrf_token_is_reused_when_signed() {
+        let issued = csrf_token(&CookieJar::new(), SECRET);
+        assert!(is_signed_token(&issued, SECRET));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "cookie",
+            HeaderValue::from_str(&format!("{}={}", CSRF_COOKIE, issued)).unwrap(),
+        );
+        let jar = CookieJar::from_headers(&headers);
+        assert_eq!(csrf_token(&jar, SECRET), issued);
+        assert_ne!(csrf_token(&jar, b"another-secret"), issued);
+    }
+}
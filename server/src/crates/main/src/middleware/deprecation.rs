@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{HeaderName, HeaderValue, header::LINK},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::config::{app_config::AppConfig, deprecation::RouteDeprecation};
+
+pub(crate) const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+pub(crate) const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Adds `Deprecation` (RFC 9745), `Sunset` (RFC 8594) and a `rel="deprecation"` `Link` to the
+/// responses of the routes listed in `DEPRECATED_ROUTES`. The route is still served as usual.
+pub(crate) async fn announce_deprecation(
+    State(config): State<Arc<AppConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let deprecation = request
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|route| config.deprecation.find(request.method(), route.as_str()))
+        .cloned();
+
+    let mut response = next.run(request).await;
+    if let Some(deprecation) = deprecation {
+        insert_headers(&mut response, &deprecation);
+    }
+
+    response
+}
+
+fn insert_headers(response: &mut Response, deprecation: &RouteDeprecation) {
+    let headers = response.headers_mut();
+    headers.insert(
+        DEPRECATION,
+        format!("@{}", deprecation.deprecated_at.timestamp())
+            .parse::<HeaderValue>()
+            .expect("a timestamp should be a valid header value"),
+    );
+    if let Some(sunset) = deprecation.sunset {
+        headers.insert(
+            SUNSET,
+            sunset
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string()
+                .parse::<HeaderValue>()
+                .expect("an HTTP date should be a valid header value"),
+        );
+    }
+    if let Some(link) = &deprecation.link
+        && let Ok(value) = HeaderValue::from_str(&format!("<{}>; rel=\"deprecation\"", link))
+    {
+        headers.append(LINK, value);
+    }
+}
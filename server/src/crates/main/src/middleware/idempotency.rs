@@ -0,0 +1,235 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{
+        HeaderMap, HeaderName, HeaderValue, Method, StatusCode,
+        header::{AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER},
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use domain::entity::idempotency::{
+    IdempotencyClaim, IdempotencyKey, IdempotencyRecord, IdempotentResponse, request_hash,
+};
+
+use crate::{
+    app::AppState,
+    config::{
+        problem_type::{
+            BAD_REQUEST, IDEMPOTENCY_KEY_IN_USE, IDEMPOTENCY_KEY_MISMATCH, INTERNAL_SERVER_ERROR,
+        },
+        rate_limit::RateLimitConfig,
+    },
+    middleware::client_ip::client_ip,
+    negotiation::Format,
+};
+
+pub(crate) const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+/// Set on responses replayed from an earlier request with the same key.
+pub(crate) const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+const MAX_KEY_LEN: usize = 255;
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Makes `POST` requests carrying an `Idempotency-Key` safe to retry: the first response is
+/// stored and replayed for later requests with the same key, route and caller, while reusing the
+/// key with a different body is rejected. Server errors are not stored, so they can be retried.
+pub(crate) async fn idempotency(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY) else {
+        return next.run(request).await;
+    };
+    let instance = request.uri().path().to_owned();
+    let principal = principal(&request, &state.config.rate_limit);
+    let Some(key) = key
+        .to_str()
+        .ok()
+        .filter(|key| !key.is_empty() && key.len() <= MAX_KEY_LEN)
+        .map(str::to_owned)
+    else {
+        return bad_request(
+            &instance,
+            "Idempotency-Key must be between 1 and 255 visible ASCII characters",
+        );
+    };
+
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_BODY_BYTES).await else {
+        return bad_request(&instance, "The request body could not be read");
+    };
+    let key = IdempotencyKey {
+        key,
+        route: format!("{} {}", parts.method, parts.uri.path()),
+        principal,
+    };
+    let request_hash = hash_request(&parts.headers, &bytes);
+    let now = Utc::now();
+
+    let claim = state
+        .idempotency_store
+        .claim(
+            &key,
+            &request_hash,
+            now,
+            now + state.config.idempotency.lock,
+        )
+        .await;
+    match claim {
+        Ok(IdempotencyClaim::Claimed) => {
+            let response = next
+                .run(Request::from_parts(parts, Body::from(bytes)))
+                .await;
+            store(&state, &key, &instance, response).await
+        }
+        Ok(IdempotencyClaim::Existing(record)) if record.request_hash != request_hash => {
+            problemdetails::new(StatusCode::UNPROCESSABLE_ENTITY)
+                .with_title("Idempotency Key Mismatch")
+                .with_type(IDEMPOTENCY_KEY_MISMATCH)
+                .with_detail("This Idempotency-Key was already used with a different request body")
+                .with_instance(instance)
+                .into_response()
+        }
+        Ok(IdempotencyClaim::Existing(IdempotencyRecord {
+            response: Some(response),
+            ..
+        })) => replay(response),
+        Ok(IdempotencyClaim::Existing(IdempotencyRecord { response: None, .. })) => {
+            let mut response = problemdetails::new(StatusCode::CONFLICT)
+                .with_title("Idempotency Key In Use")
+                .with_type(IDEMPOTENCY_KEY_IN_USE)
+                .with_detail("A request with this Idempotency-Key is still being processed")
+                .with_instance(instance)
+                .into_response();
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from_static("1"));
+            response
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "failed to claim idempotency key");
+            internal_server_error(&instance, &e.to_string())
+        }
+    }
+}
+
+/// Who sent the request, so the same key from two callers never collides. Requests carry no
+/// session yet, so bearer tokens are told apart by their hash and everyone else by client IP.
+/// Anonymous callers behind one NAT or proxy still share a scope and would be sent each other's
+/// responses if they picked the same key, which is why keys should be random.
+fn principal(request: &Request, config: &RateLimitConfig) -> String {
+    if let Some(authorization) = request.headers().get(AUTHORIZATION) {
+        return format!("token:{}", request_hash(authorization.as_bytes()));
+    }
+
+    match client_ip(request, config) {
+        Some(ip) => format!("ip:{}", ip),
+        None => "anonymous".to_owned(),
+    }
+}
+
+/// Hashes the body together with its media type and the format the response is negotiated in,
+/// so a retry that changes `Content-Type` or `Accept` is not answered in the first format.
+fn hash_request(headers: &HeaderMap, body: &[u8]) -> String {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|media_type| media_type.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    let accept = Format::from_accept(headers).map_or("", Format::media_type);
+    request_hash(&[content_type.as_bytes(), accept.as_bytes(), body].join(&b'\n'))
+}
+
+/// Stores `response` for replay, or releases the key if it should not be replayed.
+async fn store(
+    state: &AppState,
+    key: &IdempotencyKey,
+    instance: &str,
+    response: Response,
+) -> Response {
+    if response.status().is_server_error() {
+        release(state, key).await;
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!(error = %e, "failed to buffer response for idempotent replay");
+            release(state, key).await;
+            return internal_server_error(instance, &e.to_string());
+        }
+    };
+    let stored = IdempotentResponse {
+        status: parts.status.as_u16(),
+        headers: parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.as_str().to_owned(), value.to_str().ok()?.to_owned()))
+            })
+            .collect(),
+        body: bytes.to_vec(),
+    };
+    let expires_at = Utc::now() + state.config.idempotency.window;
+    if let Err(e) = state
+        .idempotency_store
+        .complete(key, &stored, expires_at)
+        .await
+    {
+        tracing::error!(error = %e, "failed to store idempotent response");
+        release(state, key).await;
+    }
+
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+async fn release(state: &AppState, key: &IdempotencyKey) {
+    if let Err(e) = state.idempotency_store.release(key).await {
+        tracing::warn!(error = %e, "failed to release idempotency key");
+    }
+}
+
+fn replay(stored: IdempotentResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let headers = response.headers_mut();
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            headers.append(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    response
+}
+
+fn bad_request(instance: &str, detail: &str) -> Response {
+    problemdetails::new(StatusCode::BAD_REQUEST)
+        .with_title("Bad Request")
+        .with_type(BAD_REQUEST)
+        .with_detail(detail.to_owned())
+        .with_instance(instance.to_owned())
+        .into_response()
+}
+
+/// `detail` is only shown in debug builds.
+#[cfg_attr(not(debug_assertions), allow(unused_variables))]
+fn internal_server_error(instance: &str, detail: &str) -> Response {
+    let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
+        .with_title("Internal Server Error")
+        .with_type(INTERNAL_SERVER_ERROR)
+        .with_instance(instance.to_owned());
+
+    #[cfg(debug_assertions)]
+    let problem = problem.with_detail(detail.to_owned());
+
+    problem.into_response()
+}
@@ -0,0 +1,124 @@
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    http::{StatusCode, header::RETRY_AFTER},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use domain::entity::rate_limit::{RateLimitDecision, RateLimitPolicy};
+
+use crate::{
+    app::AppState,
+    config::problem_type::{BAD_REQUEST, INTERNAL_SERVER_ERROR, TOO_MANY_REQUESTS},
+    middleware::client_ip::client_ip,
+    negotiation::Format,
+};
+
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Applies the configured per-route token buckets, keyed by client IP and, for routes with an
+/// email policy, by the `email` member of the body in whichever format [`Format`] decodes.
+///
+/// The email bucket is shared by every caller on purpose: it caps the mails one address can be
+/// sent however many IPs ask. The trade-off is that anyone can use up an address's bucket and
+/// lock its owner out of signing up until it refills.
+pub(crate) async fn rate_limit(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(route) = request.extensions().get::<MatchedPath>() else {
+        return next.run(request).await;
+    };
+    let Some(limit) = state
+        .config
+        .rate_limit
+        .find(request.method(), route.as_str())
+    else {
+        return next.run(request).await;
+    };
+    let scope = format!("{} {}", limit.method, limit.route);
+    let instance = request.uri().path().to_owned();
+
+    if let Some(policy) = &limit.per_ip {
+        // Lumping every such request into one shared bucket would let one caller starve the
+        // rest, so they are left to the email limit instead. `run` always serves with the
+        // socket address, so this only happens to a router mounted without it.
+        match client_ip(&request, &state.config.rate_limit) {
+            Some(ip) => {
+                let key = format!("{}|ip:{}", scope, ip);
+                if let Err(response) = acquire(&state, &key, policy, &instance).await {
+                    return response;
+                }
+            }
+            None => tracing::warn!(route = %scope, "skipped the IP rate limit without a client IP"),
+        }
+    }
+
+    let Some(policy) = &limit.per_email else {
+        return next.run(request).await;
+    };
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_BODY_BYTES).await else {
+        return problemdetails::new(StatusCode::BAD_REQUEST)
+            .with_title("Bad Request")
+            .with_type(BAD_REQUEST)
+            .with_detail("The request body could not be read")
+            .with_instance(instance)
+            .into_response();
+    };
+    let email = Format::from_content_type(&parts.headers)
+        .and_then(|format| format.decode::<serde_json::Value>(&bytes).ok())
+        .and_then(|body| {
+            body["email"]
+                .as_str()
+                .map(|email| email.trim().to_lowercase())
+        });
+    if let Some(email) = email {
+        let key = format!("{}|email:{}", scope, email);
+        if let Err(response) = acquire(&state, &key, policy, &instance).await {
+            return response;
+        }
+    }
+
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+async fn acquire(
+    state: &AppState,
+    key: &str,
+    policy: &RateLimitPolicy,
+    instance: &str,
+) -> Result<(), Response> {
+    match state.rate_limit_store.acquire(key, policy).await {
+        Ok(RateLimitDecision::Allowed { .. }) => Ok(()),
+        Ok(RateLimitDecision::Limited { retry_after }) => {
+            // Round up so a client that waits exactly `Retry-After` seconds gets a token.
+            let retry_after_secs =
+                retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            let mut response = problemdetails::new(StatusCode::TOO_MANY_REQUESTS)
+                .with_title("Too Many Requests")
+                .with_type(TOO_MANY_REQUESTS)
+                .with_detail("Too many requests have been made. Please retry later.")
+                .with_instance(instance.to_owned())
+                .into_response();
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, retry_after_secs.into());
+            Err(response)
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "failed to apply rate limit");
+            let problem = problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
+                .with_title("Internal Server Error")
+                .with_type(INTERNAL_SERVER_ERROR)
+                .with_instance(instance.to_owned());
+
+            #[cfg(debug_assertions)]
+            let problem = problem.with_detail(e.to_string());
+
+            Err(problem.into_response())
+        }
+    }
+}
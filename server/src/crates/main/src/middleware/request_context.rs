@@ -0,0 +1,29 @@
+use axum::{
+    extract::{Request, State},
+    http::header::USER_AGENT,
+    middleware::Next,
+    response::Response,
+};
+use domain::entity::request_context::RequestContext;
+
+use crate::{app::AppState, middleware::client_ip::client_ip};
+
+/// Makes the caller's IP and user agent available to the audit log for the rest of the request.
+pub(crate) async fn capture_request_context(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let context = RequestContext {
+        // Set by `require_admin` on the admin routes; nothing else authenticates a caller yet.
+        actor_id: None,
+        ip: client_ip(&request, &state.config.rate_limit),
+        user_agent: request
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned),
+    };
+
+    application::request_context::scope(context, next.run(request)).await
+}
@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Request, State},
+    http::{
+        HeaderValue, StatusCode,
+        header::{AUTHORIZATION, WWW_AUTHENTICATE},
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use domain::entity::request_context::{ADMIN_TOKEN_ACTOR_ID, RequestContext};
+
+use crate::{
+    app::AppState,
+    config::problem_type::{FORBIDDEN, UNAUTHORIZED},
+    middleware::csrf::constant_time_eq,
+};
+
+/// Admits only requests bearing the configured `ADMIN_API_TOKEN`, and records them in the audit
+/// log as made by [`ADMIN_TOKEN_ACTOR_ID`].
+pub(crate) async fn require_admin(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let instance = request.uri().path().to_owned();
+    let Some(expected) = &state.config.auth.admin_api_token else {
+        return problemdetails::new(StatusCode::FORBIDDEN)
+            .with_title("Forbidden")
+            .with_type(FORBIDDEN)
+            .with_detail("The admin API is disabled")
+            .with_instance(instance)
+            .into_response();
+    };
+
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match token {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => {
+            let context = RequestContext {
+                actor_id: Some(ADMIN_TOKEN_ACTOR_ID),
+                ..application::request_context::current()
+            };
+            application::request_context::scope(context, next.run(request)).await
+        }
+        _ => {
+            tracing::warn!(path = %instance, "rejected admin request without a valid token");
+            let mut response = problemdetails::new(StatusCode::UNAUTHORIZED)
+                .with_title("Unauthorized")
+                .with_type(UNAUTHORIZED)
+                .with_detail("A valid admin bearer token is required")
+                .with_instance(instance)
+                .into_response();
+            response
+                .headers_mut()
+                .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            response
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::time::Instant;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    http::header::CONTENT_TYPE,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::metrics::Metrics;
+
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+pub(crate) async fn track_metrics(
+    State(metrics): State<Metrics>,
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let route = matched_path
+        .as_ref()
+        .map(MatchedPath::as_str)
+        .unwrap_or(UNMATCHED_ROUTE)
+        .to_owned();
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+    let status = response.status();
+    metrics.observe_request(&method, &route, status, start.elapsed().as_secs_f64());
+
+    let is_problem = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .is_some_and(|content_type| content_type == "application/problem+json");
+    if !is_problem {
+        return response;
+    }
+
+    // Problem bodies are small, so buffering them to read the `type` member is cheap.
+    let (parts, body) = response.into_parts();
+    let bytes = axum::body::to_bytes(body, usize::MAX)
+        .await
+        .unwrap_or_default();
+    let problem_type = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|problem| problem["type"].as_str().map(str::to_owned))
+        .unwrap_or_else(|| "about:blank".to_owned());
+    metrics.observe_problem(&problem_type, status);
+
+    Response::from_parts(parts, Body::from(bytes))
+}
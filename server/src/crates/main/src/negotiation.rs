@@ -0,0 +1,295 @@
+//! Reads and writes the bodies of the API as JSON, MessagePack or CBOR, so handlers deal in the
+//! `request_response` types and never in a particular encoding.
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, FromRequestParts, Json, OriginalUri, Request},
+    http::{Extensions, HeaderMap, HeaderValue, StatusCode, Uri, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::config::problem_type::{
+    BAD_REQUEST, INTERNAL_SERVER_ERROR, NOT_ACCEPTABLE, UNSUPPORTED_MEDIA_TYPE,
+};
+
+/// An encoding of request and response bodies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    Json,
+    MessagePack,
+    Cbor,
+}
+
+impl Format {
+    /// In order of preference, for an `Accept` header that rates several of them equally.
+    pub(crate) const ALL: [Format; 3] = [Format::Json, Format::MessagePack, Format::Cbor];
+
+    pub(crate) fn media_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::MessagePack => "application/msgpack",
+            Format::Cbor => "application/cbor",
+        }
+    }
+
+    /// Also takes `application/x-msgpack`, which older MessagePack libraries still send.
+    pub(crate) fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type.to_ascii_lowercase().as_str() {
+            "application/json" => Some(Format::Json),
+            "application/msgpack" | "application/x-msgpack" => Some(Format::MessagePack),
+            "application/cbor" => Some(Format::Cbor),
+            _ => None,
+        }
+    }
+
+    /// The format named by the `Content-Type` of `headers`, ignoring its parameters.
+    pub(crate) fn from_content_type(headers: &HeaderMap) -> Option<Self> {
+        let content_type = headers.get(header::CONTENT_TYPE)?.to_str().ok()?;
+        let media_type = content_type.split(';').next()?.trim();
+        Format::from_media_type(media_type)
+    }
+
+    /// The format the `Accept` header of `headers` prefers; see [`preferred_format`].
+    pub(crate) fn from_accept(headers: &HeaderMap) -> Option<Self> {
+        let accept = headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+        preferred_format(&accept)
+    }
+
+    pub(crate) fn encode<T: Serialize>(self, value: &T) -> anyhow::Result<Vec<u8>> {
+        match self {
+            Format::Json => Ok(serde_json::to_vec(value)?),
+            // Maps rather than arrays, so fields are matched by name like they are in JSON.
+            Format::MessagePack => Ok(rmp_serde::to_vec_named(value)?),
+            Format::Cbor => {
+                let mut body = Vec::new();
+                ciborium::into_writer(value, &mut body)?;
+                Ok(body)
+            }
+        }
+    }
+
+    pub(crate) fn decode<T: DeserializeOwned>(self, body: &[u8]) -> anyhow::Result<T> {
+        match self {
+            Format::Json => Ok(serde_json::from_slice(body)?),
+            Format::MessagePack => Ok(rmp_serde::from_slice(body)?),
+            Format::Cbor => Ok(ciborium::from_reader(body)?),
+        }
+    }
+}
+
+/// The response format the `Accept` header prefers, or JSON without one. Rejects the request
+/// with a `not-acceptable` problem if none of the formats is acceptable, before the handler runs.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Accept(pub(crate) Format);
+
+impl Accept {
+    pub(crate) fn respond<T: Serialize>(self, body: T) -> Negotiated<T> {
+        Negotiated(self.0, body)
+    }
+}
+
+impl<S> FromRequestParts<S> for Accept
+where
+    S: Send + Sync,
+{
+    type Rejection = problemdetails::Problem;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Format::from_accept(&parts.headers)
+            .map(Accept)
+            .ok_or_else(|| {
+                problemdetails::new(StatusCode::NOT_ACCEPTABLE)
+                .with_title("Not Acceptable")
+                .with_type(NOT_ACCEPTABLE)
+                .with_detail(
+                    "Accept must allow application/json, application/msgpack or application/cbor",
+                )
+                .with_instance(instance(&parts.extensions, &parts.uri))
+            })
+    }
+}
+
+/// Picks the format with the highest `q` among the media ranges of `accept`, rating each format
+/// by the most specific range that matches it. An empty header accepts anything.
+fn preferred_format(accept: &str) -> Option<Format> {
+    let ranges: Vec<(&str, f32)> = accept
+        .split(',')
+        .filter_map(|range| {
+            let mut params = range.split(';');
+            let media_range = params.next()?.trim();
+            let q = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (!media_range.is_empty()).then_some((media_range, q))
+        })
+        .collect();
+    if ranges.is_empty() {
+        return Some(Format::Json);
+    }
+
+    let mut preferred: Option<(Format, f32)> = None;
+    for format in Format::ALL {
+        let q = ranges
+            .iter()
+            .filter_map(|&(range, q)| {
+                let specificity = if Format::from_media_type(range) == Some(format) {
+                    3
+                } else if range.eq_ignore_ascii_case("application/*") {
+                    2
+                } else if range == "*/*" {
+                    1
+                } else {
+                    return None;
+                };
+                Some((specificity, q))
+            })
+            .max_by_key(|&(specificity, _)| specificity)
+            .map(|(_, q)| q);
+        if let Some(q) = q
+            && q > 0.0
+            && preferred.is_none_or(|(_, best)| q > best)
+        {
+            preferred = Some((format, q));
+        }
+    }
+    preferred.map(|(format, _)| format)
+}
+
+/// A response body in the format an [`Accept`] chose; takes the place of `Json` in handlers.
+#[derive(Debug)]
+pub(crate) struct Negotiated<T>(pub(crate) Format, pub(crate) T);
+
+impl<T: Serialize> IntoResponse for Negotiated<T> {
+    fn into_response(self) -> Response {
+        let Negotiated(format, body) = self;
+        match format.encode(&body) {
+            Ok(body) => (
+                [
+                    (
+                        header::CONTENT_TYPE,
+                        HeaderValue::from_static(format.media_type()),
+                    ),
+                    (header::VARY, HeaderValue::from_static("accept")),
+                ],
+                body,
+            )
+                .into_response(),
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    media_type = format.media_type(),
+                    "failed to encode response body"
+                );
+                problemdetails::new(StatusCode::INTERNAL_SERVER_ERROR)
+                    .with_title("Internal Server Error")
+                    .with_type(INTERNAL_SERVER_ERROR)
+                    .into_response()
+            }
+        }
+    }
+}
+
+/// A request body decoded according to its `Content-Type`; takes the place of `Json` in
+/// handlers. Anything other than JSON, MessagePack or CBOR is an `unsupported-media-type`
+/// problem.
+#[derive(Debug)]
+pub(crate) struct Payload<T>(pub(crate) T);
+
+impl<T, S> FromRequest<S> for Payload<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match Format::from_content_type(req.headers()) {
+            // `Json` keeps rejecting bodies with the statuses the problem mapping in `app` expects.
+            Some(Format::Json) => Json::<T>::from_request(req, state)
+                .await
+                .map(|Json(body)| Payload(body))
+                .map_err(IntoResponse::into_response),
+            Some(format) => {
+                let instance = instance(req.extensions(), req.uri());
+                let body = Bytes::from_request(req, state)
+                    .await
+                    .map_err(IntoResponse::into_response)?;
+                format.decode(&body).map(Payload).map_err(|e| {
+                    problemdetails::new(StatusCode::BAD_REQUEST)
+                        .with_title("Bad Request")
+                        .with_type(BAD_REQUEST)
+                        .with_detail(format!("The body is not valid {}: {}", format.media_type(), e))
+                        .with_instance(instance)
+                        .into_response()
+                })
+            }
+            None => Err(problemdetails::new(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+                .with_title("Unsupported Media Type")
+                .with_type(UNSUPPORTED_MEDIA_TYPE)
+                .with_detail(
+                    "Content-Type must be application/json, application/msgpack or application/cbor",
+                )
+                .with_instance(instance(req.extensions(), req.uri()))
+                .into_response()),
+        }
+    }
+}
+
+/// The path as the client sent it; `uri` lacks the version prefix inside a nested router.
+fn instance(extensions: &Extensions, uri: &Uri) -> String {
+    extensions
+        .get::<OriginalUri>()
+        .map_or(uri, |OriginalUri(uri)| uri)
+        .path()
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preferred_format_follows_q_then_specificity() {
+        let cases = [
+            ("", Some(Format::Json)),
+            ("*/*", Some(Format::Json)),
+            ("application/cbor", Some(Format::Cbor)),
+            (
+                "application/x-msgpack, application/json;q=0.5",
+                Some(Format::MessagePack),
+            ),
+            (
+                "application/json;q=0.5, application/*;q=0.8",
+                Some(Format::MessagePack),
+            ),
+            (
+                "application/*, application/json;q=0",
+                Some(Format::MessagePack),
+            ),
+            ("text/html, */*;q=0.1", Some(Format::Json)),
+            ("text/html, application/xml", None),
+            ("application/json;q=0", None),
+        ];
+
+        for (accept, expected) in cases {
+            assert_eq!(preferred_format(accept), expected, "Accept: {}", accept);
+        }
+    }
+
+    #[test]
+    fn test_formats_round_trip() {
+        let value = serde_json::json!({"name": "Ada Lovelace", "email": null, "version": 1});
+
+        for format in Format::ALL {
+            let body = format.encode(&value).unwrap();
+            assert_eq!(format.decode::<serde_json::Value>(&body).unwrap(), value);
+        }
+    }
+}
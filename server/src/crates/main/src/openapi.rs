@@ -0,0 +1,235 @@
+use std::sync::LazyLock;
+
+use application::request_response::{
+    create_user_request::CreateUserRequestBody,
+    create_user_response::CreateUserResponseBody,
+    csrf_token_response::CsrfTokenResponseBody,
+    export_users_request::ExportUsersFormat,
+    find_all_user_response::{FindAllUserResponseBody, FindAllUserResponseBodyItem},
+    find_audit_events_response::{FindAuditEventsResponseBody, FindAuditEventsResponseBodyItem},
+    find_user_by_id_response::FindUserByIdResponseBody,
+    import_users_request::ImportUsersMode,
+    import_users_response::{
+        ImportUserRowStatus, ImportUsersResponseBody, ImportUsersResponseBodyRow,
+    },
+    problem_response::ProblemResponseBody,
+    update_user_request::UpdateUserRequestBody,
+    update_user_response::UpdateUserResponseBody,
+    verify_email_response::VerifyEmailResponseBody,
+};
+use utoipa::{
+    Modify, OpenApi,
+    openapi::{
+        self, Content, RefOr, Schema,
+        security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    },
+};
+
+use crate::{config::problem_type, handler, negotiation::Format};
+
+/// `handle_find_audit_events` repeats this name as a literal; `#[utoipa::path(security(...))]`
+/// does not accept constants.
+const ADMIN_TOKEN_SCHEME: &str = "admin_token";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "axum-nextjs-auth",
+        description = "Errors are returned as `application/problem+json` (RFC 9457); `type` \
+                       tells them apart. Mutating requests must pass the CSRF checks: a trusted \
+                       `Origin` and, when cookies are sent, an `X-CSRF-Token` header echoing \
+                       the token from `GET /v1/auth/csrf`. Writes to a user honour `If-Match` \
+                       with the `ETag` its reads return. JSON bodies may instead be sent and \
+                       requested as `application/msgpack` or `application/cbor`; an `Accept` \
+                       allowing none of the three is answered with `406` `not-acceptable`."
+    ),
+    paths(
+        handler::handle_create_user,
+        handler::handle_find_all_user,
+        handler::handle_find_user_by_id,
+        handler::handle_update_user,
+        handler::handle_delete_user,
+        handler::handle_export_users,
+        handler::handle_import_users,
+        handler::handle_issue_email_verification,
+        handler::handle_issue_csrf_token,
+        handler::handle_verify_email,
+        handler::handle_find_audit_events,
+        handler::handle_metrics,
+    ),
+    components(schemas(
+        ProblemResponseBody,
+        CreateUserRequestBody,
+        CreateUserResponseBody,
+        CsrfTokenResponseBody,
+        ExportUsersFormat,
+        FindAllUserResponseBody,
+        FindAllUserResponseBodyItem,
+        FindAuditEventsResponseBody,
+        FindAuditEventsResponseBodyItem,
+        FindUserByIdResponseBody,
+        ImportUsersMode,
+        ImportUserRowStatus,
+        ImportUsersResponseBody,
+        ImportUsersResponseBodyRow,
+        UpdateUserRequestBody,
+        UpdateUserResponseBody,
+        VerifyEmailResponseBody,
+    )),
+    modifiers(&ProblemTypes, &AdminToken, &BinaryEncodings),
+    tags(
+        (name = "users"),
+        (name = "auth"),
+        (name = "admin", description = "Requires the `ADMIN_API_TOKEN` bearer token"),
+        (name = "operations"),
+    )
+)]
+struct ApiDoc;
+
+/// The document is built once; it only depends on code, never on configuration.
+pub(crate) fn api_doc() -> &'static openapi::OpenApi {
+    static API_DOC: LazyLock<openapi::OpenApi> = LazyLock::new(ApiDoc::openapi);
+    &API_DOC
+}
+
+/// Lists the problem types from `config::problem_type` as the allowed values of
+/// `ProblemResponseBody.type`.
+struct ProblemTypes;
+
+impl Modify for ProblemTypes {
+    fn modify(&self, openapi: &mut openapi::OpenApi) {
+        let Some(RefOr::T(Schema::Object(problem))) = openapi
+            .components
+            .as_mut()
+            .and_then(|components| components.schemas.get_mut("ProblemResponseBody"))
+        else {
+            return;
+        };
+        if let Some(RefOr::T(Schema::Object(r#type))) = problem.properties.get_mut("type") {
+            r#type.enum_values = Some(problem_type::ALL.iter().map(|&t| t.into()).collect());
+        }
+    }
+}
+
+struct AdminToken;
+
+impl Modify for AdminToken {
+    fn modify(&self, openapi: &mut openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_default()
+            .add_security_scheme(
+                ADMIN_TOKEN_SCHEME,
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+    }
+}
+
+/// Offers every JSON request and response body as MessagePack and CBOR as well, which
+/// `negotiation` reads and writes with the same schema.
+struct BinaryEncodings;
+
+impl Modify for BinaryEncodings {
+    fn modify(&self, openapi: &mut openapi::OpenApi) {
+        for path in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut path.get,
+                &mut path.put,
+                &mut path.post,
+                &mut path.delete,
+            ];
+            for operation in operations.into_iter().flatten() {
+                if let Some(request) = &mut operation.request_body {
+                    let encodings =
+                        binary_encodings(request.content.get(Format::Json.media_type()));
+                    request.content.extend(encodings);
+                }
+                for response in operation.responses.responses.values_mut() {
+                    if let RefOr::T(response) = response {
+                        let encodings =
+                            binary_encodings(response.content.get(Format::Json.media_type()));
+                        response.content.extend(encodings);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `json` again under the media type of each binary encoding, if there is a JSON body at all.
+fn binary_encodings(json: Option<&Content>) -> Vec<(String, Content)> {
+    let Some(json) = json else {
+        return Vec::new();
+    };
+    [Format::MessagePack, Format::Cbor]
+        .into_iter()
+        .map(|format| (format.media_type().to_owned(), json.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_doc_is_openapi_3_1() {
+        let doc = serde_json::to_value(api_doc()).expect("document should serialize");
+
+        assert_eq!(doc["openapi"], "3.1.0");
+    }
+
+    #[test]
+    fn test_problem_type_lists_every_problem_type() {
+        let doc = serde_json::to_value(api_doc()).expect("document should serialize");
+
+        assert_eq!(
+            doc["components"]["schemas"]["ProblemResponseBody"]["properties"]["type"]["enum"],
+            serde_json::json!(problem_type::ALL)
+        );
+    }
+
+    #[test]
+    fn test_json_bodies_are_offered_in_binary_encodings() {
+        let doc = serde_json::to_value(api_doc()).expect("document should serialize");
+        let create_user = &doc["paths"]["/v1/users"]["post"];
+
+        for media_type in [
+            "application/json",
+            "application/msgpack",
+            "application/cbor",
+        ] {
+            assert_eq!(
+                create_user["requestBody"]["content"][media_type]["schema"]["$ref"],
+                "#/components/schemas/CreateUserRequestBody"
+            );
+            assert_eq!(
+                create_user["responses"]["201"]["content"][media_type]["schema"]["$ref"],
+                "#/components/schemas/CreateUserResponseBody"
+            );
+        }
+        assert!(
+            create_user["responses"]["400"]["content"]
+                .get("application/msgpack")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_create_user_documents_validator_constraints() {
+        let doc = serde_json::to_value(api_doc()).expect("document should serialize");
+        let properties = &doc["components"]["schemas"]["CreateUserRequestBody"]["properties"];
+
+        assert_eq!(
+            (
+                &properties["name"]["minLength"],
+                &properties["name"]["maxLength"],
+                &properties["email"]["format"]
+            ),
+            (
+                &serde_json::json!(2),
+                &serde_json::json!(40),
+                &serde_json::json!("email")
+            )
+        );
+    }
+}
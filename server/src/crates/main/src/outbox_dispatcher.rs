@@ -0,0 +1,45 @@
+use application::{
+    event_publisher::InProcessEventPublisher, outbox_handler::DomainEventOutboxHandler,
+    usecase::dispatch_outbox::DispatchOutboxUsecase,
+};
+use infrastructure::repository::outbox_repository_with_pg::OutboxRepositoryWithPg;
+use tokio::task::JoinHandle;
+
+use crate::app::AppState;
+
+/// Polls the outbox until the task is aborted. A full batch is followed by another poll right
+/// away so a backlog drains without waiting for the interval.
+pub(crate) fn spawn(state: AppState, pool: sqlx::PgPool) -> JoinHandle<()> {
+    let config = state.config.outbox.clone();
+    // Subscribers that must not miss an event consume it from the outbox rather than from
+    // `AppState::event_publisher`, which is not durable.
+    let durable_subscribers =
+        InProcessEventPublisher::new().subscribe(state.mail_event_subscriber());
+    let handler = DomainEventOutboxHandler::new(durable_subscribers);
+    let usecase = DispatchOutboxUsecase::new(
+        OutboxRepositoryWithPg::new(pool),
+        handler,
+        config.retry_policy,
+        config.batch_size,
+        config.lease,
+    );
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            loop {
+                match usecase.execute().await {
+                    Ok(output) if output.claimed() as i64 >= config.batch_size => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        tracing::error!(error = %e, "failed to dispatch outbox");
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
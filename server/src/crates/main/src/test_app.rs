@@ -0,0 +1,61 @@
+//! Spins up the application against its own freshly migrated schema, so a test sees only the rows
+//! it wrote itself and can run in parallel with every other test.
+
+use std::net::SocketAddr;
+
+use axum::Router;
+use infrastructure::testing::test_database::TestDatabase;
+
+use crate::{
+    app::{AppState, router},
+    config::app_config::AppConfig,
+};
+
+pub(crate) struct TestApp {
+    /// Swap fields (e.g. the mailer) before calling [`TestApp::router`] to change what it serves.
+    pub(crate) state: AppState,
+    _database: TestDatabase,
+}
+
+impl TestApp {
+    pub(crate) async fn spawn() -> Self {
+        Self::spawn_with(AppConfig::default()).await
+    }
+
+    pub(crate) async fn spawn_with(config: AppConfig) -> Self {
+        let database = TestDatabase::create()
+            .await
+            .expect("test database should be created");
+        let state = AppState::new(database.pool().clone(), config);
+
+        Self {
+            state,
+            _database: database,
+        }
+    }
+
+    pub(crate) fn router(&self) -> Router {
+        router(self.state.clone())
+    }
+
+    /// Serves [`TestApp::router`] on an ephemeral local port, for tests that go through a real
+    /// HTTP client.
+    pub(crate) async fn serve(&self) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let address = listener
+            .local_addr()
+            .expect("listener should have an address");
+        let app = self.router();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+
+        address
+    }
+}